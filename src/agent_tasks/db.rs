@@ -25,6 +25,7 @@ use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use super::{epoch, AgentTask, BatchBuilder, Provider, Source, TaskSource, TaskState};
+use crate::remote::{Message, Role};
 
 pub(super) fn hermes_db(home: &Path) -> PathBuf {
     home.join(".hermes").join("state.db")
@@ -288,6 +289,117 @@ pub(super) fn load_opencode(batches: &mut BatchBuilder, db: &Path, session: &str
     }
 }
 
+// ---------------------------------------------------------------------------
+// Conversation
+// ---------------------------------------------------------------------------
+
+/// Every spoken turn of one conversation, oldest first, in the shape the
+/// JSONL journals are read into (see `remote::read_more`) — for
+/// `workbench grep`, which searches all of them alike. Tool traffic stays
+/// out: hermes stores tool output as turns of its own, and what ran is
+/// already in the words around it.
+pub fn conversation(provider: Provider, db: &Path, session: &str) -> Vec<Message> {
+    let Some(conn) = open(db) else {
+        return Vec::new();
+    };
+    match provider {
+        Provider::Hermes => hermes_conversation(&conn, session),
+        Provider::OpenCode => opencode_conversation(&conn, session),
+        _ => None,
+    }
+    .unwrap_or_default()
+}
+
+fn spoken(role: &str, text: String, at: Option<chrono::DateTime<Utc>>) -> Option<Message> {
+    let role = match role {
+        "user" => Role::You,
+        "assistant" => Role::Agent,
+        _ => return None,
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| Message {
+        role,
+        text: text.to_string(),
+        at: at.map(|at| at.to_rfc3339()),
+    })
+}
+
+fn hermes_conversation(conn: &Connection, session: &str) -> Option<Vec<Message>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT role, content, timestamp FROM messages \
+             WHERE session_id = ?1 AND coalesce(active, 1) = 1 \
+             ORDER BY timestamp, id",
+        )
+        .ok()?;
+    let rows = stmt
+        .query_map([session], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, f64>(2)?,
+            ))
+        })
+        .ok()?;
+    Some(
+        rows.flatten()
+            .filter_map(|(role, content, stamp)| spoken(&role, content?, epoch(stamp, false)))
+            .collect(),
+    )
+}
+
+/// opencode splits a message into parts; the text parts, joined, are what
+/// was said.
+fn opencode_conversation(conn: &Connection, session: &str) -> Option<Vec<Message>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT m.id, m.data, m.time_created, p.data FROM message m \
+             JOIN part p ON p.message_id = m.id \
+             WHERE m.session_id = ?1 \
+             ORDER BY m.time_created, m.id, p.time_created, p.id",
+        )
+        .ok()?;
+    let rows = stmt
+        .query_map([session], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })
+        .ok()?;
+
+    // A message's parts arrive together; gather them until the next one.
+    let mut messages = Vec::new();
+    let mut current: Option<(String, String, i64, Vec<String>)> = None;
+    let mut finish = |current: Option<(String, String, i64, Vec<String>)>| {
+        if let Some((_, data, created, texts)) = current {
+            let role = serde_json::from_str::<Value>(&data)
+                .ok()
+                .and_then(|v| v.get("role").and_then(Value::as_str).map(str::to_string))
+                .unwrap_or_default();
+            messages.extend(spoken(&role, texts.join("\n"), epoch(created as f64, true)));
+        }
+    };
+    for (id, data, created, part) in rows.flatten() {
+        let text = serde_json::from_str::<Value>(&part).ok().and_then(|part| {
+            (part.get("type").and_then(Value::as_str) == Some("text"))
+                .then(|| part.get("text").and_then(Value::as_str).map(str::to_string))
+                .flatten()
+        });
+        match &mut current {
+            Some((current_id, _, _, texts)) if *current_id == id => texts.extend(text),
+            _ => {
+                finish(current.take());
+                current = Some((id, data, created, text.into_iter().collect()));
+            }
+        }
+    }
+    finish(current);
+    Some(messages)
+}
+
 /// Text of the newest user message no later than `before` (0 = no bound).
 fn last_prompt(
     conn: &Connection,
@@ -11,7 +11,8 @@ use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
-use super::{json_id, timestamp, AgentTask, BatchBuilder, TaskSource, TaskState};
+use super::{json_id, timestamp, AgentTask, BatchBuilder, Spend, TaskSource, TaskState};
+use crate::models::TokenUsage;
 
 pub(super) fn claude_projects_root(home: &Path) -> PathBuf {
     home.join(".claude").join("projects")
@@ -195,6 +196,38 @@ pub(super) fn ingest_claude(batches: &mut BatchBuilder, v: &Value) {
     }
 }
 
+/// Tokens an assistant line reports spending.
+///
+/// Unlike the model and the task list, a subagent's lines count here: the
+/// session asked for that work and pays for it, whichever model did it.
+pub(super) fn ingest_claude_usage(spend: &mut Spend, v: &Value) {
+    if v.get("type").and_then(Value::as_str) != Some("assistant") {
+        return;
+    }
+    let Some(message) = v.get("message") else {
+        return;
+    };
+    let Some(usage) = message.get("usage") else {
+        return;
+    };
+    let count = |key: &str| usage.get(key).and_then(Value::as_u64).unwrap_or(0);
+    let tokens = TokenUsage {
+        input: count("input_tokens"),
+        output: count("output_tokens"),
+        cache_read: count("cache_read_input_tokens"),
+        cache_write: count("cache_creation_input_tokens"),
+    };
+    let model = message.get("model").and_then(Value::as_str).unwrap_or("");
+    // No id (older harnesses) means nothing to deduplicate against; the
+    // line's own uuid keeps it from matching the reply before it.
+    let id = message
+        .get("id")
+        .or_else(|| v.get("uuid"))
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    spend.reply(id, model, tokens);
+}
+
 /// "Task #3 created successfully: ..." → the id for a provisional task.
 fn apply_task_id(batches: &mut BatchBuilder, block: &Value) {
     let Some(tool_id) = block.get("tool_use_id").and_then(Value::as_str) else {
@@ -367,6 +400,35 @@ pub(super) fn ingest_codex(batches: &mut BatchBuilder, v: &Value) {
     }
 }
 
+/// Codex's `token_count` events carry the session's running total, so the
+/// last one is the spend so far. `model` is the one the latest
+/// `turn_context` named: the tokens since the previous report were its.
+pub(super) fn ingest_codex_usage(spend: &mut Spend, v: &Value, model: Option<&str>) {
+    if v.get("type").and_then(Value::as_str) != Some("event_msg")
+        || v.pointer("/payload/type").and_then(Value::as_str) != Some("token_count")
+    {
+        return;
+    }
+    // `info` is null on the rate-limit-only reports codex sends at startup.
+    let Some(total) = v
+        .pointer("/payload/info/total_token_usage")
+        .filter(|t| t.is_object())
+    else {
+        return;
+    };
+    let count = |key: &str| total.get(key).and_then(Value::as_u64).unwrap_or(0);
+    // OpenAI counts cached prompt tokens inside `input_tokens`; they are
+    // billed at the cache rate, so split them out.
+    let cached = count("cached_input_tokens");
+    let tokens = TokenUsage {
+        input: count("input_tokens").saturating_sub(cached),
+        output: count("output_tokens"),
+        cache_read: cached,
+        cache_write: 0,
+    };
+    spend.running_total(model.unwrap_or(""), tokens);
+}
+
 /// Codex has no "use this id" flag, so its rollout has to be recognised.
 ///
 /// Workbench always starts codex with `resume` — either a conversation id or
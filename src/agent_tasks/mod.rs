@@ -31,11 +31,12 @@ mod files;
 
 use chrono::{DateTime, Utc};
 use serde_json::Value;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use crate::models::AgentType;
+use crate::models::{AgentType, TokenUsage};
 
+pub use db::conversation;
 pub use files::claude_log_for_session;
 
 /// Where a task sits in the agent's list.
@@ -110,6 +111,18 @@ impl Provider {
         }
     }
 
+    /// The agent's command, which is also how `for_command` reads it back —
+    /// the form a provider travels in through the roster.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Provider::Claude => "claude",
+            Provider::Codex => "codex",
+            Provider::OpenCode => "opencode",
+            Provider::Hermes => "hermes",
+            Provider::Pi => "pi",
+        }
+    }
+
     /// Append-only log file vs. SQLite store.
     fn is_file_log(&self) -> bool {
         matches!(self, Provider::Claude | Provider::Codex | Provider::Pi)
@@ -181,6 +194,8 @@ pub struct TaskTracker {
     /// named it. Both agents let you change model mid-session and write the
     /// new one on the next turn, so the last mention wins.
     model: Option<String>,
+    /// Tokens the journal says this session has spent (see `Spend`).
+    spend: Spend,
 }
 
 impl TaskTracker {
@@ -192,6 +207,7 @@ impl TaskTracker {
             change_token: None,
             batches: BatchBuilder::default(),
             model: None,
+            spend: Spend::default(),
         }
     }
 
@@ -213,6 +229,12 @@ impl TaskTracker {
         self.model.as_deref()
     }
 
+    /// Tokens spent so far, by the model that spent them. Empty for a
+    /// provider whose store does not record usage.
+    pub fn spend(&self) -> &BTreeMap<String, TokenUsage> {
+        &self.spend.by_model
+    }
+
     /// The agent's own conversation id, read off the store we resolved. This
     /// is what `claude --resume` / `codex resume` / `hermes --resume` need to
     /// restore THIS session's history rather than the directory's most recent.
@@ -241,17 +263,18 @@ impl TaskTracker {
         tracker
     }
 
+    /// A tracker that has already seen `tokens` spent by `model` (tests).
+    #[cfg(test)]
+    pub fn having_spent(provider: Provider, model: &str, tokens: TokenUsage) -> Self {
+        let mut tracker = Self::new(provider);
+        tracker.spend.add(model, tokens);
+        tracker
+    }
+
     /// Feed one already-parsed log line, as `tail_file` would (tests).
     #[cfg(test)]
     fn ingest_line(&mut self, value: &Value) {
-        if let Some(model) = files::model_in(self.provider, value) {
-            self.model = Some(model);
-        }
-        match self.provider {
-            Provider::Claude => files::ingest_claude(&mut self.batches, value),
-            Provider::Codex => files::ingest_codex(&mut self.batches, value),
-            _ => {}
-        }
+        self.ingest(value);
     }
 
     /// Locate the store if we haven't yet, then read whatever is new.
@@ -295,6 +318,10 @@ impl TaskTracker {
         // The next store located may be a different conversation, and a model
         // carried over from the last one would be a confident wrong answer.
         self.model = None;
+        // Spend is the opposite: the money went whichever journal recorded
+        // it, and `/clear` moving the session to a new one does not give it
+        // back. Keep the totals; forget only where we were in the old file.
+        self.spend.rollover();
     }
 
     /// Parse the bytes appended since the last pass.
@@ -307,9 +334,11 @@ impl TaskTracker {
             Err(_) => return,
         };
         if len < self.offset {
-            // Truncated/replaced underneath us — start over.
+            // Truncated/replaced underneath us — start over. The same lines
+            // will be read again, so their tokens must not be counted twice.
             self.offset = 0;
             self.batches = BatchBuilder::default();
+            self.spend = Spend::default();
         }
         if len == self.offset {
             return;
@@ -337,14 +366,7 @@ impl TaskTracker {
                     }
                     consumed += n as u64;
                     if let Ok(value) = serde_json::from_str::<Value>(line.trim_end()) {
-                        if let Some(model) = files::model_in(self.provider, &value) {
-                            self.model = Some(model);
-                        }
-                        match self.provider {
-                            Provider::Claude => files::ingest_claude(&mut self.batches, &value),
-                            Provider::Codex => files::ingest_codex(&mut self.batches, &value),
-                            _ => {}
-                        }
+                        self.ingest(&value);
                     }
                 }
                 Err(_) => break,
@@ -353,6 +375,25 @@ impl TaskTracker {
         self.offset = consumed;
     }
 
+    /// Apply one journal line: the model it names, the tokens it spent, and
+    /// whatever it does to the task list.
+    fn ingest(&mut self, value: &Value) {
+        if let Some(model) = files::model_in(self.provider, value) {
+            self.model = Some(model);
+        }
+        match self.provider {
+            Provider::Claude => {
+                files::ingest_claude(&mut self.batches, value);
+                files::ingest_claude_usage(&mut self.spend, value);
+            }
+            Provider::Codex => {
+                files::ingest_codex(&mut self.batches, value);
+                files::ingest_codex_usage(&mut self.spend, value, self.model.as_deref());
+            }
+            _ => {}
+        }
+    }
+
     /// SQLite stores have no append offset, so rebuild the list when a cheap
     /// probe says something moved. Sessions are small — a few dozen rows.
     fn requery_db(&mut self, db: &Path, session: &str) {
@@ -489,6 +530,65 @@ impl BatchBuilder {
     }
 }
 
+/// Running token totals for one session, split by model.
+///
+/// The two file formats count differently — Claude per reply, Codex as a
+/// running total — so each keeps a little cursor state alongside the sums.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Spend {
+    by_model: BTreeMap<String, TokenUsage>,
+    /// Claude only: the reply last counted, as (message id, model, usage).
+    /// A reply with several content blocks is journalled as several lines,
+    /// each repeating its usage so far; counting each line would bill the
+    /// reply once per block.
+    last_reply: Option<(String, String, TokenUsage)>,
+    /// Codex only: its running total at the last `token_count`.
+    reported_total: TokenUsage,
+}
+
+impl Spend {
+    fn add(&mut self, model: &str, tokens: TokenUsage) {
+        if tokens.is_empty() {
+            return;
+        }
+        *self.by_model.entry(model.to_string()).or_default() += tokens;
+    }
+
+    /// One line of a Claude reply. A repeat of the reply already counted
+    /// adds only what grew since.
+    pub(crate) fn reply(&mut self, id: &str, model: &str, usage: TokenUsage) {
+        let counted = match &self.last_reply {
+            Some((last_id, _, counted)) if last_id == id => *counted,
+            _ => TokenUsage::default(),
+        };
+        self.add(model, usage.since(&counted));
+        self.last_reply = Some((id.to_string(), model.to_string(), usage));
+    }
+
+    /// A running total (Codex). What it grew by since the last report goes to
+    /// the model answering now.
+    ///
+    /// A total that went backwards is a new process counting from zero —
+    /// codex appends to the rollout it resumes, but not to its counter — so
+    /// all of it is new.
+    pub(crate) fn running_total(&mut self, model: &str, total: TokenUsage) {
+        let last = self.reported_total;
+        let restarted = total.input < last.input
+            || total.output < last.output
+            || total.cache_read < last.cache_read;
+        let grown = if restarted { total } else { total.since(&last) };
+        self.add(model, grown);
+        self.reported_total = total;
+    }
+
+    /// The tracker moved to another journal: keep what was spent, forget
+    /// where we were in the old file.
+    fn rollover(&mut self) {
+        self.last_reply = None;
+        self.reported_total = TokenUsage::default();
+    }
+}
+
 /// Filters out the machinery that also arrives as "user" text: system
 /// reminders, slash-command envelopes, tool output, interrupt notices.
 fn is_real_prompt(text: &str) -> bool {
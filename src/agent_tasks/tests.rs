@@ -494,6 +494,48 @@ fn hermes_todo_calls_become_a_task_list_under_their_prompt() {
     assert_eq!(batch.tasks[1].state, TaskState::Pending);
 }
 
+/// What `workbench grep` searches for an opencode agent: each message's text
+/// parts as one message, and nothing from the tool traffic between them.
+#[test]
+fn an_opencode_conversation_reads_as_whole_messages() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = opencode_fixture(dir.path());
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    let parts: [(&str, i64, &str, serde_json::Value); 4] = [
+        ("m1", 1000, r#"{"role":"user"}"#, serde_json::json!({"type": "text", "text": "plan the migration"})),
+        ("m2", 2000, r#"{"role":"assistant"}"#, serde_json::json!({"type": "text", "text": "Two steps."})),
+        ("m2", 2000, r#"{"role":"assistant"}"#, serde_json::json!({"type": "tool", "tool": "bash"})),
+        ("m2", 2000, r#"{"role":"assistant"}"#, serde_json::json!({"type": "text", "text": "Add, then backfill."})),
+    ];
+    for (n, (message, created, data, part)) in parts.iter().enumerate() {
+        conn.execute(
+            "INSERT OR IGNORE INTO message (id, session_id, time_created, time_updated, data) \
+             VALUES (?1, 'ses_1', ?2, ?2, ?3)",
+            rusqlite::params![message, created, data],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO part (id, message_id, session_id, time_created, time_updated, data) \
+             VALUES (?1, ?2, 'ses_1', ?3, ?3, ?4)",
+            rusqlite::params![format!("prt_{n}"), message, created + n as i64, part.to_string()],
+        )
+        .unwrap();
+    }
+
+    let messages = conversation(Provider::OpenCode, &db, "ses_1");
+
+    let said: Vec<(crate::remote::Role, &str)> =
+        messages.iter().map(|m| (m.role, m.text.as_str())).collect();
+    assert_eq!(
+        said,
+        vec![
+            (crate::remote::Role::You, "plan the migration"),
+            (crate::remote::Role::Agent, "Two steps.\nAdd, then backfill."),
+        ]
+    );
+    assert!(messages[0].at.is_some());
+}
+
 #[test]
 fn hermes_prefers_a_cwd_match_and_never_reuses_a_claimed_session() {
     let dir = tempfile::tempdir().unwrap();
@@ -1229,6 +1271,126 @@ fn a_located_codex_rollout_names_the_model_it_answers_with() {
     assert_eq!(t.model(), Some(FIXTURE_CODEX_MODEL));
 }
 
+// ---------------------------------------------------------------------------
+// What a session has spent
+// ---------------------------------------------------------------------------
+
+/// One journal line of a Claude reply. A reply with several content blocks is
+/// written as several of these, sharing `id` and repeating its usage.
+fn claude_reply_usage(id: &str, model: &str, input: u64, output: u64, sidechain: bool) -> String {
+    serde_json::json!({
+        "type": "assistant",
+        "isSidechain": sidechain,
+        "message": {
+            "id": id,
+            "model": model,
+            "content": [{"type": "text", "text": "ok"}],
+            "usage": {
+                "input_tokens": input,
+                "output_tokens": output,
+                "cache_read_input_tokens": 1000,
+                "cache_creation_input_tokens": 200
+            }
+        }
+    })
+    .to_string()
+}
+
+fn codex_token_count(input: u64, cached: u64, output: u64) -> String {
+    serde_json::json!({
+        "type": "event_msg",
+        "payload": {"type": "token_count", "info": {"total_token_usage": {
+            "input_tokens": input,
+            "cached_input_tokens": cached,
+            "output_tokens": output,
+            "reasoning_output_tokens": 0,
+            "total_tokens": input + output
+        }}}
+    })
+    .to_string()
+}
+
+/// The overcount this guards against: a reply with a thinking block, a text
+/// block and a tool call is three journal lines carrying one reply's usage.
+#[test]
+fn a_claude_reply_split_across_lines_is_counted_once() {
+    let mut t = TaskTracker::new(Provider::Claude);
+    feed(
+        &mut t,
+        &[
+            &claude_reply_usage("msg_1", "claude-opus-5", 10, 5, false),
+            &claude_reply_usage("msg_1", "claude-opus-5", 10, 40, false),
+            &claude_reply_usage("msg_1", "claude-opus-5", 10, 40, false),
+            &claude_reply_usage("msg_2", "claude-opus-5", 3, 7, false),
+        ],
+    );
+
+    let spent = t.spend()["claude-opus-5"];
+    assert_eq!(spent.input, 13);
+    assert_eq!(spent.output, 47, "the reply's final count, not the sum of its lines");
+    assert_eq!(spent.cache_read, 2000);
+    assert_eq!(spent.cache_write, 400);
+}
+
+/// A subagent's turns are not the session's model, but they are its bill.
+#[test]
+fn a_subagents_spend_counts_under_its_own_model() {
+    let mut t = TaskTracker::new(Provider::Claude);
+    feed(
+        &mut t,
+        &[
+            &claude_reply_usage("msg_1", "claude-opus-5", 10, 5, false),
+            &claude_reply_usage("msg_2", "claude-haiku-4-5", 100, 50, true),
+        ],
+    );
+
+    assert_eq!(t.model(), Some("claude-opus-5"));
+    assert_eq!(t.spend()["claude-haiku-4-5"].input, 100);
+    assert_eq!(t.spend().len(), 2);
+}
+
+/// Codex reports a running total, and the tokens it grew by belong to
+/// whichever model the latest `turn_context` named.
+#[test]
+fn codex_running_totals_are_split_by_the_model_that_spent_them() {
+    let mut t = TaskTracker::new(Provider::Codex);
+    feed(
+        &mut t,
+        &[
+            &codex_turn_context("gpt-5.6-sol"),
+            &codex_token_count(1000, 400, 100),
+            &codex_token_count(3000, 2000, 300),
+            &codex_turn_context("gpt-5.6"),
+            &codex_token_count(4000, 2500, 350),
+        ],
+    );
+
+    let sol = t.spend()["gpt-5.6-sol"];
+    assert_eq!(sol.input, 1000, "cached tokens are split out of input");
+    assert_eq!(sol.cache_read, 2000);
+    assert_eq!(sol.output, 300);
+    let after = t.spend()["gpt-5.6"];
+    assert_eq!((after.input, after.cache_read, after.output), (500, 500, 50));
+}
+
+/// Codex appends to the rollout it resumes but starts its counter again, so a
+/// total smaller than the last is a fresh count rather than nothing new.
+#[test]
+fn a_codex_counter_that_restarts_is_not_lost() {
+    let mut t = TaskTracker::new(Provider::Codex);
+    feed(
+        &mut t,
+        &[
+            &codex_turn_context("gpt-5.6"),
+            &codex_token_count(5000, 0, 500),
+            &codex_token_count(100, 0, 10),
+        ],
+    );
+
+    assert_eq!(t.spend()["gpt-5.6"].input, 5100);
+    assert_eq!(t.spend()["gpt-5.6"].output, 510);
+}
+
 // ---------------------------------------------------------------------------
 // pi
 // ---------------------------------------------------------------------------
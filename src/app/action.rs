@@ -1,8 +1,10 @@
 use crate::app::state::ConfigTab;
+use crate::app::state::{DiffNav, DiffSubject};
 use crate::app::state::TaskEdit;
 use crate::app::state::ToastLevel;
-use crate::git::DiffStat;
-use crate::models::AgentType;
+use crate::app::merge::MergeConflict;
+use crate::git::{DiffStat, FileDiff};
+use crate::models::{AgentType, MergeStrategy, VerifyOutcome};
 use crossterm::event::KeyEvent;
 use ratatui::style::Color;
 use std::collections::HashMap;
@@ -24,6 +26,36 @@ pub struct ParallelWorktreeSpec {
     pub worktree_path: PathBuf,
 }
 
+/// How a session asked for by name should start, beyond its provider: the
+/// TUI's own options (worktree, ⚡) plus what a script wants waiting for the
+/// agent when it arrives. Deserialized straight from `agent.new`'s params
+/// and the phone's request body.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+pub struct SpawnOptions {
+    #[serde(default)]
+    pub worktree: bool,
+    #[serde(default)]
+    pub dangerously_skip_permissions: bool,
+    #[serde(default)]
+    pub alias: Option<String>,
+    /// Said first, as soon as the agent is ready to hear it.
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Queued behind the prompt.
+    #[serde(default)]
+    pub todos: Vec<String>,
+    /// The short id of an agent this one takes over from (`workbench
+    /// handoff --to`). It starts in that agent's checkout — or, with
+    /// `worktree`, in a new one branched from it — so the work in progress
+    /// is in front of it.
+    #[serde(default)]
+    pub successor_of: Option<String>,
+    /// An existing worktree and its branch to start in, for callers inside
+    /// workbench: a fresh agent brought in to resolve a merge conflict.
+    #[serde(skip)]
+    pub checkout: Option<(PathBuf, String)>,
+}
+
 /// Result of a background session-worktree merge (see `session_worktree.rs`).
 #[derive(Debug, Clone)]
 pub enum WorktreeMergeOutcome {
@@ -43,6 +75,7 @@ pub struct ParallelMergePlan {
     pub winner_branch: String,
     pub winner_worktree_path: PathBuf,
     pub session_ids: Vec<Uuid>,
+    pub strategy: MergeStrategy,
 }
 
 #[derive(Debug, Clone)]
@@ -70,19 +103,43 @@ pub enum Action {
 
     // Session operations
     CreateSession(AgentType, bool, bool), // (agent_type, dangerously_skip_permissions, with_worktree)
-    /// Same, but in a named workspace — used when the request did not come
-    /// from the cursor (the phone).
-    CreateSessionIn(Uuid, AgentType, bool, bool),
+    /// Same, but in a named workspace and under an id the asker chose — used
+    /// when the request did not come from the cursor (the phone, the control
+    /// socket), so the asker can name the agent before it exists.
+    CreateSessionIn(Uuid, Uuid, AgentType, SpawnOptions), // (workspace, session, …)
     ActivateSession(Uuid),
     RestartSession(Uuid),
     StopSession(Uuid),
     KillSession(Uuid),
     InitiateDeleteSession(Uuid, String), // (id, name) - first 'd' press
     ConfirmDeleteSession,                // second 'd' press
+    /// Delete without the second press, for a caller that confirmed on its
+    /// own terms (the control socket's `confirm`).
+    DeleteSession(Uuid),
     MergeSessionWorktree(Uuid),          // Merge session's worktree branch into main
     SwitchToWorktree(Option<Uuid>),      // Switch to session's worktree (None = back to main)
     ConfirmMergeWithCommit,              // Commit changes and merge to main
     CancelMerge,                         // Cancel the merge modal
+    /// Step the strategy picker in either merge confirm modal.
+    CycleMergeStrategy(isize),
+    // A landing that stopped on conflicts (see `handlers::merge_conflict`)
+    MergeConflicted(Box<MergeConflict>),
+    /// Hand the conflict in the modal to the branch's agent, or to a fresh
+    /// one started in its worktree.
+    ResolveMergeConflict { fresh: bool },
+    AbortMergeConflict,
+    /// Close the modal and leave the conflicted checkout for a human.
+    DismissMergeConflict,
+    MergeConflictStaged {
+        conflict: Box<MergeConflict>,
+        fresh: bool,
+        error: Option<String>,
+    },
+    /// The worktree, looked at again after the resolving agent's turn.
+    MergeConflictRechecked {
+        conflict: Box<MergeConflict>,
+        error: Option<String>,
+    },
     // Background git results (work runs on blocking threads, never the event loop)
     SessionWorktreeMergeChecked {
         session_id: Uuid,
@@ -91,14 +148,14 @@ pub enum Action {
     },
     SessionWorktreeMergeFinished {
         session_id: Uuid,
-        committed: bool,
+        strategy: MergeStrategy,
         outcome: WorktreeMergeOutcome,
     },
     SessionWorktreeCreated {
         workspace_id: Uuid,
         session_id: Uuid,
         agent_type: AgentType,
-        dangerously_skip_permissions: bool,
+        options: SpawnOptions,
         worktree: Option<(PathBuf, String)>, // (worktree_path, branch); None = run in workspace
         failed: bool,                        // worktree creation failed (warn) vs. skipped
     },
@@ -232,6 +289,11 @@ pub enum Action {
     MergeSelectedReport,  // Merge winner from reports tab
     ConfirmParallelMerge, // Confirm parallel merge after seeing uncommitted changes
     CancelParallelMerge,  // Cancel parallel merge
+    RerunVerification,    // Run the verify command again in the selected attempt
+    VerificationFinished {
+        attempt_id: Uuid,
+        outcome: VerifyOutcome,
+    },
 
     // Mouse
     MouseClick(u16, u16), // (x, y) coordinates
@@ -252,6 +314,16 @@ pub enum Action {
     // Diff stats
     DiffStatsUpdated(HashMap<PathBuf, DiffStat>),
 
+    // Diff viewer
+    OpenDiff(DiffSubject),
+    DiffLoaded {
+        request_id: u64,
+        result: Result<Vec<FileDiff>, String>,
+    },
+    DiffNavigate(DiffNav),
+    MergeFromDiff, // Close the viewer and start the subject's usual merge flow
+    CloseDiff,
+
     // Debug
     ToggleDebugOverlay, // F11 - show terminal dimension debug info
 
@@ -84,6 +84,73 @@ pub fn press_after(
     });
 }
 
+/// The bytes a named key sends, the way the output pane encodes it when you
+/// press it: `esc`, `enter`, `tab`, `shift-tab`, the arrows, `home`/`end`,
+/// `pageup`/`pagedown`, `backspace`, `delete`, `insert`, `space`, `f1`–`f12`,
+/// and `ctrl-` or `alt-` with a character. A single character is itself.
+/// Case does not matter in a name; it does in a lone character.
+pub fn named_key(name: &str) -> Option<Vec<u8>> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(c.to_string().into_bytes());
+    }
+    let lower = name.to_ascii_lowercase();
+    let bytes: &[u8] = match lower.as_str() {
+        "esc" | "escape" => b"\x1b",
+        "enter" | "return" => b"\r",
+        "tab" => b"\t",
+        "shift-tab" | "backtab" => b"\x1b[Z",
+        "backspace" => b"\x7f",
+        "delete" | "del" => b"\x1b[3~",
+        "insert" => b"\x1b[2~",
+        "space" => b" ",
+        "up" => b"\x1b[A",
+        "down" => b"\x1b[B",
+        "right" => b"\x1b[C",
+        "left" => b"\x1b[D",
+        "home" => b"\x01",
+        "end" => b"\x05",
+        "pageup" => b"\x1b[5~",
+        "pagedown" => b"\x1b[6~",
+        "f1" => b"\x1bOP",
+        "f2" => b"\x1bOQ",
+        "f3" => b"\x1bOR",
+        "f4" => b"\x1bOS",
+        "f5" => b"\x1b[15~",
+        "f6" => b"\x1b[17~",
+        "f7" => b"\x1b[18~",
+        "f8" => b"\x1b[19~",
+        "f9" => b"\x1b[20~",
+        "f10" => b"\x1b[21~",
+        "f11" => b"\x1b[23~",
+        "f12" => b"\x1b[24~",
+        _ => {
+            // `ctrl-c`, `alt-b`: the modifier keeps its meaning only on a
+            // single ASCII character, as it does at the keyboard.
+            let (modifier, key) = lower.split_once('-')?;
+            let &[key] = key.as_bytes() else {
+                return None;
+            };
+            return match modifier {
+                "ctrl" if key.is_ascii_alphabetic() => Some(vec![key & 0x1f]),
+                "alt" if key.is_ascii_graphic() => Some(vec![0x1b, key]),
+                _ => None,
+            };
+        }
+    };
+    Some(bytes.to_vec())
+}
+
+/// Press a sequence of keys, each as its own write and a beat apart. One
+/// write of Esc and a letter is Alt-letter to the agent, and text with an
+/// Enter in the same chunk is a paste; spacing them is what makes each key
+/// arrive as the key it is.
+pub fn press_keys(action_tx: &mpsc::UnboundedSender<Action>, session_id: Uuid, keys: Vec<Vec<u8>>) {
+    for (i, bytes) in keys.into_iter().enumerate() {
+        press_after(action_tx, session_id, bytes, SUBMIT_DELAY * i as u32);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,6 +189,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn keys_are_named_the_way_the_output_pane_sends_them() {
+        assert_eq!(named_key("Esc").unwrap(), vec![0x1b]);
+        assert_eq!(named_key("shift-tab").unwrap(), b"\x1b[Z".to_vec());
+        assert_eq!(named_key("ctrl-c").unwrap(), vec![0x03]);
+        assert_eq!(named_key("alt-b").unwrap(), vec![0x1b, b'b']);
+        assert_eq!(named_key("f5").unwrap(), b"\x1b[15~".to_vec());
+        assert_eq!(named_key("Y").unwrap(), b"Y".to_vec());
+        assert!(named_key("ctrl-").is_none());
+        assert!(named_key("ctrl-1").is_none());
+        assert!(named_key("hyper-x").is_none());
+    }
+
     #[test]
     fn a_message_cannot_close_its_own_paste() {
         let sneaky = "innocent\x1b[201~?then keys";
@@ -4,23 +4,33 @@
 //! - export transcripts + refresh each workspace's `agents.json` roster
 //! - ensure the standing instructions block in workspace instruction files
 //! - poll the inbox for `workbench ask` / `workbench alias` messages
-//! - deliver queued consults to their target session when it is idle
-//! - capture the target's reply (transcript delta at its next idle) and
-//!   write it to the replies directory
+//! - queue each consult on its target's TODO queue, which delivers it when
+//!   the target is free (see `app::todo_dispatch`)
+//! - capture the target's reply at its next idle — from its journal where
+//!   workbench can read one, else the transcript delta — and write it to
+//!   the replies directory
+//! - keep the pending consults on disk, and pick them up again after a
+//!   restart
 //!
 //! All file writes go through atomic temp+rename; the larger ones are
 //! offloaded to blocking threads.
 
 use crate::app::{AppState, Toast, ToastLevel};
 use crate::comms::{self, InboxMessage, Reply, Roster, RosterAgent};
+use crate::config::user_config::{consult_policy_for, ConsultOrder, ConsultPolicy, PolicySubject};
 use crate::models::SessionStatus;
-use std::collections::HashSet;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 const INBOX_POLL_INTERVAL: Duration = Duration::from_millis(1000);
 const ROSTER_REFRESH_INTERVAL: Duration = Duration::from_millis(2000);
 const CONSULT_TTL: Duration = Duration::from_secs(15 * 60);
+/// Settled consults remembered for the control socket. It only needs to see
+/// each one on the next publish, so a handful of ticks' worth is plenty.
+const SETTLED_KEPT: usize = 32;
 
 /// A consult accepted from the inbox, waiting for delivery and then a reply.
 #[derive(Debug)]
@@ -28,6 +38,11 @@ pub struct PendingConsult {
     pub ticket: String,
     pub workspace_id: Uuid,
     pub from_short: String,
+    /// The asker's project name, when it is not the target's — the target
+    /// cannot tell `a1b2c3d4` from one of its own neighbours otherwise.
+    pub from_project: Option<String>,
+    /// The group ticket for one copy of a question asked of several peers.
+    pub group: Option<String>,
     pub to_session: Uuid,
     pub to_short: String,
     pub question: String,
@@ -40,8 +55,33 @@ pub struct PendingConsult {
     /// slow-to-start target would be "idle" again before it ever produced a
     /// reply and we'd capture nothing.
     pub activity_at_delivery: Option<Instant>,
+    /// The target's journal and how far it ran at delivery, for a provider
+    /// whose journal we can read. The reply is the agent's own messages
+    /// after that point — its prose, without the tool output and status
+    /// lines a transcript delta drags along.
+    pub journal_at_delivery: Option<(crate::agent_tasks::Provider, std::path::PathBuf, crate::remote::Cursor)>,
     pub delivered_at: Option<Instant>,
     pub created: Instant,
+    /// `delivered_at` and `created` by the wall clock, read once when each
+    /// happened. Working them out from the `Instant`s at save time drifts
+    /// by a few microseconds a tick, and the pending file would be
+    /// rewritten every tick for a change nobody made.
+    pub delivered_wall: Option<DateTime<Utc>>,
+    pub created_wall: DateTime<Utc>,
+}
+
+/// A consult whose reply has been written, whatever that reply said. Kept
+/// only so `remote::publish` can show it settled for a tick or two — that is
+/// how `control` learns to announce `consult.answered` by diffing, the same
+/// way it learns everything else.
+#[derive(Debug, Clone)]
+pub struct SettledConsult {
+    pub ticket: String,
+    pub workspace_id: Uuid,
+    pub from_short: String,
+    pub to_short: String,
+    /// The reply's status: "answered" | "timeout" | …
+    pub status: String,
 }
 
 /// If the target never produces attributable output, give up waiting for
@@ -55,8 +95,21 @@ pub struct CommsState {
     /// Serialized roster per workspace, to skip no-op writes.
     pub roster_cache: std::collections::HashMap<Uuid, String>,
     pub pending: Vec<PendingConsult>,
+    /// Most recent last; see `SettledConsult`.
+    pub settled: VecDeque<SettledConsult>,
     /// Workspaces whose instruction files were ensured this run.
     pub instructions_done: HashSet<Uuid>,
+    /// The consults saved last, per workspace, to skip no-op writes.
+    pub pending_saved: HashMap<Uuid, String>,
+    /// Consults saved by the previous run have been picked up.
+    pub restored: bool,
+    /// Sessions that edited a file another session had claimed, and what
+    /// they edited (see `crate::claims`). Shown on the session's row until
+    /// the claim is released.
+    pub claim_conflicts: HashMap<Uuid, crate::claims::Conflict>,
+    /// When each target accepted its consults over the last hour, for
+    /// `ConsultPolicy::per_hour`.
+    pub accepted: HashMap<Uuid, VecDeque<Instant>>,
 }
 
 impl CommsState {
@@ -66,11 +119,30 @@ impl CommsState {
             last_roster_refresh: Instant::now(),
             roster_cache: std::collections::HashMap::new(),
             pending: Vec::new(),
+            settled: VecDeque::new(),
             instructions_done: HashSet::new(),
+            pending_saved: HashMap::new(),
+            restored: false,
+            claim_conflicts: HashMap::new(),
+            accepted: HashMap::new(),
         }
     }
 }
 
+fn settle(state: &mut AppState, consult: &PendingConsult, status: &str) {
+    let settled = &mut state.system.comms.settled;
+    settled.push_back(SettledConsult {
+        ticket: consult.ticket.clone(),
+        workspace_id: consult.workspace_id,
+        from_short: consult.from_short.clone(),
+        to_short: consult.to_short.clone(),
+        status: status.to_string(),
+    });
+    while settled.len() > SETTLED_KEPT {
+        settled.pop_front();
+    }
+}
+
 fn toast(state: &mut AppState, msg: String, level: ToastLevel) {
     state
         .ui
@@ -83,17 +155,265 @@ fn toast(state: &mut AppState, msg: String, level: ToastLevel) {
 
 /// Main entry point, called once per Tick with the sessions that just went
 /// idle this tick.
-pub fn tick(
-    state: &mut AppState,
-    action_tx: &tokio::sync::mpsc::UnboundedSender<crate::app::Action>,
-    newly_idle: &[Uuid],
-) {
+pub fn tick(state: &mut AppState, newly_idle: &[Uuid]) {
+    // Before anything is exported: a reply the last run never captured may
+    // still be sitting in the transcript it left behind.
+    if !state.system.comms.restored {
+        state.system.comms.restored = true;
+        restore_pending(state);
+    }
     export_transcripts_for(state, newly_idle);
     capture_replies(state, newly_idle);
-    deliver_pending(state, action_tx);
     poll_inbox(state);
     refresh_rosters(state);
     expire_stale(state);
+    save_pending(state);
+}
+
+// ---------------------------------------------------------------------------
+// Surviving a restart
+// ---------------------------------------------------------------------------
+
+/// A `PendingConsult` as written to `comms::pending_path`. The clocks become
+/// wall-clock times, since an `Instant` means nothing to the next process.
+/// What the target was doing at delivery is not kept: its pane starts over
+/// with the new process anyway.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedConsult {
+    ticket: String,
+    from_short: String,
+    #[serde(default)]
+    from_project: Option<String>,
+    #[serde(default)]
+    group: Option<String>,
+    to_session: Uuid,
+    to_short: String,
+    question: String,
+    delivered: bool,
+    transcript_base: usize,
+    #[serde(default)]
+    delivered_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+fn monotonic(at: DateTime<Utc>) -> Instant {
+    let ago = (Utc::now() - at).to_std().unwrap_or_default();
+    Instant::now().checked_sub(ago).unwrap_or_else(Instant::now)
+}
+
+impl SavedConsult {
+    fn from_pending(p: &PendingConsult) -> Self {
+        Self {
+            ticket: p.ticket.clone(),
+            from_short: p.from_short.clone(),
+            from_project: p.from_project.clone(),
+            group: p.group.clone(),
+            to_session: p.to_session,
+            to_short: p.to_short.clone(),
+            question: p.question.clone(),
+            delivered: p.delivered,
+            transcript_base: p.transcript_base,
+            delivered_at: p.delivered_wall,
+            created_at: p.created_wall,
+        }
+    }
+
+    fn into_pending(self, workspace_id: Uuid) -> PendingConsult {
+        PendingConsult {
+            ticket: self.ticket,
+            workspace_id,
+            from_short: self.from_short,
+            from_project: self.from_project,
+            group: self.group,
+            to_session: self.to_session,
+            to_short: self.to_short,
+            question: self.question,
+            delivered: self.delivered,
+            transcript_base: self.transcript_base,
+            activity_at_delivery: None,
+            journal_at_delivery: None,
+            delivered_at: self.delivered_at.map(monotonic),
+            created: monotonic(self.created_at),
+            delivered_wall: self.delivered_at,
+            created_wall: self.created_at,
+        }
+    }
+}
+
+/// Write each workspace's pending consults when they changed — including an
+/// empty list, so a consult settled this run is not revived by the next.
+fn save_pending(state: &mut AppState) {
+    save_pending_with(state, |ws_id, json| {
+        comms::ensure_workspace_dirs(&ws_id.to_string())
+            .and_then(|_| comms::pending_path(&ws_id.to_string()))
+            .and_then(|path| comms::write_atomic(&path, json.as_bytes()))
+    });
+}
+
+/// `save_pending`, with the write itself handed in so a test can count them.
+fn save_pending_with(
+    state: &mut AppState,
+    mut write: impl FnMut(Uuid, &str) -> anyhow::Result<()>,
+) {
+    let workspace_ids: Vec<Uuid> = state.data.workspaces.iter().map(|w| w.id).collect();
+    for ws_id in workspace_ids {
+        let saved: Vec<SavedConsult> = state
+            .system
+            .comms
+            .pending
+            .iter()
+            .filter(|p| p.workspace_id == ws_id)
+            .map(SavedConsult::from_pending)
+            .collect();
+        let json = serde_json::to_string_pretty(&saved).unwrap_or_default();
+        // Nothing pending and nothing ever saved: leave the disk alone.
+        let last = state.system.comms.pending_saved.get(&ws_id);
+        if last == Some(&json) || (last.is_none() && saved.is_empty()) {
+            continue;
+        }
+        match write(ws_id, &json) {
+            Ok(()) => {
+                state.system.comms.pending_saved.insert(ws_id, json);
+            }
+            Err(err) => crate::logger::warn(format!("failed to save pending consults: {err}")),
+        }
+    }
+}
+
+/// Pick up the consults the previous run left pending.
+///
+/// One not yet delivered is simply queued again: its target comes back with
+/// the rest of the workspace, and the question has not been asked. One that
+/// was delivered is harder — the target answered in a process that is gone.
+/// If it finished and went idle before the restart, its answer is in the
+/// transcript exported then, past `transcript_base`, and is relayed from
+/// there. Otherwise the answer cannot be recovered, and the asker is told so
+/// rather than left to wait out its timeout.
+fn restore_pending(state: &mut AppState) {
+    let workspace_ids: Vec<Uuid> = state.data.workspaces.iter().map(|w| w.id).collect();
+    for ws_id in workspace_ids {
+        let Ok(path) = comms::pending_path(&ws_id.to_string()) else {
+            continue;
+        };
+        let Ok(bytes) = std::fs::read(&path) else {
+            continue;
+        };
+        let saved: Vec<SavedConsult> = match serde_json::from_slice(&bytes) {
+            Ok(saved) => saved,
+            Err(err) => {
+                crate::logger::warn(format!("unreadable pending consults {}: {err}", path.display()));
+                continue;
+            }
+        };
+        for consult in saved {
+            let target = state.get_session(consult.to_session);
+            let export = target.and_then(|s| {
+                comms::transcript_path(
+                    &s.workspace_id.to_string(),
+                    &s.agent_type.display_name().to_lowercase(),
+                    &consult.to_short,
+                )
+                .ok()
+            });
+            let consult = consult.into_pending(ws_id);
+            if target.is_some() && !consult.delivered {
+                // Its queue item was saved with the session — unless the
+                // state write lost the race with this file's.
+                let queued = state
+                    .get_session(consult.to_session)
+                    .is_some_and(|s| s.todo_queue.find_consult(&consult.ticket).is_some());
+                if !queued {
+                    enqueue(state, &consult);
+                }
+                state.system.comms.pending.push(consult);
+                continue;
+            }
+            let answer = consult
+                .delivered_at
+                .zip(export)
+                .and_then(|(delivered_at, export)| {
+                    let modified = std::fs::metadata(&export).and_then(|m| m.modified()).ok()?;
+                    // Exported before the question went in: it cannot hold
+                    // the answer, only what came before it.
+                    if modified.elapsed().ok()? >= delivered_at.elapsed() {
+                        return None;
+                    }
+                    let text = std::fs::read_to_string(&export).ok()?;
+                    reply_from_export(&text, consult.transcript_base)
+                });
+            match answer {
+                Some(answer) => {
+                    write_settled(state, &consult, "answered", Some(answer), None);
+                }
+                None => {
+                    write_settled(state, &consult, "error", None, Some("workbench restarted"));
+                }
+            }
+        }
+    }
+}
+
+/// Write a consult's reply and note it settled.
+fn write_settled(
+    state: &mut AppState,
+    consult: &PendingConsult,
+    status: &str,
+    reply: Option<String>,
+    reason: Option<&str>,
+) {
+    let written = Reply {
+        ticket: consult.ticket.clone(),
+        status: status.to_string(),
+        from: consult.from_short.clone(),
+        to: consult.to_short.clone(),
+        question: consult.question.clone(),
+        reply,
+        reason: reason.map(str::to_string),
+    };
+    if let Err(err) = comms::write_reply(&consult.workspace_id.to_string(), &written) {
+        crate::logger::warn(format!("failed to write consult reply: {err}"));
+    }
+    settle(state, consult, status);
+}
+
+/// The reply in what the target journaled after delivery: everything the
+/// agent said after the consult went in as a user turn. Tool calls and
+/// their output are left out — the asker wants the answer, and can read the
+/// transcript for the working. `None` when the agent said nothing, which
+/// sends the caller to the transcript instead.
+fn reply_from_journal(messages: &[crate::remote::Message]) -> Option<String> {
+    use crate::remote::Role;
+
+    // The delivered question is the first user turn. A journal that missed
+    // it still has the answer in it, so start from the top then.
+    let start = messages
+        .iter()
+        .position(|m| m.role == Role::You)
+        .map_or(0, |i| i + 1);
+    let reply = messages[start..]
+        .iter()
+        .filter(|m| m.role == Role::Agent)
+        .map(|m| m.text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    (!reply.is_empty()).then_some(reply)
+}
+
+/// What a transcript exported by `export_transcript` holds past `base` — the
+/// reply, as `capture_replies` would have taken it from the live buffer.
+/// `None` when nothing was written after the question.
+fn reply_from_export(text: &str, base: usize) -> Option<String> {
+    // The export opens with a heading and a blank line; the buffer's own
+    // lines follow, one per line.
+    let reply = text
+        .lines()
+        .skip(2 + base)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+    (!reply.is_empty()).then_some(reply)
 }
 
 // ---------------------------------------------------------------------------
@@ -145,6 +465,26 @@ fn export_transcript(state: &AppState, session_id: Uuid) {
     });
 }
 
+/// The session's journal for the roster: the store its tracker resolved, or
+/// for a stopped session the file it last wrote to (see `remote::journal`).
+fn roster_journal(state: &AppState, session_id: Uuid) -> Option<comms::RosterJournal> {
+    use crate::agent_tasks::Source;
+    let tracker = state.system.agent_tasks.get(&session_id);
+    if let Some(Source::DbSession { db, session }) = tracker.and_then(|t| t.source()) {
+        return Some(comms::RosterJournal {
+            provider: tracker?.provider().name().to_string(),
+            path: db.to_string_lossy().to_string(),
+            session: Some(session.clone()),
+        });
+    }
+    let (provider, path) = crate::remote::journal(state, session_id)?;
+    Some(comms::RosterJournal {
+        provider: provider.name().to_string(),
+        path: path.to_string_lossy().to_string(),
+        session: None,
+    })
+}
+
 fn refresh_rosters(state: &mut AppState) {
     if state.system.comms.last_roster_refresh.elapsed() < ROSTER_REFRESH_INTERVAL {
         return;
@@ -198,19 +538,82 @@ fn refresh_rosters(state: &mut AppState) {
                     status: status.to_string(),
                     transcript,
                     supports_consult: s.agent_type.is_redraw_style(),
+                    journal: roster_journal(state, s.id),
                 });
             }
         }
 
+        let cross_project = state
+            .system
+            .user_config
+            .shares_across_projects(&ws_name, &ws_path);
+        let notes = crate::notes::notes_dir(&ws_id.to_string())
+            .map(|dir| crate::notes::list(&dir))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|note| comms::RosterNote {
+                key: note.key,
+                version: note.version,
+                author: note.author,
+                updated_at: note.updated_at.to_rfc3339(),
+            })
+            .collect();
+        // Claims and conflicts count only while everyone involved is running.
+        let registry = crate::claims::registry_path(&ws_id.to_string())
+            .map(|path| crate::claims::load(&path))
+            .unwrap_or_default();
+        let live = |short: &str| {
+            agents
+                .iter()
+                .any(|a| a.id.eq_ignore_ascii_case(short) && a.status != "stopped")
+        };
+        let claims: Vec<crate::claims::Claim> = registry
+            .claims
+            .iter()
+            .filter(|claim| live(&claim.session))
+            .cloned()
+            .collect();
+        let conflicts: Vec<crate::claims::Conflict> = registry
+            .standing_conflicts()
+            .filter(|conflict| live(&conflict.session) && live(&conflict.holder))
+            .cloned()
+            .collect();
+        if let Some(sessions) = state.data.sessions.get(&ws_id) {
+            for session in sessions {
+                let short = session.short_id();
+                match conflicts.iter().rev().find(|c| c.session.eq_ignore_ascii_case(&short)) {
+                    Some(conflict) => {
+                        state
+                            .system
+                            .comms
+                            .claim_conflicts
+                            .insert(session.id, conflict.clone());
+                    }
+                    None => {
+                        state.system.comms.claim_conflicts.remove(&session.id);
+                    }
+                }
+            }
+        }
+
         let roster = Roster {
             workspace_id: ws_id.to_string(),
             workspace_name: ws_name,
             workspace_path: ws_path.to_string_lossy().to_string(),
             updated_at: chrono::Utc::now().to_rfc3339(),
             agents,
+            cross_project,
+            notes,
+            claims,
         };
         // Compare everything except the timestamp so unchanged rosters skip IO.
-        let fingerprint = serde_json::to_string(&roster.agents).unwrap_or_default();
+        let fingerprint = format!(
+            "{}{}{}{}",
+            cross_project,
+            serde_json::to_string(&roster.agents).unwrap_or_default(),
+            serde_json::to_string(&roster.notes).unwrap_or_default(),
+            serde_json::to_string(&roster.claims).unwrap_or_default()
+        );
         if state.system.comms.roster_cache.get(&ws_id) == Some(&fingerprint) {
             continue;
         }
@@ -275,9 +678,15 @@ fn poll_inbox(state: &mut AppState) {
                     ticket,
                     from,
                     to,
+                    to_workspace,
+                    group,
                     message,
                     ..
-                }) => ingest_ask(state, ws_id, ticket, from, to, message),
+                }) => ingest_ask(
+                    state,
+                    ws_id,
+                    Ask { ticket, from, to, to_workspace, group, message },
+                ),
                 Some(InboxMessage::Alias { ticket, from, alias }) => {
                     ingest_alias(state, ws_id, ticket, from, alias)
                 }
@@ -317,16 +726,72 @@ fn refuse(state: &AppState, ws_id: Uuid, ticket: &str, from: &str, to: &str, q:
     }
 }
 
-fn ingest_ask(
-    state: &mut AppState,
-    ws_id: Uuid,
+/// The workspace a consult is addressed to: the asker's own, or — for a
+/// `project:agent` address — another one, provided both projects opted in to
+/// cross-project consults. The CLI already checked the target's roster; this
+/// checks the config itself, which the roster only mirrors.
+fn target_workspace(state: &AppState, ws_id: Uuid, to_workspace: Option<&str>) -> Result<Uuid, String> {
+    let Some(to_workspace) = to_workspace else {
+        return Ok(ws_id);
+    };
+    let Some(target) = state
+        .data
+        .workspaces
+        .iter()
+        .find(|w| w.id.to_string() == to_workspace)
+    else {
+        return Err(format!("no open project {to_workspace}"));
+    };
+    if target.id == ws_id {
+        return Ok(ws_id);
+    }
+    let config = &state.system.user_config;
+    let shares = |id: Uuid| {
+        state
+            .data
+            .workspaces
+            .iter()
+            .find(|w| w.id == id)
+            .is_some_and(|w| config.shares_across_projects(&w.name, &w.path))
+    };
+    if !shares(ws_id) {
+        return Err("this project does not take part in cross-project consults".to_string());
+    }
+    if !shares(target.id) {
+        return Err(format!("project {} does not take cross-project consults", target.name));
+    }
+    Ok(target.id)
+}
+
+/// The parts of an `InboxMessage::Ask` the tick acts on.
+struct Ask {
     ticket: String,
     from: String,
     to: String,
+    to_workspace: Option<String>,
+    group: Option<String>,
     message: String,
-) {
-    let Some(target) = find_session_by_short(state, ws_id, &to) else {
-        refuse(state, ws_id, &ticket, &from, &to, &message, format!("no session {to} in this workspace"));
+}
+
+fn ingest_ask(state: &mut AppState, ws_id: Uuid, ask: Ask) {
+    let Ask {
+        ticket,
+        from,
+        to,
+        to_workspace,
+        group,
+        message,
+    } = ask;
+    let target_ws = match target_workspace(state, ws_id, to_workspace.as_deref()) {
+        Ok(target_ws) => target_ws,
+        Err(reason) => {
+            refuse(state, ws_id, &ticket, &from, &to, &message, reason);
+            return;
+        }
+    };
+    let Some(target) = find_session_by_short(state, target_ws, &to) else {
+        let place = if target_ws == ws_id { "this workspace" } else { "that project" };
+        refuse(state, ws_id, &ticket, &from, &to, &message, format!("no session {to} in {place}"));
         return;
     };
     let (running, consultable) = state
@@ -349,10 +814,48 @@ fn ingest_ask(
         );
         return;
     }
+    // The user's policy for this target: who may ask, and how often.
+    let policy = consult_policy(state, target).cloned();
+    if let Some(reason) = policy.as_ref().and_then(|policy| {
+        let asker = find_session_by_short(state, ws_id, &from).and_then(|id| state.get_session(id));
+        let provider = asker
+            .map(|s| s.agent_type.display_name().to_lowercase())
+            .unwrap_or_default();
+        policy.refuses(PolicySubject {
+            short_id: &from,
+            alias: asker.and_then(|s| s.alias.as_deref()),
+            provider: &provider,
+        })
+    }) {
+        refuse(state, ws_id, &ticket, &from, &to, &message, reason);
+        return;
+    }
+    let hour_ago = Instant::now().checked_sub(Duration::from_secs(3600));
+    let accepted = state.system.comms.accepted.entry(target).or_default();
+    accepted.retain(|at| hour_ago.is_none_or(|hour_ago| *at > hour_ago));
+    if let Some(limit) = policy.as_ref().and_then(|policy| policy.per_hour) {
+        if accepted.len() >= limit as usize {
+            let free_in = accepted
+                .front()
+                .map(|oldest| Duration::from_secs(3600).saturating_sub(oldest.elapsed()))
+                .unwrap_or_default();
+            let reason = format!(
+                "{to} takes {limit} consults an hour and has had them; try again in {} min",
+                free_in.as_secs().div_ceil(60)
+            );
+            refuse(state, ws_id, &ticket, &from, &to, &message, reason);
+            return;
+        }
+    }
+
     // Cycle guard: refuse if the target is itself waiting on a consult it
-    // sent to the asker (A→B while B→A would deadlock on idle-gating).
+    // sent to the asker (A→B while B→A would deadlock on idle-gating). The
+    // two may sit in different projects, so compare the sessions themselves.
+    let asker = find_session_by_short(state, ws_id, &from);
     let cycle = state.system.comms.pending.iter().any(|p| {
-        p.workspace_id == ws_id && p.from_short.eq_ignore_ascii_case(&to) && p.to_short.eq_ignore_ascii_case(&from)
+        p.workspace_id == target_ws
+            && p.from_short.eq_ignore_ascii_case(&to)
+            && Some(p.to_session) == asker
     });
     if cycle {
         refuse(
@@ -361,14 +864,14 @@ fn ingest_ask(
         );
         return;
     }
-    // One outstanding consult per asker keeps amplification bounded.
-    if state
-        .system
-        .comms
-        .pending
-        .iter()
-        .any(|p| p.workspace_id == ws_id && p.from_short.eq_ignore_ascii_case(&from))
-    {
+    // One outstanding consult per asker keeps amplification bounded. The
+    // copies of one group question count as one: the fan-out was a single
+    // deliberate act, bounded by the roster, and its copies arrive together.
+    if state.system.comms.pending.iter().any(|p| {
+        p.workspace_id == ws_id
+            && p.from_short.eq_ignore_ascii_case(&from)
+            && (group.is_none() || p.group != group)
+    }) {
         refuse(
             state, ws_id, &ticket, &from, &to, &message,
             "you already have an outstanding consult; collect its reply first".to_string(),
@@ -381,26 +884,58 @@ fn ingest_ask(
         format!("Consult {ticket}: {from} → {to} queued"),
         ToastLevel::Info,
     );
-    state.system.comms.pending.push(PendingConsult {
+    state
+        .system
+        .comms
+        .accepted
+        .entry(target)
+        .or_default()
+        .push_back(Instant::now());
+    let from_project = (target_ws != ws_id)
+        .then(|| state.data.workspaces.iter().find(|w| w.id == ws_id))
+        .flatten()
+        .map(|w| w.name.clone());
+    let consult = PendingConsult {
         ticket,
         workspace_id: ws_id,
         from_short: from,
+        from_project,
+        group,
         to_session: target,
         to_short: to,
         question: message,
         delivered: false,
         transcript_base: 0,
         activity_at_delivery: None,
+        journal_at_delivery: None,
         delivered_at: None,
         created: Instant::now(),
-    });
+        delivered_wall: None,
+        created_wall: Utc::now(),
+    };
+    enqueue(state, &consult);
+    state.system.comms.pending.push(consult);
+}
+
+/// Put a consult on its target's TODO queue, where the user's policy says it
+/// goes: ahead of the queued work by default, or behind it.
+fn enqueue(state: &mut AppState, consult: &PendingConsult) {
+    let behind = consult_policy(state, consult.to_session)
+        .is_some_and(|policy| policy.order == ConsultOrder::BehindTodos);
+    let from = match &consult.from_project {
+        Some(project) => format!("{} in {project}", consult.from_short),
+        None => consult.from_short.clone(),
+    };
+    if let Some(session) = state.get_session_mut(consult.to_session) {
+        session
+            .todo_queue
+            .add_consult(&consult.ticket, from, &consult.question, behind);
+    }
+    crate::app::handlers::save_state(state, "failed to queue a consult");
 }
 
 fn ingest_alias(state: &mut AppState, ws_id: Uuid, ticket: String, from: String, alias: String) {
-    let ok_chars = alias
-        .chars()
-        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
-    let valid = !alias.is_empty() && alias.len() <= 24 && ok_chars;
+    let valid = comms::alias_is_valid(&alias);
     let taken = state
         .data
         .sessions
@@ -454,70 +989,97 @@ fn ingest_alias(state: &mut AppState, ws_id: Uuid, ticket: String, from: String,
 // Delivery + reply capture
 // ---------------------------------------------------------------------------
 
-fn deliver_pending(
-    state: &mut AppState,
-    action_tx: &tokio::sync::mpsc::UnboundedSender<crate::app::Action>,
-) {
-    let deliverable: Vec<usize> = state
+/// What the target is sent for the consult `ticket`, while it still waits
+/// to go — `None` once it has gone, or was never here to begin with.
+pub fn consult_prompt(state: &AppState, ticket: &str) -> Option<String> {
+    let p = state
         .system
         .comms
         .pending
         .iter()
-        .enumerate()
-        .filter(|(_, p)| !p.delivered && state.data.idle_queue.contains(&p.to_session))
-        .map(|(i, _)| i)
-        .collect();
+        .find(|p| p.ticket == ticket && !p.delivered)?;
+    let asker = match &p.from_project {
+        Some(project) => format!("{} in project {}", p.from_short, project),
+        None => p.from_short.clone(),
+    };
+    Some(format!(
+        "[workbench consult {} from agent {}] {}\n(Reply normally — your full response will be relayed back to {} when you finish. Do not use `workbench ask` to answer this.)",
+        p.ticket, asker, p.question, p.from_short
+    ))
+}
 
-    for idx in deliverable {
-        let (target, base, framed, ticket, from, to_short) = {
-            let p = &state.system.comms.pending[idx];
-            let base = state
-                .system
-                .transcript_buffers
-                .get(&p.to_session)
-                .map(|t| t.len())
-                .unwrap_or(0);
-            let framed = format!(
-                "[workbench consult {} from agent {}] {}\n(Reply normally — your full response will be relayed back to {} when you finish. Do not use `workbench ask` to answer this.)",
-                p.ticket, p.from_short, p.question, p.from_short
-            );
-            (
-                p.to_session,
-                base,
-                framed,
-                p.ticket.clone(),
-                p.from_short.clone(),
-                p.to_short.clone(),
-            )
-        };
+/// The TODO queue has sent the consult `ticket`: note where the target's
+/// transcript and journal stood, so the reply is what comes after.
+pub fn consult_delivered(state: &mut AppState, ticket: &str) {
+    let Some(idx) = state
+        .system
+        .comms
+        .pending
+        .iter()
+        .position(|p| p.ticket == ticket)
+    else {
+        return;
+    };
+    let target = state.system.comms.pending[idx].to_session;
+    let base = state
+        .system
+        .transcript_buffers
+        .get(&target)
+        .map(|t| t.len())
+        .unwrap_or(0);
+    // Sending input marks activity via the echo path only after output
+    // arrives; drop the target from the idle queue now so the reply is not
+    // captured from the idle it was sent in.
+    state.data.idle_queue.retain(|&id| id != target);
 
-        // Bracketed paste so multiline questions don't submit early.
-        let mut payload = Vec::with_capacity(framed.len() + 12);
-        payload.extend_from_slice(b"\x1b[200~");
-        payload.extend_from_slice(framed.as_bytes());
-        payload.extend_from_slice(b"\x1b[201~");
-        let _ = action_tx.send(crate::app::Action::SendInput(target, payload));
-        // The Enter goes on its own, a beat later, or the agent reads the
-        // newline as part of the paste (see `app::agent_input`).
-        crate::app::agent_input::submit(action_tx, target);
-
-        // Sending input marks activity via the echo path only after output
-        // arrives; drop the target from the idle queue now so a second
-        // pending consult can't double-deliver this tick.
-        state.data.idle_queue.retain(|&id| id != target);
-
-        let activity = state.data.last_activity.get(&target).copied();
-        let p = &mut state.system.comms.pending[idx];
-        p.delivered = true;
-        p.transcript_base = base;
-        p.activity_at_delivery = activity;
-        p.delivered_at = Some(Instant::now());
-        toast(
-            state,
-            format!("Consult {ticket}: {from} → {to_short} delivered"),
-            ToastLevel::Info,
-        );
-    }
+    let activity = state.data.last_activity.get(&target).copied();
+    let journal = crate::remote::journal(state, target).map(|(provider, path)| {
+        let len = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        (provider, path, crate::remote::Cursor(len))
+    });
+    let p = &mut state.system.comms.pending[idx];
+    p.delivered = true;
+    p.transcript_base = base;
+    p.activity_at_delivery = activity;
+    p.journal_at_delivery = journal;
+    p.delivered_at = Some(Instant::now());
+    p.delivered_wall = Some(Utc::now());
+    let note = format!("Consult {ticket}: {} → {} delivered", p.from_short, p.to_short);
+    toast(state, note, ToastLevel::Info);
+}
+
+/// The user took the consult `ticket` off its target's queue before it went
+/// out: the asker hears no, now, rather than waiting out the TTL. One already
+/// delivered is past refusing — its reply is captured as usual.
+pub fn decline(state: &mut AppState, ticket: &str) {
+    let Some(idx) = state
+        .system
+        .comms
+        .pending
+        .iter()
+        .position(|p| p.ticket == ticket && !p.delivered)
+    else {
+        return;
+    };
+    let consult = state.system.comms.pending.remove(idx);
+    write_settled(state, &consult, "refused", None, Some("the user turned it down"));
+    let note = format!("Consult {ticket}: {} → {} refused", consult.from_short, consult.to_short);
+    toast(state, note, ToastLevel::Info);
+}
+
+/// The policy the user set for consults to `session_id`, if any.
+fn consult_policy(state: &AppState, session_id: Uuid) -> Option<&ConsultPolicy> {
+    let session = state.get_session(session_id)?;
+    let short_id = session.short_id();
+    let provider = session.agent_type.display_name().to_lowercase();
+    consult_policy_for(
+        &state.system.user_config.consult_policies,
+        PolicySubject {
+            short_id: &short_id,
+            alias: session.alias.as_deref(),
+            provider: &provider,
+        },
+    )
 }
 
 fn capture_replies(state: &mut AppState, newly_idle: &[Uuid]) {
@@ -544,22 +1106,35 @@ fn capture_replies(state: &mut AppState, newly_idle: &[Uuid]) {
 
     for &i in finished.iter().rev() {
         let p = state.system.comms.pending.remove(i);
-        let reply_text = state
-            .system
-            .transcript_buffers
-            .get(&p.to_session)
-            .map(|t| {
-                let start = p.transcript_base.min(t.len());
-                let mut out = String::new();
-                for idx in start..t.len() {
-                    if let Some(line) = t.line(idx) {
-                        out.push_str(line);
+        let from_journal = p.journal_at_delivery.as_ref().and_then(|(provider, path, cursor)| {
+            // A different journal now (the agent cleared its conversation,
+            // say) means the cursor points into the wrong file.
+            let now = crate::remote::journal(state, p.to_session).map(|(_, now)| now);
+            if now.as_ref() != Some(path) {
+                return None;
+            }
+            let mut messages = Vec::new();
+            crate::remote::read_more(path, *provider, *cursor, &mut messages);
+            reply_from_journal(&messages)
+        });
+        let reply_text = from_journal.unwrap_or_else(|| {
+            state
+                .system
+                .transcript_buffers
+                .get(&p.to_session)
+                .map(|t| {
+                    let start = p.transcript_base.min(t.len());
+                    let mut out = String::new();
+                    for idx in start..t.len() {
+                        if let Some(line) = t.line(idx) {
+                            out.push_str(line);
+                        }
+                        out.push('\n');
                     }
-                    out.push('\n');
-                }
-                out.trim().to_string()
-            })
-            .unwrap_or_default();
+                    out.trim().to_string()
+                })
+                .unwrap_or_default()
+        });
 
         let reply = Reply {
             ticket: p.ticket.clone(),
@@ -576,6 +1151,7 @@ fn capture_replies(state: &mut AppState, newly_idle: &[Uuid]) {
                 crate::logger::warn(format!("failed to write consult reply: {err}"));
             }
         });
+        settle(state, &p, "answered");
         toast(
             state,
             format!("Consult {}: {} answered", p.ticket, p.to_short),
@@ -599,6 +1175,19 @@ fn expire_stale(state: &mut AppState) {
         out
     };
     for p in expired {
+        // Gone from the queue too, if it never went out; one the agent is
+        // answering stays, since the turn it started is already under way.
+        if let Some(session) = state.get_session_mut(p.to_session) {
+            let queued = session
+                .todo_queue
+                .find_consult(&p.ticket)
+                .filter(|item| item.state == crate::models::TodoState::Pending)
+                .map(|item| item.id);
+            if let Some(id) = queued {
+                session.todo_queue.remove(id);
+            }
+        }
+        settle(state, &p, "timeout");
         let reply = Reply {
             ticket: p.ticket.clone(),
             status: "timeout".into(),
@@ -613,3 +1202,122 @@ fn expire_stale(state: &mut AppState) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The export has a two-line heading the live buffer does not, so the
+    /// reply starts that much further in; an export that stops at the
+    /// question holds no reply at all.
+    #[test]
+    fn a_reply_is_recovered_from_the_transcript_exported_before_a_restart() {
+        let export = "# claude a1b2c3d4 — workbench transcript\n\nhello\n[consult] what now?\nship it\n\n";
+
+        assert_eq!(reply_from_export(export, 2).as_deref(), Some("ship it"));
+        assert_eq!(reply_from_export(export, 3), None);
+    }
+
+    /// Only what the agent said after the question is the reply: not the
+    /// question itself, and not the tool traffic between its sentences.
+    #[test]
+    fn a_journaled_reply_is_the_agents_prose_after_the_question() {
+        use crate::remote::{Message, Role};
+        let said = |role, text: &str| Message {
+            role,
+            text: text.into(),
+            at: None,
+        };
+        let messages = vec![
+            said(Role::You, "[workbench consult c1 from agent aaaa1111] which lock?"),
+            said(Role::Agent, "Let me look."),
+            said(Role::Tool, "▸ Bash · rg Mutex"),
+            said(Role::Agent, "The session map's. Take it first."),
+        ];
+
+        assert_eq!(
+            reply_from_journal(&messages).as_deref(),
+            Some("Let me look.\n\nThe session map's. Take it first.")
+        );
+        assert_eq!(reply_from_journal(&messages[..1]), None);
+    }
+
+    /// The clocks cross a restart as wall-clock times: a consult five
+    /// minutes old stays five minutes old, so its TTL is not reset.
+    #[test]
+    fn a_saved_consult_keeps_its_age() {
+        let consult = PendingConsult {
+            ticket: "c1".into(),
+            workspace_id: Uuid::new_v4(),
+            from_short: "aaaa1111".into(),
+            from_project: None,
+            group: Some("g1".into()),
+            to_session: Uuid::new_v4(),
+            to_short: "bbbb2222".into(),
+            question: "why?".into(),
+            delivered: true,
+            transcript_base: 40,
+            activity_at_delivery: Some(Instant::now()),
+            journal_at_delivery: None,
+            delivered_at: Some(Instant::now() - Duration::from_secs(60)),
+            created: Instant::now() - Duration::from_secs(300),
+            delivered_wall: Some(Utc::now() - chrono::Duration::seconds(60)),
+            created_wall: Utc::now() - chrono::Duration::seconds(300),
+        };
+
+        let json = serde_json::to_string(&SavedConsult::from_pending(&consult)).unwrap();
+        let saved: SavedConsult = serde_json::from_str(&json).unwrap();
+        let restored = saved.into_pending(consult.workspace_id);
+
+        assert_eq!(restored.transcript_base, 40);
+        assert_eq!(restored.group.as_deref(), Some("g1"));
+        assert!(restored.activity_at_delivery.is_none());
+        let age = restored.created.elapsed().as_secs();
+        assert!((299..=302).contains(&age), "{age}");
+        let since_delivery = restored.delivered_at.unwrap().elapsed().as_secs();
+        assert!((59..=62).contains(&since_delivery), "{since_delivery}");
+        assert_eq!(restored.created_wall, consult.created_wall);
+    }
+
+    /// A consult that has not moved is not news to the disk: saving twice
+    /// writes once, however much time passes between the two.
+    #[test]
+    fn saving_pending_consults_that_did_not_change_writes_once() {
+        let mut state = AppState::default();
+        let workspace =
+            crate::models::Workspace::new("w".into(), std::path::PathBuf::from("/tmp/w"));
+        let workspace_id = workspace.id;
+        state.data.workspaces.push(workspace);
+        state.system.comms.pending.push(PendingConsult {
+            ticket: "c1".into(),
+            workspace_id,
+            from_short: "aaaa1111".into(),
+            from_project: None,
+            group: None,
+            to_session: Uuid::new_v4(),
+            to_short: "bbbb2222".into(),
+            question: "why?".into(),
+            delivered: true,
+            transcript_base: 0,
+            activity_at_delivery: None,
+            journal_at_delivery: None,
+            delivered_at: Some(Instant::now() - Duration::from_secs(60)),
+            created: Instant::now() - Duration::from_secs(300),
+            delivered_wall: Some(Utc::now() - chrono::Duration::seconds(60)),
+            created_wall: Utc::now() - chrono::Duration::seconds(300),
+        });
+
+        let mut writes = 0;
+        save_pending_with(&mut state, |_, _| {
+            writes += 1;
+            Ok(())
+        });
+        std::thread::sleep(Duration::from_millis(5));
+        save_pending_with(&mut state, |_, _| {
+            writes += 1;
+            Ok(())
+        });
+
+        assert_eq!(writes, 1);
+    }
+}
@@ -6,7 +6,9 @@ use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
-use super::handlers::{config, input, navigation, parallel, session, tasks, workspace};
+use super::handlers::{
+    config, diff, input, merge_conflict, navigation, parallel, session, tasks, workspace,
+};
 use super::pty_ops::request_pty_resize;
 
 /// Send an action onto the dispatch channel, logging on failure instead of
@@ -56,11 +58,18 @@ pub fn process_action(
 
             state.tick_animation();
             navigation::handle_drag_auto_scroll(state);
+            if let Some(session_id) = state.keyboard_session() {
+                state
+                    .data
+                    .last_user_focus
+                    .insert(session_id, std::time::Instant::now());
+            }
             let newly_idle = state.update_idle_queue();
 
-            // Agent-to-agent comms: transcript/roster export, consult
-            // delivery and reply capture (see app::comms_tick).
-            super::comms_tick::tick(state, action_tx, &newly_idle);
+            // Agent-to-agent comms: transcript/roster export, queueing
+            // consults and capturing their replies (see app::comms_tick).
+            // The TODO queue delivers them.
+            super::comms_tick::tick(state, &newly_idle);
 
             // Process newly idle sessions
             for session_id in &newly_idle {
@@ -115,6 +124,8 @@ pub fn process_action(
             remote_tick(state, action_tx);
             sync_repository_map(state);
             super::todo_dispatch::tick(state, action_tx);
+            super::verify::tick(state, action_tx);
+            merge_conflict::tick(state, action_tx);
             tasks::sync_selection(state);
             refresh_agent_tasks(state, action_tx);
             refresh_scrollback(state, action_tx);
@@ -195,6 +206,9 @@ pub fn process_action(
         Action::DiffStatsUpdated(stats) => {
             state.system.diff_stats = stats;
         }
+        Action::VerificationFinished { attempt_id, outcome } => {
+            super::verify::finished(state, attempt_id, outcome);
+        }
         Action::PortsScanned(servers) => {
             state.system.dev_servers = servers;
             state.system.port_scan_inflight = false;
@@ -237,12 +251,14 @@ pub fn process_action(
                 Action::CreateSession(_, _, _) | Action::CreateSessionIn(_, _, _, _) | Action::CreateTerminal |
                 Action::ActivateSession(_) | Action::RestartSession(_) | Action::StopSession(_) |
                 Action::KillSession(_) | Action::InitiateDeleteSession(_, _) |
-                Action::ConfirmDeleteSession | Action::CancelPendingDelete | Action::EnterCreateSessionMode |
+                Action::ConfirmDeleteSession | Action::DeleteSession(_) |
+                Action::CancelPendingDelete | Action::EnterCreateSessionMode |
                 Action::EnterSetStartCommandMode | Action::SetStartCommand(_, _) | Action::PinSession(_) |
                 Action::UnpinSession(_) | Action::UnpinFocusedSession | Action::ToggleSplitView |
                 Action::SessionExited(_, _) | Action::PtyOutput(_, _) | Action::SendInput(_, _) |
                 Action::MergeSessionWorktree(_) | Action::SwitchToWorktree(_) |
                 Action::ConfirmMergeWithCommit | Action::CancelMerge |
+                Action::CycleMergeStrategy(_) |
                 Action::SessionWorktreeMergeChecked { .. } |
                 Action::SessionWorktreeMergeFinished { .. } |
                 Action::SessionWorktreeCreated { .. } => {
@@ -298,11 +314,26 @@ pub fn process_action(
                 Action::ParallelWorktreesReady { .. } | Action::ParallelWorktreesFailed { .. } |
                 Action::ParallelMergeFinished { .. } |
                 Action::SelectNextReport | Action::SelectPrevReport |
-                Action::ViewReport | Action::MergeSelectedReport |
+                Action::ViewReport | Action::MergeSelectedReport | Action::RerunVerification |
                 Action::ConfirmParallelMerge | Action::CancelParallelMerge => {
                     parallel::handle_parallel_action(state, action, pty_manager, action_tx, pty_tx)?;
                 }
 
+                // Diff viewer
+                Action::OpenDiff(_) | Action::DiffLoaded { .. } | Action::DiffNavigate(_) |
+                Action::MergeFromDiff | Action::CloseDiff => {
+                    diff::handle_diff_action(state, action, action_tx);
+                }
+
+                // A landing that stopped on conflicts
+                Action::MergeConflicted(_) | Action::ResolveMergeConflict { .. } |
+                Action::AbortMergeConflict | Action::DismissMergeConflict |
+                Action::MergeConflictStaged { .. } | Action::MergeConflictRechecked { .. } => {
+                    merge_conflict::handle_merge_conflict_action(
+                        state, action, pty_manager, action_tx, pty_tx,
+                    )?;
+                }
+
                 // Toast notifications
                 Action::ShowToast(msg, level) => {
                     use crate::app::Toast;
@@ -368,6 +399,7 @@ pub fn process_action(
                 Action::Quit | Action::ConfirmQuit | Action::Tick | Action::Resize(_, _) |
                 Action::ForceRedraw | Action::OpenRepositoryMap |
                 Action::UtilityContentLoaded(_) | Action::DiffStatsUpdated(_) |
+                Action::VerificationFinished { .. } |
                 Action::PortsScanned(_) | Action::ScrollbackLoaded { .. } => {}
             }
 
@@ -921,7 +953,13 @@ fn apply_remote(
     }
 
     // Creating an agent names a project, not a session.
-    if let RemoteCommand::NewAgent { project, provider } = &command {
+    if let RemoteCommand::NewAgent {
+        project,
+        provider,
+        id,
+        options,
+    } = &command
+    {
         let Ok(workspace_id) = project.parse::<uuid::Uuid>() else {
             return;
         };
@@ -939,12 +977,31 @@ fn apply_remote(
             ));
             return;
         }
+        let mut options = options.clone();
+        // The control socket checks the alias before it asks; this is for
+        // the phone, and for two requests racing for the same name.
+        if let Some(alias) = options.alias.take() {
+            let taken = state.data.sessions.get(&workspace_id).is_some_and(|sessions| {
+                sessions.iter().any(|session| {
+                    session
+                        .alias
+                        .as_deref()
+                        .is_some_and(|taken| taken.eq_ignore_ascii_case(&alias))
+                })
+            });
+            if !crate::comms::alias_is_valid(&alias) || taken {
+                crate::logger::warn(format!("new agent cannot be called {alias}; starting it unnamed"));
+            } else {
+                options.alias = Some(alias);
+            }
+        }
         crate::logger::info(format!("phone started a {provider} in {project}"));
-        // Permissions stay on: a prompt is answerable from the phone now, so
-        // there is no reason to hand a remote-started agent a free pass.
+        // Permissions stay on unless the caller asked otherwise: a prompt is
+        // answerable from the phone and the socket, so there is no reason to
+        // hand a remote-started agent a free pass by default.
         dispatch_action(
             action_tx,
-            Action::CreateSessionIn(workspace_id, agent_type, false, false),
+            Action::CreateSessionIn(workspace_id, *id, agent_type, options),
         );
         return;
     }
@@ -953,7 +1010,17 @@ fn apply_remote(
         RemoteCommand::Todo { agent, .. }
         | RemoteCommand::Reply { agent, .. }
         | RemoteCommand::Answer { agent, .. }
-        | RemoteCommand::Focus { agent } => agent.clone(),
+        | RemoteCommand::Focus { agent }
+        | RemoteCommand::TodoRemove { agent, .. }
+        | RemoteCommand::TodoMove { agent, .. }
+        | RemoteCommand::TodoPause { agent, .. }
+        | RemoteCommand::TodoClearDone { agent }
+        | RemoteCommand::Stop { agent }
+        | RemoteCommand::Restart { agent }
+        | RemoteCommand::Kill { agent }
+        | RemoteCommand::Delete { agent }
+        | RemoteCommand::Screen { agent, .. }
+        | RemoteCommand::Keys { agent, .. } => agent.clone(),
         // Handled above.
         RemoteCommand::NewAgent { .. } | RemoteCommand::Subscribe { .. } => return,
     };
@@ -1015,11 +1082,87 @@ fn apply_remote(
                 )),
             }
         }
+        // The same queue operations the TODO pane's keys perform.
+        RemoteCommand::TodoRemove { todo, .. } => {
+            let removed = state
+                .get_session_mut(session_id)
+                .and_then(|session| session.todo_queue.remove(todo));
+            if let Some(ticket) = removed.as_ref().and_then(|item| item.consult_ticket()) {
+                super::comms_tick::decline(state, ticket);
+            }
+            super::handlers::save_state(state, "failed to save the todo queue");
+        }
+        RemoteCommand::TodoMove { todo, by, .. } => {
+            if let Some(session) = state.get_session_mut(session_id) {
+                session.todo_queue.shift(todo, by);
+            }
+            super::handlers::save_state(state, "failed to save the todo queue");
+        }
+        RemoteCommand::TodoPause { paused, .. } => {
+            if let Some(session) = state.get_session_mut(session_id) {
+                session.todo_queue.paused = paused;
+            }
+            super::handlers::save_state(state, "failed to save the todo queue");
+        }
+        RemoteCommand::TodoClearDone { .. } => {
+            if let Some(session) = state.get_session_mut(session_id) {
+                session.todo_queue.clear_completed();
+            }
+            super::handlers::save_state(state, "failed to save the todo queue");
+        }
         RemoteCommand::Focus { .. } => {
             state.system.remote_focus = Some(session_id);
             // A different conversation means the cached one is of no use.
             state.system.remote_thread = None;
         }
+        // The session list's keys, with the guards those keys have. The
+        // socket checked them against the snapshot already; they are checked
+        // again here because the agent may have moved on since.
+        RemoteCommand::Stop { .. } => {
+            if state.system.pty_handles.contains_key(&session_id) {
+                crate::logger::info(format!("socket interrupted {agent}"));
+                dispatch_action(action_tx, Action::StopSession(session_id));
+            }
+        }
+        RemoteCommand::Restart { .. } => {
+            let stopped = state.get_session(session_id).is_some_and(|session| {
+                matches!(
+                    session.status,
+                    crate::models::SessionStatus::Stopped | crate::models::SessionStatus::Errored
+                )
+            });
+            if stopped {
+                crate::logger::info(format!("socket restarted {agent}"));
+                dispatch_action(action_tx, Action::RestartSession(session_id));
+            } else {
+                crate::logger::info(format!("socket asked to restart {agent}, which is running"));
+            }
+        }
+        RemoteCommand::Kill { .. } => {
+            crate::logger::info(format!("socket killed {agent}"));
+            dispatch_action(action_tx, Action::KillSession(session_id));
+        }
+        RemoteCommand::Delete { .. } => {
+            crate::logger::info(format!("socket deleted {agent}"));
+            dispatch_action(action_tx, Action::DeleteSession(session_id));
+        }
+        RemoteCommand::Keys { keys, .. } => {
+            // Checked again here: the snapshot the socket refused from can be
+            // a tick behind you clicking into the pane.
+            if state.user_is_at(session_id) {
+                crate::logger::info(format!("socket keys for {agent} held back: you are at it"));
+            } else if state.system.pty_handles.contains_key(&session_id) {
+                super::agent_input::press_keys(action_tx, session_id, keys);
+            }
+        }
+        RemoteCommand::Screen {
+            scrollback, reply, ..
+        } => {
+            if let Some(text) = crate::remote::screen_text(state, session_id, scrollback) {
+                // The caller gave up waiting; nothing to tell anyone.
+                let _ = reply.send(text);
+            }
+        }
         // Handled before the session lookup, which they do not need.
         RemoteCommand::NewAgent { .. } | RemoteCommand::Subscribe { .. } => {}
     }
@@ -1542,23 +1685,122 @@ mod tests {
             crate::remote::RemoteCommand::NewAgent {
                 project: workspace_id.to_string(),
                 provider: "codex".into(),
+                id: uuid::Uuid::new_v4(),
+                options: Default::default(),
             },
             &tx,
         );
 
         match rx.try_recv() {
-            Ok(Action::CreateSessionIn(target, agent, dangerous, worktree)) => {
+            Ok(Action::CreateSessionIn(target, _, agent, options)) => {
                 assert_eq!(target, workspace_id);
                 assert_eq!(agent, AgentType::Codex);
                 // A prompt is answerable from the phone, so a remotely started
                 // agent keeps its permission gates.
-                assert!(!dangerous);
-                assert!(!worktree);
+                assert!(!options.dangerously_skip_permissions);
+                assert!(!options.worktree);
+            }
+            other => panic!("expected a session to be created, got {other:?}"),
+        }
+    }
+
+    /// The id the caller picked is the one the session gets — it is what the
+    /// caller is waiting to see — and a name already in use is dropped rather
+    /// than shared, since two agents answering to one alias is worse than one
+    /// agent answering to none.
+    #[test]
+    fn a_remote_spawn_keeps_its_id_and_gives_up_a_taken_alias() {
+        let (mut state, workspace_id) = state_with_workspace();
+        let existing = add_agent(&mut state, workspace_id, SessionStatus::Running, None);
+        state.get_session_mut(existing).unwrap().alias = Some("reviewer".into());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let wanted = uuid::Uuid::new_v4();
+
+        apply_remote(
+            &mut state,
+            crate::remote::RemoteCommand::NewAgent {
+                project: workspace_id.to_string(),
+                provider: "claude".into(),
+                id: wanted,
+                options: crate::app::SpawnOptions {
+                    alias: Some("Reviewer".into()),
+                    prompt: Some("look at the diff".into()),
+                    ..Default::default()
+                },
+            },
+            &tx,
+        );
+
+        match rx.try_recv() {
+            Ok(Action::CreateSessionIn(_, id, _, options)) => {
+                assert_eq!(id, wanted);
+                assert_eq!(options.alias, None);
+                assert_eq!(options.prompt.as_deref(), Some("look at the diff"));
             }
             other => panic!("expected a session to be created, got {other:?}"),
         }
     }
 
+    /// A handoff successor that stays in its predecessor's checkout shares
+    /// the worktree. Stopping and deleting the old agent must not pull the
+    /// tree out from under the new one; the last session out removes it.
+    #[tokio::test]
+    async fn a_shared_worktree_goes_with_the_last_session_using_it() {
+        let repo = tempfile::tempdir().unwrap();
+        for args in [
+            vec!["init", "-q"],
+            vec!["config", "user.email", "t@t"],
+            vec!["config", "user.name", "t"],
+            vec!["commit", "-q", "--allow-empty", "-m", "init"],
+        ] {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(repo.path())
+                .output()
+                .unwrap();
+        }
+        let tree = repo.path().join("tree");
+        crate::git::create_worktree(repo.path(), "handed-on", &tree).unwrap();
+
+        let mut state = AppState::default();
+        let workspace = Workspace::new("w".into(), repo.path().to_path_buf());
+        let workspace_id = workspace.id;
+        state.data.workspaces.push(workspace);
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let session = Session::new_with_worktree(
+                workspace_id,
+                AgentType::Codex,
+                false,
+                tree.clone(),
+                "handed-on".into(),
+            );
+            ids.push(session.id);
+            state.data.sessions.entry(workspace_id).or_default().push(session);
+        }
+        let pty_manager = PtyManager::new();
+        let (action_tx, _) = mpsc::unbounded_channel();
+        let (pty_tx, _) = mpsc::channel(1);
+        let delete = |state: &mut AppState, id| {
+            process_action(state, Action::DeleteSession(id), &pty_manager, &action_tx, &pty_tx)
+                .unwrap();
+        };
+
+        delete(&mut state, ids[0]);
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        assert!(tree.exists(), "the successor is still working in it");
+
+        delete(&mut state, ids[1]);
+        let gone = async {
+            while tree.exists() {
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+        };
+        tokio::time::timeout(std::time::Duration::from_secs(10), gone)
+            .await
+            .expect("the last session out removes the worktree");
+    }
+
     #[test]
     fn a_nonsense_project_or_provider_starts_nothing() {
         let (mut state, workspace_id) = state_with_workspace();
@@ -1568,14 +1810,20 @@ mod tests {
             crate::remote::RemoteCommand::NewAgent {
                 project: workspace_id.to_string(),
                 provider: "definitely-not-an-agent".into(),
+                id: uuid::Uuid::new_v4(),
+                options: Default::default(),
             },
             crate::remote::RemoteCommand::NewAgent {
                 project: uuid::Uuid::new_v4().to_string(),
                 provider: "claude".into(),
+                id: uuid::Uuid::new_v4(),
+                options: Default::default(),
             },
             crate::remote::RemoteCommand::NewAgent {
                 project: "not-a-uuid".into(),
                 provider: "claude".into(),
+                id: uuid::Uuid::new_v4(),
+                options: Default::default(),
             },
         ] {
             apply_remote(&mut state, command, &tx);
@@ -1583,6 +1831,65 @@ mod tests {
         assert!(rx.try_recv().is_err(), "nothing should have been started");
     }
 
+    /// The snapshot the socket checked can be a tick old. An agent that came
+    /// back in the meantime is not restarted on top of itself.
+    #[test]
+    fn a_remote_restart_leaves_a_running_agent_alone() {
+        let (mut state, workspace_id) = state_with_workspace();
+        let running = add_agent(&mut state, workspace_id, SessionStatus::Running, None);
+        let stopped = add_agent(&mut state, workspace_id, SessionStatus::Stopped, None);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        for id in [running, stopped] {
+            let agent = state.get_session(id).unwrap().short_id();
+            apply_remote(&mut state, crate::remote::RemoteCommand::Restart { agent }, &tx);
+        }
+
+        assert!(matches!(rx.try_recv(), Ok(Action::RestartSession(id)) if id == stopped));
+        assert!(rx.try_recv().is_err(), "the running agent must not be restarted");
+    }
+
+    /// The socket's queue edits are the TODO pane's keys by another route, so
+    /// they land on the same queue and are saved the same way.
+    #[test]
+    fn the_socket_edits_a_queue_the_way_the_todo_pane_does() {
+        let (mut state, workspace_id) = state_with_workspace();
+        let id = add_agent(&mut state, workspace_id, SessionStatus::Running, None);
+        let short = state.get_session(id).unwrap().short_id();
+        let (first, second, third) = {
+            let queue = &mut state.get_session_mut(id).unwrap().todo_queue;
+            (queue.add("first"), queue.add("second"), queue.add("third"))
+        };
+        state.get_session_mut(id).unwrap().todo_queue.mark_running(first);
+        state.get_session_mut(id).unwrap().todo_queue.finish_running();
+        let (tx, _rx) = mpsc::unbounded_channel();
+
+        for command in [
+            crate::remote::RemoteCommand::TodoMove {
+                agent: short.clone(),
+                todo: third,
+                by: -9,
+            },
+            crate::remote::RemoteCommand::TodoRemove {
+                agent: short.clone(),
+                todo: second,
+            },
+            crate::remote::RemoteCommand::TodoPause {
+                agent: short.clone(),
+                paused: true,
+            },
+            crate::remote::RemoteCommand::TodoClearDone { agent: short },
+        ] {
+            apply_remote(&mut state, command, &tx);
+        }
+
+        let queue = &state.get_session(id).unwrap().todo_queue;
+        let left: Vec<&str> = queue.items.iter().map(|item| item.text.as_str()).collect();
+        assert_eq!(left, vec!["third"]);
+        assert!(queue.paused);
+        assert!(state.system.state_dirty, "an edit from the socket must be saved");
+    }
+
     /// A running agent is typed to directly — no queue, no delay.
     #[test]
     fn messaging_a_running_agent_goes_straight_to_its_terminal() {
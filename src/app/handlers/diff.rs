@@ -0,0 +1,163 @@
+use crate::app::{Action, AppState, DiffSubject, DiffView, InputMode};
+use crate::git;
+use std::path::PathBuf;
+use tokio::sync::mpsc;
+
+use super::report_background_error;
+
+// The viewer opens at once with a loading frame and git runs on a blocking
+// thread: a branch that touched a few hundred files takes long enough to
+// diff that doing it inline would freeze the whole TUI.
+
+/// Where to run the diff and what to compare against. The base is resolved
+/// off the event loop when the workspace is on a detached HEAD, since that
+/// takes a git call.
+struct DiffTarget {
+    checkout: PathBuf,
+    base: Option<String>,
+    workspace_path: PathBuf,
+    title: String,
+}
+
+pub fn handle_diff_action(
+    state: &mut AppState,
+    action: Action,
+    action_tx: &mpsc::UnboundedSender<Action>,
+) {
+    match action {
+        Action::OpenDiff(subject) => open(state, subject, action_tx),
+        Action::DiffLoaded { request_id, result } => {
+            if let Some(view) = state.ui.diff_view.as_mut() {
+                if view.request_id == request_id {
+                    view.files = Some(result);
+                    view.selected_file = 0;
+                    view.scroll = 0;
+                }
+            }
+        }
+        Action::DiffNavigate(nav) => {
+            if let Some(view) = state.ui.diff_view.as_mut() {
+                view.navigate(nav);
+            }
+        }
+        Action::MergeFromDiff => {
+            let Some(view) = close(state) else {
+                return;
+            };
+            // The same doors the session list and the Reports tab open, so
+            // the merge from here confirms and commits exactly as they do.
+            match view.subject {
+                DiffSubject::Session(session_id) => {
+                    let _ = action_tx.send(Action::MergeSessionWorktree(session_id));
+                }
+                DiffSubject::Attempt(attempt_id) => {
+                    super::parallel::open_merge_confirm(state, attempt_id);
+                }
+            }
+        }
+        Action::CloseDiff => {
+            close(state);
+        }
+        _ => {}
+    }
+}
+
+fn close(state: &mut AppState) -> Option<DiffView> {
+    if state.ui.input_mode == InputMode::DiffViewer {
+        state.ui.input_mode = InputMode::Normal;
+    }
+    state.ui.diff_view.take()
+}
+
+fn open(state: &mut AppState, subject: DiffSubject, action_tx: &mpsc::UnboundedSender<Action>) {
+    state.ui.diff_request_id += 1;
+    let request_id = state.ui.diff_request_id;
+    state.ui.input_mode = InputMode::DiffViewer;
+
+    let target = match target(state, subject) {
+        Ok(target) => target,
+        Err(message) => {
+            let mut view = DiffView::new(subject, "Diff".to_string(), request_id);
+            view.files = Some(Err(message));
+            state.ui.diff_view = Some(view);
+            return;
+        }
+    };
+    state.ui.diff_view = Some(DiffView::new(subject, target.title, request_id));
+
+    let tx = action_tx.clone();
+    let DiffTarget {
+        checkout,
+        base,
+        workspace_path,
+        ..
+    } = target;
+    tokio::task::spawn_blocking(move || {
+        let result = base
+            .map(Ok)
+            .unwrap_or_else(|| git::get_head_commit(&workspace_path))
+            .and_then(|base| git::branch_diff(&checkout, &base))
+            .map_err(|err| format!("{err:#}"));
+        if let Err(err) = tx.send(Action::DiffLoaded { request_id, result }) {
+            report_background_error("failed to report diff", err);
+        }
+    });
+}
+
+fn target(state: &AppState, subject: DiffSubject) -> Result<DiffTarget, String> {
+    match subject {
+        DiffSubject::Session(session_id) => {
+            let session = state
+                .get_session(session_id)
+                .ok_or_else(|| "That agent is gone".to_string())?;
+            let checkout = session
+                .worktree_path
+                .clone()
+                .ok_or_else(|| format!("{} works in the main checkout", session.display_name()))?;
+            let workspace_path = state
+                .data
+                .workspaces
+                .iter()
+                .find(|w| w.id == session.workspace_id)
+                .map(|w| w.path.clone())
+                .ok_or_else(|| "The agent's project is gone".to_string())?;
+            let base = git::get_current_branch_fast(&workspace_path);
+            let branch = session.worktree_branch.clone().unwrap_or_else(|| "worktree".into());
+            let title = format!(
+                "{} · {} vs {}",
+                session.display_name(),
+                branch,
+                base.as_deref().unwrap_or("HEAD")
+            );
+            Ok(DiffTarget {
+                checkout,
+                base,
+                workspace_path,
+                title,
+            })
+        }
+        DiffSubject::Attempt(attempt_id) => {
+            let (ws, task, attempt) = state
+                .data
+                .workspaces
+                .iter()
+                .flat_map(|ws| ws.parallel_tasks.iter().map(move |task| (ws, task)))
+                .find_map(|(ws, task)| task.get_attempt(attempt_id).map(|a| (ws, task, a)))
+                .ok_or_else(|| "That attempt is gone".to_string())?;
+            let short_commit: String = task.source_commit.chars().take(8).collect();
+            let title = format!(
+                "{} attempt · {} vs {} @ {}",
+                attempt.agent_type.display_name(),
+                attempt.branch_name,
+                task.source_branch,
+                short_commit
+            );
+            Ok(DiffTarget {
+                checkout: attempt.worktree_path.clone(),
+                base: Some(task.source_commit.clone()),
+                workspace_path: ws.path.clone(),
+                title,
+            })
+        }
+    }
+}
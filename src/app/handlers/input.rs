@@ -59,7 +59,11 @@ pub fn handle_input_action(state: &mut AppState, action: Action) -> Result<()> {
                 state.ui.file_browser.query.push(c);
                 state.apply_file_browser_filter();
             } else if state.ui.input_mode == InputMode::CreateParallelTask {
-                state.ui.parallel_task.prompt.push(c);
+                if state.ui.parallel_task.editing_verify() {
+                    state.ui.parallel_task.verify_command.push(c);
+                } else {
+                    state.ui.parallel_task.prompt.push(c);
+                }
             } else {
                 state.ui.input_buffer.push(c);
             }
@@ -71,7 +75,11 @@ pub fn handle_input_action(state: &mut AppState, action: Action) -> Result<()> {
                 state.ui.file_browser.query.pop();
                 state.apply_file_browser_filter();
             } else if state.ui.input_mode == InputMode::CreateParallelTask {
-                state.ui.parallel_task.prompt.pop();
+                if state.ui.parallel_task.editing_verify() {
+                    state.ui.parallel_task.verify_command.pop();
+                } else {
+                    state.ui.parallel_task.prompt.pop();
+                }
             } else {
                 state.ui.input_buffer.pop();
             }
@@ -158,6 +166,10 @@ pub fn handle_input_action(state: &mut AppState, action: Action) -> Result<()> {
                 state.ui.input_mode = InputMode::CreateParallelTask;
                 state.ui.parallel_task.prompt.clear();
                 state.ui.parallel_task.agent_idx = 0;
+                state.ui.parallel_task.verify_command = state
+                    .selected_workspace()
+                    .and_then(|ws| ws.verify_command.clone())
+                    .unwrap_or_default();
                 // Pre-select agents that have running sessions in the workspace
                 let ws_id = state.selected_workspace().map(|w| w.id);
                 if let Some(workspace_id) = ws_id {
@@ -186,8 +198,9 @@ pub fn handle_input_action(state: &mut AppState, action: Action) -> Result<()> {
         }
         Action::NextParallelAgent => {
             let agent_count = state.ui.parallel_task.agents.len();
-            // Total items = agents + 2 (dangerous mode + report checkboxes)
-            let total_items = agent_count + 2;
+            // Total items = agents + 2 checkboxes (dangerous mode, report)
+            // + the verify command field
+            let total_items = agent_count + 3;
             if total_items > 0 {
                 state.ui.parallel_task.agent_idx =
                     (state.ui.parallel_task.agent_idx + 1) % total_items;
@@ -195,8 +208,9 @@ pub fn handle_input_action(state: &mut AppState, action: Action) -> Result<()> {
         }
         Action::PrevParallelAgent => {
             let agent_count = state.ui.parallel_task.agents.len();
-            // Total items = agents + 2 (dangerous mode + report checkboxes)
-            let total_items = agent_count + 2;
+            // Total items = agents + 2 checkboxes (dangerous mode, report)
+            // + the verify command field
+            let total_items = agent_count + 3;
             if total_items > 0 {
                 if state.ui.parallel_task.agent_idx == 0 {
                     state.ui.parallel_task.agent_idx = total_items - 1;
@@ -0,0 +1,445 @@
+//! A landing that stopped on conflicts, and getting it unstuck.
+//!
+//! `app::merge::land` reports the clash instead of leaving it behind
+//! silently; the modal lists the conflicted files and offers to back out,
+//! to leave things as they are for a human, or to hand the conflict to an
+//! agent — the one whose branch it is, or a fresh one started in its
+//! worktree. A handed-over conflict is moved into that worktree first (see
+//! `merge::stage_in_worktree`) and goes to the agent as an item at the head
+//! of its TODO queue, so it waits for a turn to end like anything else we
+//! send. The end of the turn that item started is our cue: the worktree is
+//! looked at again, and the landing is retried once nothing is left
+//! unmerged — or the modal comes back with what still is.
+
+use crate::app::merge::{self, MergeConflict, MergeSource};
+use crate::app::{Action, AppState, InputMode, SpawnOptions};
+use crate::models::{AgentType, SessionStatus, TodoState};
+use crate::pty::PtyManager;
+use anyhow::Result;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::{
+    parallel, report_background_error, report_runtime_error, save_state, session_worktree,
+};
+
+pub fn handle_merge_conflict_action(
+    state: &mut AppState,
+    action: Action,
+    pty_manager: &PtyManager,
+    action_tx: &mpsc::UnboundedSender<Action>,
+    pty_tx: &mpsc::Sender<Action>,
+) -> Result<()> {
+    match action {
+        Action::MergeConflicted(conflict) => {
+            state.ui.set_task_status(format!(
+                "Landing {} stopped on conflicts in {} files",
+                conflict.landing.branch,
+                conflict.conflict.files.len()
+            ));
+            open(state, conflict);
+        }
+        Action::AbortMergeConflict => {
+            let Some(conflict) = close(state) else {
+                return Ok(());
+            };
+            tokio::task::spawn_blocking(move || {
+                if let Err(err) = merge::abort(&conflict.conflict) {
+                    report_background_error("failed to abort conflicted merge", err);
+                }
+            });
+            state.ui.set_task_status("Merge backed out — nothing changed");
+        }
+        Action::DismissMergeConflict => {
+            let Some(conflict) = close(state) else {
+                return Ok(());
+            };
+            let status = match &conflict.conflict.in_progress {
+                Some(dir) => format!("Conflicted merge left as it is in {}", dir.display()),
+                None => "Nothing was merged".to_string(),
+            };
+            state.ui.set_task_status(status);
+        }
+        Action::ResolveMergeConflict { fresh } => delegate(state, fresh, action_tx),
+        Action::MergeConflictStaged {
+            conflict,
+            fresh,
+            error,
+        } => {
+            if let Some(err) = error {
+                report_runtime_error(
+                    state,
+                    "failed to move a merge conflict into the worktree",
+                    &err,
+                    "Could not hand the conflict over",
+                );
+                state.ui.set_task_status(format!("Could not hand the conflict over: {err}"));
+            } else if conflict.resolved() {
+                reland(state, *conflict, action_tx);
+            } else {
+                hand_over(state, *conflict, fresh, pty_manager, action_tx, pty_tx);
+            }
+        }
+        Action::MergeConflictRechecked { conflict, error } => {
+            if let Some(err) = error {
+                report_runtime_error(
+                    state,
+                    "failed to recheck a merge conflict",
+                    &err,
+                    "Could not check the conflict",
+                );
+            } else if conflict.resolved() {
+                reland(state, *conflict, action_tx);
+            } else {
+                state.ui.set_task_status(format!(
+                    "{} files on {} are still conflicted",
+                    conflict.conflict.files.len(),
+                    conflict.landing.branch
+                ));
+                open(state, conflict);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Look at the conflicts whose resolving agent's turn has ended.
+///
+/// Called on the tick. A resolution whose queue item or agent has gone is
+/// dropped: somebody took the conflict back, and it is theirs now.
+pub fn tick(state: &mut AppState, action_tx: &mpsc::UnboundedSender<Action>) {
+    let mut idx = 0;
+    while idx < state.system.merge_conflicts.len() {
+        let Some((session_id, todo_id)) = state.system.merge_conflicts[idx].resolver else {
+            state.system.merge_conflicts.remove(idx);
+            continue;
+        };
+        let item = state
+            .get_session(session_id)
+            .and_then(|s| s.todo_queue.items.iter().find(|item| item.id == todo_id))
+            .map(|item| item.state);
+        match item {
+            Some(TodoState::Done) => {
+                let mut conflict = state.system.merge_conflicts.remove(idx);
+                conflict.resolver = None;
+                let tx = action_tx.clone();
+                tokio::task::spawn_blocking(move || {
+                    let error = merge::recheck(&mut conflict).err().map(|e| format!("{e:#}"));
+                    let conflict = Box::new(conflict);
+                    if let Err(err) = tx.send(Action::MergeConflictRechecked { conflict, error }) {
+                        report_background_error("failed to report merge conflict check", err);
+                    }
+                });
+            }
+            Some(_) => idx += 1,
+            None => {
+                let conflict = state.system.merge_conflicts.remove(idx);
+                state.ui.set_task_status(format!(
+                    "Stopped waiting on the conflict in {} — its agent or queue item is gone",
+                    conflict.landing.branch
+                ));
+                save_state(state, "failed to save merge conflicts");
+            }
+        }
+    }
+}
+
+/// Put back the conflicts a restart interrupted. One handed to an agent
+/// goes back to waiting on it — its queue item was put back to pending
+/// with the rest of the queue, and runs again when the agent does. One
+/// that was on screen comes back on screen, unless the checkout it was
+/// waiting in has since been finished or backed out by hand.
+pub fn restore(state: &mut AppState, conflicts: Vec<MergeConflict>) {
+    for conflict in conflicts {
+        if conflict.resolver.is_some() {
+            state.system.merge_conflicts.push(conflict);
+            continue;
+        }
+        let settled = conflict
+            .conflict
+            .in_progress
+            .as_deref()
+            .is_some_and(|dir| !crate::git::operation_in_progress(dir));
+        if !settled && state.ui.merge_conflict.is_none() {
+            open(state, Box::new(conflict));
+        }
+    }
+}
+
+// Both save: the conflict on screen is part of what is persisted.
+fn open(state: &mut AppState, conflict: Box<MergeConflict>) {
+    state.ui.merge_conflict = Some(conflict);
+    state.ui.input_mode = InputMode::MergeConflict;
+    save_state(state, "failed to save merge conflict");
+}
+
+fn close(state: &mut AppState) -> Option<Box<MergeConflict>> {
+    if state.ui.input_mode == InputMode::MergeConflict {
+        state.ui.input_mode = InputMode::Normal;
+    }
+    save_state(state, "failed to save merge conflict");
+    state.ui.merge_conflict.take()
+}
+
+/// The running agent whose branch it is, if there is one.
+pub fn originating_agent(state: &AppState, source: MergeSource) -> Option<Uuid> {
+    let session_id = match source {
+        MergeSource::Session(session_id) => session_id,
+        MergeSource::Attempt(attempt_id) => state
+            .data
+            .workspaces
+            .iter()
+            .flat_map(|ws| ws.parallel_tasks.iter())
+            .find_map(|task| task.get_attempt(attempt_id))?
+            .session_id,
+    };
+    state
+        .get_session(session_id)
+        .filter(|s| s.status == SessionStatus::Running)
+        .map(|s| s.id)
+}
+
+fn delegate(state: &mut AppState, fresh: bool, action_tx: &mpsc::UnboundedSender<Action>) {
+    let Some(source) = state.ui.merge_conflict.as_ref().map(|c| c.source) else {
+        return;
+    };
+    if !fresh && originating_agent(state, source).is_none() {
+        state.ui.set_task_status("That agent is not running — f starts a fresh one");
+        return;
+    }
+    let Some(mut conflict) = close(state) else {
+        return;
+    };
+    state.ui.set_task_status("Moving the conflict into the agent's worktree…");
+
+    let tx = action_tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let error = merge::stage_in_worktree(&mut conflict)
+            .err()
+            .map(|e| format!("{e:#}"));
+        if let Err(err) = tx.send(Action::MergeConflictStaged {
+            conflict,
+            fresh,
+            error,
+        }) {
+            report_background_error("failed to report staged merge conflict", err);
+        }
+    });
+}
+
+/// Put the conflict at the head of an agent's queue and start waiting on
+/// it.
+fn hand_over(
+    state: &mut AppState,
+    mut conflict: MergeConflict,
+    fresh: bool,
+    pty_manager: &PtyManager,
+    action_tx: &mpsc::UnboundedSender<Action>,
+    pty_tx: &mpsc::Sender<Action>,
+) {
+    let prompt = conflict.prompt();
+    let resolver = if fresh {
+        start_fresh_agent(state, &conflict, prompt, pty_manager, action_tx, pty_tx)
+    } else {
+        originating_agent(state, conflict.source).and_then(|session_id| {
+            let session = state.get_session_mut(session_id)?;
+            Some((session_id, session.todo_queue.add_next(prompt)))
+        })
+    };
+    let Some((session_id, todo_id)) = resolver else {
+        // Still staged in the worktree; the modal can try someone else.
+        state.ui.set_task_status("No agent to hand the conflict to");
+        open(state, Box::new(conflict));
+        return;
+    };
+
+    let name = state
+        .get_session(session_id)
+        .map(|s| s.display_name())
+        .unwrap_or_default();
+    state.ui.set_task_status(format!(
+        "{} conflicted files on {} handed to {}",
+        conflict.conflict.files.len(),
+        conflict.landing.branch,
+        name
+    ));
+    conflict.resolver = Some((session_id, todo_id));
+    state.system.merge_conflicts.push(conflict);
+    save_state(state, "failed to save merge conflict hand-over");
+}
+
+/// A new agent of the same kind as the branch's, in the branch's worktree,
+/// with the conflict as the first thing on its queue.
+fn start_fresh_agent(
+    state: &mut AppState,
+    conflict: &MergeConflict,
+    prompt: String,
+    pty_manager: &PtyManager,
+    action_tx: &mpsc::UnboundedSender<Action>,
+    pty_tx: &mpsc::Sender<Action>,
+) -> Option<(Uuid, Uuid)> {
+    let (workspace_id, agent_type) = source_agent(state, conflict.source)?;
+    let options = SpawnOptions {
+        checkout: Some((conflict.landing.worktree_path.clone(), conflict.landing.branch.clone())),
+        prompt: Some(prompt),
+        ..SpawnOptions::default()
+    };
+    let session_id = super::session::create_session_in(
+        state,
+        workspace_id,
+        Uuid::new_v4(),
+        agent_type,
+        options,
+        pty_manager,
+        action_tx,
+        pty_tx,
+    )?;
+    let todo_id = state.get_session(session_id)?.todo_queue.items.first()?.id;
+    Some((session_id, todo_id))
+}
+
+/// The workspace and kind of agent the branch belongs to.
+fn source_agent(state: &AppState, source: MergeSource) -> Option<(Uuid, AgentType)> {
+    match source {
+        MergeSource::Session(session_id) => state
+            .get_session(session_id)
+            .map(|s| (s.workspace_id, s.agent_type.clone())),
+        MergeSource::Attempt(attempt_id) => state.data.workspaces.iter().find_map(|ws| {
+            ws.parallel_tasks
+                .iter()
+                .find_map(|task| task.get_attempt(attempt_id))
+                .map(|attempt| (ws.id, attempt.agent_type.clone()))
+        }),
+    }
+}
+
+/// Run the landing again, the way it was first confirmed.
+fn reland(
+    state: &mut AppState,
+    conflict: MergeConflict,
+    action_tx: &mpsc::UnboundedSender<Action>,
+) {
+    state.ui.set_task_status(format!(
+        "Conflicts on {} resolved — landing it again",
+        conflict.landing.branch
+    ));
+    let strategy = conflict.landing.strategy;
+    match conflict.source {
+        MergeSource::Session(session_id) => {
+            session_worktree::start_worktree_merge(state, action_tx, session_id, strategy);
+        }
+        MergeSource::Attempt(attempt_id) => {
+            let landed = parallel::select_parallel_winner(state, attempt_id, strategy, action_tx);
+            if let Err(err) = landed {
+                report_runtime_error(
+                    state,
+                    "failed to land a resolved attempt",
+                    err,
+                    "Could not land the resolved attempt",
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::merge::{Conflict, Landing};
+    use crate::models::{MergeStrategy, Session, Workspace};
+    use std::path::PathBuf;
+
+    fn conflict(source: MergeSource) -> MergeConflict {
+        MergeConflict {
+            source,
+            landing: Landing {
+                strategy: MergeStrategy::Merge,
+                workspace_path: PathBuf::from("/tmp/ws"),
+                worktree_path: PathBuf::from("/tmp/ws/.worktrees/x"),
+                branch: "agent-x".into(),
+                target: None,
+                commit_message: String::new(),
+                fallback_subject: String::new(),
+                report: None,
+            },
+            conflict: Conflict {
+                files: Vec::new(),
+                in_progress: None,
+                target: "main".into(),
+            },
+            resolver: None,
+        }
+    }
+
+    /// Waiting ends with the turn the conflict started; a resolution whose
+    /// item was deleted from the queue is given up on rather than waited
+    /// for forever.
+    #[tokio::test]
+    async fn the_end_of_the_resolving_turn_triggers_a_recheck() {
+        let mut state = AppState::default();
+        let workspace = Workspace::new("ws".into(), PathBuf::from("/tmp/ws"));
+        let workspace_id = workspace.id;
+        let session = Session::new(workspace_id, AgentType::Claude, false);
+        let session_id = session.id;
+        state.data.workspaces.push(workspace);
+        state.data.sessions.insert(workspace_id, vec![session]);
+
+        let session = state.get_session_mut(session_id).unwrap();
+        let todo_id = session.todo_queue.add_next("resolve it");
+        let gone_id = session.todo_queue.add("deleted later");
+        session.todo_queue.remove(gone_id);
+        for todo in [todo_id, gone_id] {
+            let mut waiting = conflict(MergeSource::Session(session_id));
+            waiting.resolver = Some((session_id, todo));
+            state.system.merge_conflicts.push(waiting);
+        }
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        tick(&mut state, &tx);
+        assert_eq!(state.system.merge_conflicts.len(), 1, "the orphan is dropped");
+        assert!(rx.try_recv().is_err(), "nothing to recheck mid-turn");
+
+        let session = state.get_session_mut(session_id).unwrap();
+        session.todo_queue.mark_running(todo_id);
+        session.todo_queue.finish_running();
+        tick(&mut state, &tx);
+        assert!(state.system.merge_conflicts.is_empty());
+        // The worktree doesn't exist, so the check comes back failed — but
+        // it does come back.
+        let Some(Action::MergeConflictRechecked { error, .. }) = rx.recv().await else {
+            panic!("expected a recheck");
+        };
+        assert!(error.is_some());
+    }
+
+    /// Through the state file and back: a handed-over conflict is waited on
+    /// again, the one on screen reopens, and one whose merge was finished
+    /// by hand while workbench was closed is let go.
+    #[test]
+    fn conflicts_in_hand_survive_a_restart() {
+        let session_id = Uuid::new_v4();
+        let mut handed = conflict(MergeSource::Session(session_id));
+        handed.resolver = Some((session_id, Uuid::new_v4()));
+        let on_screen = conflict(MergeSource::Attempt(Uuid::new_v4()));
+        let mut settled = conflict(MergeSource::Session(session_id));
+        settled.conflict.in_progress = Some(PathBuf::from("/nonexistent/checkout"));
+
+        let json = crate::persistence::serialize_state(
+            &[],
+            &Default::default(),
+            &Default::default(),
+            &[settled, handed, on_screen],
+        )
+        .unwrap();
+        let persisted: crate::persistence::PersistedState = serde_json::from_str(&json).unwrap();
+        let mut state = AppState::default();
+        restore(&mut state, persisted.merge_conflicts);
+
+        assert_eq!(state.system.merge_conflicts.len(), 1);
+        assert_eq!(state.system.merge_conflicts[0].source, MergeSource::Session(session_id));
+        assert_eq!(state.ui.input_mode, InputMode::MergeConflict);
+        let reopened = state.ui.merge_conflict.as_ref().unwrap();
+        assert!(matches!(reopened.source, MergeSource::Attempt(_)));
+    }
+}
@@ -1,5 +1,7 @@
 pub mod config;
+pub mod diff;
 pub mod input;
+pub mod merge_conflict;
 pub mod navigation;
 pub mod parallel;
 pub mod session;
@@ -71,10 +73,19 @@ pub(crate) fn flush_dirty_state(
     }
 
     let notepad_contents = state.notepad_content_for_persistence();
+    // The conflict on screen goes too, so that a restart puts it back there.
+    let merge_conflicts: Vec<_> = state
+        .system
+        .merge_conflicts
+        .iter()
+        .chain(state.ui.merge_conflict.as_deref())
+        .cloned()
+        .collect();
     let json = match persistence::serialize_state(
         &state.data.workspaces,
         &state.data.sessions,
         &notepad_contents,
+        &merge_conflicts,
     ) {
         Ok(json) => json,
         Err(err) => {
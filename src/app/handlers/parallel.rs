@@ -2,9 +2,10 @@ use crate::app::{
     Action, AppState, FocusPanel, InputMode, ParallelMergePlan, ParallelWorktreeSpec, Toast,
     ToastLevel,
 };
+use crate::app::merge::{self, LandError, Landing, MergeConflict, MergeSource};
 use crate::git;
 use crate::models::{
-    AttemptStatus, ParallelTask, ParallelTaskAttempt, ParallelTaskStatus, Session,
+    AttemptStatus, MergeStrategy, ParallelTask, ParallelTaskAttempt, ParallelTaskStatus, Session,
 };
 use crate::pty::{PtyManager, SessionSpawnConfig};
 use anyhow::{anyhow, Result};
@@ -37,6 +38,9 @@ pub fn handle_parallel_action(
         Action::MergeSelectedReport => {
             merge_selected_report(state)?;
         }
+        Action::RerunVerification => {
+            rerun_selected_verification(state);
+        }
         Action::ConfirmParallelMerge => {
             confirm_parallel_merge(state, action_tx)?;
         }
@@ -153,6 +157,17 @@ fn start_parallel_task(
         }
     }
 
+    // Remember the verify command for the next task here; the new task
+    // copies it from the workspace once its worktrees exist.
+    let verify_command = state.ui.parallel_task.verify_command.trim().to_string();
+    if let Some(ws) = state
+        .data
+        .workspaces
+        .get_mut(state.ui.selected_workspace_idx)
+    {
+        ws.verify_command = (!verify_command.is_empty()).then_some(verify_command);
+    }
+
     // Reset modal state and switch to normal mode
     state.ui.input_mode = InputMode::Normal;
     state.ui.parallel_task.prompt.clear();
@@ -275,6 +290,7 @@ fn handle_parallel_worktrees_ready(
         request_report,
     );
     task.id = task_id;
+    task.verify_command = state.data.workspaces[workspace_idx].verify_command.clone();
 
     // Store the task.
     if let Some(ws) = state.data.workspaces.get_mut(workspace_idx) {
@@ -446,75 +462,85 @@ fn cancel_parallel_task(
     Ok(())
 }
 
-fn select_parallel_winner(
+pub(super) fn select_parallel_winner(
     state: &mut AppState,
     attempt_id: Uuid,
+    strategy: MergeStrategy,
     action_tx: &mpsc::UnboundedSender<Action>,
 ) -> Result<()> {
     // Find the task and attempt info — only collect the winner's session/worktree
-    let (
-        workspace_path,
-        workspace_id,
-        task_id,
-        source_branch,
-        winner_branch,
-        winner_worktree_path,
-        winner_session_id,
-    ) = {
-        let ws = state
-            .selected_workspace()
-            .ok_or_else(|| anyhow!("No workspace selected"))?;
-
-        let task = ws
-            .parallel_tasks
+    let (plan, landing) = {
+        // Any workspace's: a merge that went back to an agent to resolve
+        // conflicts lands again when it is done, wherever the cursor is.
+        let (ws, task) = state
+            .data
+            .workspaces
             .iter()
-            .find(|t| t.attempts.iter().any(|a| a.id == attempt_id))
+            .flat_map(|ws| ws.parallel_tasks.iter().map(move |task| (ws, task)))
+            .find(|(_, t)| t.attempts.iter().any(|a| a.id == attempt_id))
             .ok_or_else(|| anyhow!("Task not found"))?;
 
         let attempt = task
             .get_attempt(attempt_id)
             .ok_or_else(|| anyhow!("Attempt not found"))?;
 
-        (
-            ws.path.clone(),
-            ws.id,
-            task.id,
-            task.source_branch.clone(),
-            attempt.branch_name.clone(),
-            attempt.worktree_path.clone(),
-            attempt.session_id,
-        )
-    };
-
-    let plan = ParallelMergePlan {
-        workspace_path,
-        workspace_id,
-        task_id,
-        winner_attempt_id: attempt_id,
-        source_branch,
-        winner_branch,
-        winner_worktree_path,
-        session_ids: vec![winner_session_id],
+        let plan = ParallelMergePlan {
+            workspace_path: ws.path.clone(),
+            workspace_id: ws.id,
+            task_id: task.id,
+            winner_attempt_id: attempt_id,
+            source_branch: task.source_branch.clone(),
+            winner_branch: attempt.branch_name.clone(),
+            winner_worktree_path: attempt.worktree_path.clone(),
+            // With any agent brought in to resolve a conflict in the same
+            // checkout.
+            session_ids: std::iter::once(attempt.session_id)
+                .chain(
+                    state
+                        .data
+                        .sessions
+                        .get(&ws.id)
+                        .into_iter()
+                        .flatten()
+                        .filter(|s| s.worktree_path.as_ref() == Some(&attempt.worktree_path))
+                        .map(|s| s.id),
+                )
+                .collect(),
+            strategy,
+        };
+        // Auto-commit any uncommitted changes in the winner's worktree before merging.
+        // Agents often edit files without committing — without this, those changes
+        // would be lost when the worktree is force-removed during cleanup.
+        let landing = Landing {
+            strategy,
+            workspace_path: plan.workspace_path.clone(),
+            worktree_path: plan.winner_worktree_path.clone(),
+            branch: plan.winner_branch.clone(),
+            target: Some(plan.source_branch.clone()),
+            commit_message: "parallel task: auto-commit uncommitted changes".to_string(),
+            fallback_subject: task.prompt.clone(),
+            report: attempt.report_content.clone(),
+        };
+        (plan, landing)
     };
+    merge::remember_strategy(state, plan.workspace_id, strategy);
+    save_state(state, "failed to save merge strategy");
 
     let action_tx = action_tx.clone();
     task::spawn_blocking(move || {
-        // Auto-commit any uncommitted changes in the winner's worktree before merging.
-        // Agents often edit files without committing — without this, those changes
-        // would be lost when the worktree is force-removed during cleanup.
-        let result = if git::worktree_has_changes(&plan.winner_worktree_path) {
-            git::commit_all_changes(
-                &plan.winner_worktree_path,
-                "parallel task: auto-commit uncommitted changes",
-            )
-            .and_then(|_| git::checkout_branch(&plan.workspace_path, &plan.source_branch))
-            .and_then(|_| git::merge_branch(&plan.workspace_path, &plan.winner_branch))
-        } else {
-            git::checkout_branch(&plan.workspace_path, &plan.source_branch)
-                .and_then(|_| git::merge_branch(&plan.workspace_path, &plan.winner_branch))
+        let action = match merge::land(&landing) {
+            Err(LandError::Conflict(conflict)) => Action::MergeConflicted(Box::new(MergeConflict {
+                source: MergeSource::Attempt(plan.winner_attempt_id),
+                landing,
+                conflict,
+                resolver: None,
+            })),
+            result => Action::ParallelMergeFinished {
+                plan,
+                error: result.err().map(|e| e.to_string()),
+            },
         };
-        let error = result.err().map(|e| e.to_string());
-        if let Err(err) = action_tx.send(Action::ParallelMergeFinished { plan, error }) {
+        if let Err(err) = action_tx.send(action) {
             report_background_error("failed to report parallel merge result", err);
         }
     });
@@ -547,12 +573,14 @@ fn handle_parallel_merge_finished(
         "failed to kill merged parallel session",
     );
 
-    // Remove the merged attempt's worktree
+    // Remove the merged attempt's worktree — and its branch, unless keeping
+    // the branch was the point
     {
         let workspace_path = plan.workspace_path.clone();
         let worktree_path = plan.winner_worktree_path.clone();
+        let delete_branch = plan.strategy.lands();
         task::spawn_blocking(move || {
-            if let Err(err) = git::remove_worktree(&workspace_path, &worktree_path, true) {
+            if let Err(err) = git::remove_worktree(&workspace_path, &worktree_path, delete_branch) {
                 report_background_error("failed to remove merged parallel worktree", err);
             }
         });
@@ -570,6 +598,9 @@ fn handle_parallel_merge_finished(
         .iter_mut()
         .find(|ws| ws.id == plan.workspace_id)
     {
+        if !plan.strategy.lands() {
+            ws.keep_branch(plan.winner_branch.clone());
+        }
         if let Some(task) = ws.get_parallel_task_mut(plan.task_id) {
             task.attempts.retain(|a| a.id != plan.winner_attempt_id);
 
@@ -662,7 +693,7 @@ fn view_selected_report(state: &mut AppState) {
     let attempt = state
         .selected_workspace()
         .and_then(|ws| ws.active_parallel_task())
-        .and_then(|t| t.attempts.get(state.ui.parallel_task.selected_report_idx))
+        .and_then(|t| t.ranked_attempt(state.ui.parallel_task.selected_report_idx))
         .cloned();
 
     if let Some(attempt) = attempt {
@@ -677,18 +708,45 @@ fn merge_selected_report(state: &mut AppState) -> Result<()> {
     let attempt_id = state
         .selected_workspace()
         .and_then(|ws| ws.active_parallel_task())
-        .and_then(|t| t.attempts.get(state.ui.parallel_task.selected_report_idx))
+        .and_then(|t| t.ranked_attempt(state.ui.parallel_task.selected_report_idx))
         .map(|a| a.id);
 
     if let Some(attempt_id) = attempt_id {
         // Show confirmation modal instead of merging directly
-        state.ui.merging_parallel_attempt_id = Some(attempt_id);
-        state.ui.input_mode = InputMode::ConfirmParallelMerge;
+        open_merge_confirm(state, attempt_id);
     }
 
     Ok(())
 }
 
+/// Ask before landing `attempt_id`, with the strategy picker on the
+/// selected workspace's last choice.
+pub(crate) fn open_merge_confirm(state: &mut AppState, attempt_id: Uuid) {
+    state.ui.merge_strategy = state
+        .selected_workspace()
+        .map(|ws| ws.merge_strategy)
+        .unwrap_or_default();
+    state.ui.merging_parallel_attempt_id = Some(attempt_id);
+    state.ui.input_mode = InputMode::ConfirmParallelMerge;
+}
+
+fn rerun_selected_verification(state: &mut AppState) {
+    let attempt_id = state
+        .selected_workspace()
+        .and_then(|ws| ws.active_parallel_task())
+        .and_then(|t| t.ranked_attempt(state.ui.parallel_task.selected_report_idx))
+        .map(|a| a.id);
+    let Some(attempt_id) = attempt_id else {
+        return;
+    };
+    let status = if crate::app::verify::rerun(state, attempt_id) {
+        "Verification queued"
+    } else {
+        "Nothing to rerun — no verify command, or it is running"
+    };
+    state.ui.set_task_status(status);
+}
+
 fn confirm_parallel_merge(
     state: &mut AppState,
     action_tx: &mpsc::UnboundedSender<Action>,
@@ -697,7 +755,8 @@ fn confirm_parallel_merge(
     state.ui.input_mode = InputMode::Normal;
 
     if let Some(attempt_id) = attempt_id {
-        select_parallel_winner(state, attempt_id, action_tx)?;
+        let strategy = state.ui.merge_strategy;
+        select_parallel_winner(state, attempt_id, strategy, action_tx)?;
     }
 
     Ok(())
@@ -841,7 +900,7 @@ mod tests {
         let attempt = state
             .selected_workspace()
             .and_then(|ws| ws.active_parallel_task())
-            .and_then(|t| t.attempts.get(state.ui.parallel_task.selected_report_idx))
+            .and_then(|t| t.ranked_attempt(state.ui.parallel_task.selected_report_idx))
             .cloned();
 
         if let Some(attempt) = attempt {
@@ -871,7 +930,7 @@ mod tests {
         let attempt = state
             .selected_workspace()
             .and_then(|ws| ws.active_parallel_task())
-            .and_then(|t| t.attempts.get(state.ui.parallel_task.selected_report_idx))
+            .and_then(|t| t.ranked_attempt(state.ui.parallel_task.selected_report_idx))
             .cloned();
         if let Some(attempt) = attempt {
             state.set_active_session_id(Some(attempt.session_id));
@@ -882,7 +941,7 @@ mod tests {
         let attempt = state
             .selected_workspace()
             .and_then(|ws| ws.active_parallel_task())
-            .and_then(|t| t.attempts.get(state.ui.parallel_task.selected_report_idx))
+            .and_then(|t| t.ranked_attempt(state.ui.parallel_task.selected_report_idx))
             .cloned();
         if let Some(attempt) = attempt {
             state.set_active_session_id(Some(attempt.session_id));
@@ -892,13 +951,17 @@ mod tests {
 
     // ==================== Merge Report Tests ====================
 
+    /// The cursor counts in the Reports tab's order, not the order the
+    /// attempts were started in: an attempt whose agent failed sinks below
+    /// one that did not.
     #[test]
     fn test_merge_gets_correct_attempt_id() {
         let mut state = create_test_state();
         let ws_id = state.data.workspaces[0].id;
 
         let mut task = create_test_task(ws_id, "Test task");
-        let attempt1 = create_test_attempt(task.id, AgentType::Claude);
+        let mut attempt1 = create_test_attempt(task.id, AgentType::Claude);
+        attempt1.status = AttemptStatus::Failed;
         let attempt2 = create_test_attempt(task.id, AgentType::Gemini);
         let attempt1_id = attempt1.id;
         let attempt2_id = attempt2.id;
@@ -910,17 +973,17 @@ mod tests {
         let attempt_id = state
             .selected_workspace()
             .and_then(|ws| ws.active_parallel_task())
-            .and_then(|t| t.attempts.get(state.ui.parallel_task.selected_report_idx))
+            .and_then(|t| t.ranked_attempt(state.ui.parallel_task.selected_report_idx))
             .map(|a| a.id);
-        assert_eq!(attempt_id, Some(attempt1_id));
+        assert_eq!(attempt_id, Some(attempt2_id));
 
         state.ui.parallel_task.selected_report_idx = 1;
         let attempt_id = state
             .selected_workspace()
             .and_then(|ws| ws.active_parallel_task())
-            .and_then(|t| t.attempts.get(state.ui.parallel_task.selected_report_idx))
+            .and_then(|t| t.ranked_attempt(state.ui.parallel_task.selected_report_idx))
             .map(|a| a.id);
-        assert_eq!(attempt_id, Some(attempt2_id));
+        assert_eq!(attempt_id, Some(attempt1_id));
     }
 
     // ==================== Prompt Sent Tracking Tests ====================
@@ -1,6 +1,6 @@
 use crate::app::pty_ops::request_pty_resize;
 use crate::app::{
-    Action, AppState, FocusPanel, InputMode, PendingDelete, Toast, ToastLevel,
+    Action, AppState, FocusPanel, InputMode, PendingDelete, SpawnOptions, Toast, ToastLevel,
 };
 use crate::git;
 use crate::models::{AgentType, AttemptStatus, Session};
@@ -62,13 +62,13 @@ pub fn handle_session_action(
                 pty_tx,
             );
         }
-        Action::CreateSessionIn(workspace_id, agent_type, dangerously_skip_permissions, with_worktree) => {
+        Action::CreateSessionIn(workspace_id, session_id, agent_type, options) => {
             create_session_in(
                 state,
                 workspace_id,
+                session_id,
                 agent_type,
-                dangerously_skip_permissions,
-                with_worktree,
+                options,
                 pty_manager,
                 action_tx,
                 pty_tx,
@@ -132,7 +132,12 @@ pub fn handle_session_action(
             state.ui.pending_delete = Some(PendingDelete::Session(id, name));
         }
         Action::ConfirmDeleteSession => {
-            confirm_delete_session(state, action_tx);
+            if let Some(PendingDelete::Session(session_id, _)) = state.ui.pending_delete.take() {
+                delete_session(state, session_id, action_tx);
+            }
+        }
+        Action::DeleteSession(session_id) => {
+            delete_session(state, session_id, action_tx);
         }
         Action::CancelPendingDelete => {
             state.ui.pending_delete = None;
@@ -145,20 +150,20 @@ pub fn handle_session_action(
             has_changes,
             workspace_clean,
         } => {
-            handle_merge_checked(state, action_tx, session_id, has_changes, workspace_clean);
+            handle_merge_checked(state, session_id, has_changes, workspace_clean);
         }
         Action::SessionWorktreeMergeFinished {
             session_id,
-            committed,
+            strategy,
             outcome,
         } => {
-            handle_merge_finished(state, session_id, committed, outcome);
+            handle_merge_finished(state, session_id, strategy, outcome);
         }
         Action::SessionWorktreeCreated {
             workspace_id,
             session_id,
             agent_type,
-            dangerously_skip_permissions,
+            options,
             worktree,
             failed,
         } => {
@@ -169,7 +174,7 @@ pub fn handle_session_action(
                 workspace_id,
                 session_id,
                 agent_type,
-                dangerously_skip_permissions,
+                options,
                 worktree,
                 failed,
             );
@@ -181,6 +186,9 @@ pub fn handle_session_action(
             state.ui.merging_session_id = None;
             state.ui.input_mode = InputMode::Normal;
         }
+        Action::CycleMergeStrategy(delta) => {
+            state.ui.merge_strategy = state.ui.merge_strategy.cycle(delta);
+        }
         Action::SwitchToWorktree(session_id_opt) => {
             handle_switch_to_worktree(state, pty_manager, pty_tx, session_id_opt);
         }
@@ -410,9 +418,13 @@ fn create_session(
     create_session_in(
         state,
         workspace_id,
+        Uuid::new_v4(),
         agent_type,
-        dangerously_skip_permissions,
-        with_worktree,
+        SpawnOptions {
+            worktree: with_worktree,
+            dangerously_skip_permissions,
+            ..SpawnOptions::default()
+        },
         pty_manager,
         action_tx,
         pty_tx,
@@ -422,19 +434,30 @@ fn create_session(
 /// Create a session in a named workspace.
 ///
 /// The phone can ask for an agent in a project it is not looking at, so the
-/// target is explicit rather than "whatever the cursor is on".
+/// target is explicit rather than "whatever the cursor is on" — and so is the
+/// id, which lets a caller that cannot see app state (the control socket)
+/// know what to wait for.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn create_session_in(
     state: &mut AppState,
     workspace_id: Uuid,
+    session_id: Uuid,
     agent_type: AgentType,
-    dangerously_skip_permissions: bool,
-    with_worktree: bool,
+    options: SpawnOptions,
     pty_manager: &PtyManager,
     action_tx: &mpsc::UnboundedSender<Action>,
     pty_tx: &mpsc::Sender<Action>,
 ) -> Option<Uuid> {
     let workspace_path = state.get_workspace(workspace_id)?.path.clone();
+    let dangerously_skip_permissions = options.dangerously_skip_permissions;
+    // The checkout to start in: one we were given, or that of the agent this
+    // one takes over from, if it had its own.
+    let predecessor = options.checkout.clone().or_else(|| {
+        let short = options.successor_of.as_deref()?;
+        let sessions = state.data.sessions.get(&workspace_id)?;
+        let session = sessions.iter().find(|s| s.short_id() == short)?;
+        Some((session.worktree_path.clone()?, session.worktree_branch.clone()?))
+    });
 
     if let Some(ws) = state.get_workspace_mut(workspace_id) {
         ws.touch();
@@ -445,14 +468,28 @@ pub(crate) fn create_session_in(
     // a git repo. `git worktree add` blocks for a noticeable moment on big
     // repos, so it runs on a blocking thread; the session spawn completes when
     // SessionWorktreeCreated arrives.
-    if with_worktree && agent_type.is_agent() && git::is_git_repo(&workspace_path) {
-        let session_id = uuid::Uuid::new_v4();
+    if options.worktree && agent_type.is_agent() && git::is_git_repo(&workspace_path) {
         let short_id = session_id.to_string()[..8].to_string();
         let branch_name = git::session_branch_name(&agent_type.display_name(), &short_id);
         let worktree_path = git::get_session_worktree_path(&workspace_path, &short_id);
 
+        let start = predecessor.map(|(_, branch)| branch);
         let tx = action_tx.clone();
         tokio::task::spawn_blocking(move || {
+            // A successor's branch starts where its predecessor's is, not at
+            // the workspace's HEAD. What it had not committed stays behind.
+            // Without that branch there is no handoff: a successor started
+            // from HEAD would carry on work it cannot see. It is not started
+            // at all, and whoever asked for it gives up waiting and puts the
+            // predecessor's queue back.
+            if let Some(start) = &start {
+                if let Err(err) = git::create_branch_at(&workspace_path, &branch_name, start) {
+                    report_background_error("failed to branch from the predecessor", &err);
+                    let message = format!("Handoff stopped: could not branch from {start}");
+                    let _ = tx.send(Action::ShowToast(message, ToastLevel::Error));
+                    return;
+                }
+            }
             let worktree = match git::create_worktree(&workspace_path, &branch_name, &worktree_path)
             {
                 Ok(()) => Some((worktree_path, branch_name)),
@@ -466,7 +503,7 @@ pub(crate) fn create_session_in(
                 workspace_id,
                 session_id,
                 agent_type,
-                dangerously_skip_permissions,
+                options,
                 worktree,
                 failed,
             }) {
@@ -476,9 +513,26 @@ pub(crate) fn create_session_in(
         return Some(session_id);
     }
 
-    // Default: run in workspace directly (no worktree isolation)
-    let session = Session::new(workspace_id, agent_type.clone(), dangerously_skip_permissions);
-    let session_id = session.id;
+    // Default: run in workspace directly (no worktree isolation) — or, taking
+    // over, in the predecessor's worktree, uncommitted changes and all.
+    let (mut session, working_dir) = match predecessor {
+        Some((worktree_path, branch)) => (
+            Session::new_with_worktree(
+                workspace_id,
+                agent_type.clone(),
+                dangerously_skip_permissions,
+                worktree_path.clone(),
+                branch,
+            ),
+            worktree_path,
+        ),
+        None => (
+            Session::new(workspace_id, agent_type.clone(), dangerously_skip_permissions),
+            workspace_path,
+        ),
+    };
+    session.id = session_id;
+    seed_session(&mut session, options);
 
     let pty_rows = state.pane_rows();
     let cols = state.output_pane_cols();
@@ -490,7 +544,7 @@ pub(crate) fn create_session_in(
         session_id,
         workspace_id,
         agent_type,
-        working_dir: &workspace_path,
+        working_dir: &working_dir,
         rows: pty_rows,
         cols,
         pty_tx: pty_tx.clone(),
@@ -518,10 +572,11 @@ fn finish_worktree_session_spawn(
     workspace_id: Uuid,
     session_id: Uuid,
     agent_type: AgentType,
-    dangerously_skip_permissions: bool,
+    options: SpawnOptions,
     worktree: Option<(std::path::PathBuf, String)>,
     failed: bool,
 ) {
+    let dangerously_skip_permissions = options.dangerously_skip_permissions;
     if failed {
         show_toast(
             state,
@@ -559,6 +614,7 @@ fn finish_worktree_session_spawn(
     };
     // Keep the ID used for branch/worktree naming.
     session.id = session_id;
+    seed_session(&mut session, options);
 
     let pty_rows = state.pane_rows();
     let cols = state.output_pane_cols();
@@ -587,6 +643,23 @@ fn finish_worktree_session_spawn(
     );
 }
 
+/// What the asker wanted waiting for the agent. The prompt and the TODOs go
+/// through its queue rather than straight to the PTY: the agent takes seconds
+/// to boot, and the queue already knows how to wait until it is ready.
+fn seed_session(session: &mut Session, options: SpawnOptions) {
+    if options.alias.is_some() {
+        session.alias = options.alias;
+    }
+    if let Some(prompt) = options.prompt.filter(|prompt| !prompt.trim().is_empty()) {
+        session.todo_queue.add(prompt);
+    }
+    for todo in options.todos {
+        if !todo.trim().is_empty() {
+            session.todo_queue.add(todo);
+        }
+    }
+}
+
 fn create_terminal(
     state: &mut AppState,
     pty_manager: &PtyManager,
@@ -836,10 +909,25 @@ fn remove_worktree_in_background(
     });
 }
 
-fn confirm_delete_session(state: &mut AppState, action_tx: &mpsc::UnboundedSender<Action>) {
-    let Some(PendingDelete::Session(session_id, _)) = state.ui.pending_delete.take() else {
-        return;
-    };
+/// Whether a session other than `session_id` still works in `worktree_path`.
+fn worktree_shared(state: &AppState, session_id: Uuid, worktree_path: &std::path::Path) -> bool {
+    state
+        .data
+        .sessions
+        .values()
+        .flatten()
+        .any(|s| s.id != session_id && s.worktree_path.as_deref() == Some(worktree_path))
+}
+
+/// Delete a session, its PTY and its worktree. The session's own workspace,
+/// not the selected one: a delete from the socket can name an agent in a
+/// project nobody is looking at.
+fn delete_session(
+    state: &mut AppState,
+    session_id: Uuid,
+    action_tx: &mpsc::UnboundedSender<Action>,
+) {
+    let workspace_id = state.workspace_id_for_session(session_id);
 
     // Get session info before deleting
     let session_info: Option<(bool, Option<std::path::PathBuf>, Option<uuid::Uuid>)> = state
@@ -858,10 +946,14 @@ fn confirm_delete_session(state: &mut AppState, action_tx: &mpsc::UnboundedSende
 
     let (is_terminal, session_worktree_path, parallel_attempt_id) =
         session_info.unwrap_or((false, None, None));
+    // A handoff successor works on in its predecessor's checkout; the
+    // worktree goes with the last session using it, not the first.
+    let session_worktree_path =
+        session_worktree_path.filter(|path| !worktree_shared(state, session_id, path));
 
     // Check if this session is part of a parallel task and get cleanup info
     let parallel_cleanup_info: Option<(std::path::PathBuf, std::path::PathBuf, uuid::Uuid)> = {
-        let workspace = state.selected_workspace();
+        let workspace = workspace_id.and_then(|id| state.get_workspace(id));
         if let Some(ws) = workspace {
             if let Some(attempt_id) = parallel_attempt_id {
                 // Find the parallel task and attempt
@@ -880,7 +972,9 @@ fn confirm_delete_session(state: &mut AppState, action_tx: &mpsc::UnboundedSende
     };
 
     // Get workspace path for regular session worktree cleanup
-    let workspace_path = state.selected_workspace().map(|ws| ws.path.clone());
+    let workspace_path = workspace_id
+        .and_then(|id| state.get_workspace(id))
+        .map(|ws| ws.path.clone());
 
     // Kill PTY handle
     if let Some(handle) = state.system.pty_handles.remove(&session_id) {
@@ -889,7 +983,7 @@ fn confirm_delete_session(state: &mut AppState, action_tx: &mpsc::UnboundedSende
     // The agent's last hook report outlives the process it described, so drop
     // it with the session rather than leaving a file a future session with the
     // same short id could inherit.
-    if let Some(workspace_id) = state.workspace_id_for_session(session_id) {
+    if let Some(workspace_id) = workspace_id {
         crate::agent_status::forget(
             &workspace_id.to_string(),
             &crate::models::Session::short_id_of(session_id),
@@ -908,7 +1002,7 @@ fn confirm_delete_session(state: &mut AppState, action_tx: &mpsc::UnboundedSende
         );
 
         // Mark the attempt as failed and potentially clean up the task
-        if let Some(ws) = state.selected_workspace_mut() {
+        if let Some(ws) = workspace_id.and_then(|id| state.get_workspace_mut(id)) {
             if let Some(task) = ws.get_parallel_task_mut(task_id) {
                 // Find and mark the attempt as failed
                 if let Some(attempt) = task
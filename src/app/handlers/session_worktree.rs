@@ -1,8 +1,8 @@
 use crate::app::{Action, AppState, FocusPanel, InputMode, Toast, ToastLevel, WorktreeMergeOutcome};
+use crate::app::merge::{self, Conflict, LandError, Landing, MergeConflict, MergeSource};
 use crate::git;
-use crate::models::{AgentType, Session, SessionStatus};
+use crate::models::{AgentType, MergeStrategy, Session, SessionStatus};
 use crate::pty::{PtyManager, Resume, SessionSpawnConfig};
-use std::path::Path;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use uuid::Uuid;
@@ -26,8 +26,8 @@ fn show_toast(state: &mut AppState, msg: impl Into<String>, level: ToastLevel) {
 // The merge flow shells out to git (status/commit/merge/worktree remove),
 // which can block for seconds on large repos, so every git call runs on a
 // blocking thread. The flow is: MergeSessionWorktree kicks off a status
-// check → SessionWorktreeMergeChecked decides (confirm modal / blocked /
-// merge) → SessionWorktreeMergeFinished reports the outcome.
+// check → SessionWorktreeMergeChecked opens the confirm modal, where the
+// strategy is picked → SessionWorktreeMergeFinished reports the outcome.
 
 pub(super) fn handle_merge_session_worktree(
     state: &mut AppState,
@@ -56,44 +56,46 @@ pub(super) fn handle_merge_session_worktree(
 
 pub(super) fn handle_merge_checked(
     state: &mut AppState,
-    action_tx: &mpsc::UnboundedSender<Action>,
     session_id: Uuid,
     has_changes: bool,
     workspace_clean: bool,
 ) {
-    if has_changes {
-        state.ui.merging_session_id = Some(session_id);
-        state.ui.input_mode = InputMode::ConfirmMergeWorktree;
-        return;
-    }
-
-    if !workspace_clean {
-        show_toast(
-            state,
-            "Merge blocked: workspace has uncommitted changes",
-            ToastLevel::Warning,
-        );
+    let Some(workspace_id) = state.get_session(session_id).map(|s| s.workspace_id) else {
         return;
-    }
-
-    start_worktree_merge(state, action_tx, session_id, false);
+    };
+    // Always through the modal, even for a clean worktree on a clean
+    // workspace: it is where the strategy is chosen.
+    state.ui.merging_session_id = Some(session_id);
+    state.ui.merge_worktree_dirty = has_changes;
+    state.ui.merge_workspace_dirty = !workspace_clean;
+    state.ui.merge_strategy = merge::remembered_strategy(state, workspace_id);
+    state.ui.input_mode = InputMode::ConfirmMergeWorktree;
 }
 
 pub(super) fn handle_confirm_merge_with_commit(
     state: &mut AppState,
     action_tx: &mpsc::UnboundedSender<Action>,
 ) {
+    let strategy = state.ui.merge_strategy;
+    // The modal says why; stay on it so another strategy can be picked.
+    if strategy.lands() && state.ui.merge_workspace_dirty {
+        return;
+    }
     if let Some(session_id) = state.ui.merging_session_id.take() {
-        start_worktree_merge(state, action_tx, session_id, true);
+        if let Some(workspace_id) = state.get_session(session_id).map(|s| s.workspace_id) {
+            merge::remember_strategy(state, workspace_id, strategy);
+            save_state(state, "failed to save merge strategy");
+        }
+        start_worktree_merge(state, action_tx, session_id, strategy);
     }
     state.ui.input_mode = InputMode::Normal;
 }
 
-fn start_worktree_merge(
+pub(super) fn start_worktree_merge(
     state: &mut AppState,
     action_tx: &mpsc::UnboundedSender<Action>,
     session_id: Uuid,
-    commit_first: bool,
+    strategy: MergeStrategy,
 ) {
     let Some((workspace_path, worktree_path, branch_name, agent_name)) =
         commit_merge_info_for_session(state, session_id)
@@ -103,73 +105,101 @@ fn start_worktree_merge(
 
     show_toast(state, "Merging worktree…", ToastLevel::Info);
 
+    let landing = Landing {
+        strategy,
+        workspace_path,
+        worktree_path,
+        fallback_subject: format!("Agent {} work on {}", agent_name, branch_name),
+        branch: branch_name,
+        target: None,
+        commit_message: format!("Agent {} work - auto-committed for merge", agent_name),
+        report: None,
+    };
     let tx = action_tx.clone();
     tokio::task::spawn_blocking(move || {
-        let outcome = run_worktree_merge(
-            &workspace_path,
-            &worktree_path,
-            &branch_name,
-            &agent_name,
-            commit_first,
-        );
-        if let Err(err) = tx.send(Action::SessionWorktreeMergeFinished {
-            session_id,
-            committed: commit_first,
-            outcome,
-        }) {
+        let action = match run_worktree_merge(&landing) {
+            Ok(outcome) => Action::SessionWorktreeMergeFinished {
+                session_id,
+                strategy,
+                outcome,
+            },
+            Err(conflict) => Action::MergeConflicted(Box::new(MergeConflict {
+                source: MergeSource::Session(session_id),
+                landing,
+                conflict,
+                resolver: None,
+            })),
+        };
+        if let Err(err) = tx.send(action) {
             report_background_error("failed to report worktree merge result", err);
         }
     });
 }
 
-fn run_worktree_merge(
-    workspace_path: &Path,
-    worktree_path: &Path,
-    branch_name: &str,
-    agent_name: &str,
-    commit_first: bool,
-) -> WorktreeMergeOutcome {
+fn run_worktree_merge(landing: &Landing) -> Result<WorktreeMergeOutcome, Conflict> {
     // Re-check cleanliness here: the earlier check ran before the user sat on
     // the confirm modal (or before this task was queued), so it can be stale.
-    if !git::is_clean(workspace_path).unwrap_or(false) {
-        return WorktreeMergeOutcome::WorkspaceDirty;
+    // Keeping the branch never touches the workspace, so it doesn't care.
+    if landing.strategy.lands() && !git::is_clean(&landing.workspace_path).unwrap_or(false) {
+        return Ok(WorktreeMergeOutcome::WorkspaceDirty);
     }
 
-    if commit_first {
-        let commit_msg = format!("Agent {} work - auto-committed for merge", agent_name);
-        if git::commit_all_changes(worktree_path, &commit_msg).is_err() {
-            return WorktreeMergeOutcome::CommitFailed;
-        }
-    }
-
-    if git::merge_branch(workspace_path, branch_name).is_err() {
-        return WorktreeMergeOutcome::MergeFailed;
+    match merge::land(landing) {
+        Ok(()) => {}
+        Err(LandError::Commit(_)) => return Ok(WorktreeMergeOutcome::CommitFailed),
+        Err(LandError::Land(_)) => return Ok(WorktreeMergeOutcome::MergeFailed),
+        Err(LandError::Conflict(conflict)) => return Err(conflict),
     }
 
-    let worktree_removed = match git::remove_worktree(workspace_path, worktree_path, true) {
-        Ok(()) => true,
-        Err(err) => {
-            report_background_error("failed to remove merged worktree", err);
-            false
-        }
-    };
-    WorktreeMergeOutcome::Merged { worktree_removed }
+    let delete_branch = landing.strategy.lands();
+    let worktree_removed =
+        match git::remove_worktree(&landing.workspace_path, &landing.worktree_path, delete_branch) {
+            Ok(()) => true,
+            Err(err) => {
+                report_background_error("failed to remove merged worktree", err);
+                false
+            }
+        };
+    Ok(WorktreeMergeOutcome::Merged { worktree_removed })
 }
 
 pub(super) fn handle_merge_finished(
     state: &mut AppState,
     session_id: Uuid,
-    committed: bool,
+    strategy: MergeStrategy,
     outcome: WorktreeMergeOutcome,
 ) {
     match outcome {
         WorktreeMergeOutcome::Merged { worktree_removed } => {
-            clear_worktree_info(state, session_id);
+            let (workspace_id, branch, worktree_path) = state
+                .get_session(session_id)
+                .map(|s| (Some(s.workspace_id), s.worktree_branch.clone(), s.worktree_path.clone()))
+                .unwrap_or_default();
+            let branch = branch.unwrap_or_default();
+            if !strategy.lands() && !branch.is_empty() {
+                if let Some(ws) = workspace_id.and_then(|id| state.get_workspace_mut(id)) {
+                    ws.keep_branch(branch.clone());
+                }
+            }
+            // A fresh agent brought in to resolve a conflict worked in the
+            // same checkout, which is gone now for it too.
+            let sharing: Vec<Uuid> = state
+                .data
+                .sessions
+                .values()
+                .flatten()
+                .filter(|s| worktree_path.is_some() && s.worktree_path == worktree_path)
+                .map(|s| s.id)
+                .collect();
+            for id in sharing {
+                clear_worktree_info(state, id);
+            }
             save_state(state, "failed to save merged worktree");
-            let msg = if committed {
-                "Worktree committed and merged successfully"
-            } else {
-                "Worktree merged successfully"
+            let msg = match strategy {
+                MergeStrategy::Merge => "Worktree merged successfully".to_string(),
+                MergeStrategy::Squash => "Worktree squash-merged as one commit".to_string(),
+                MergeStrategy::Rebase => "Worktree rebased and fast-forwarded".to_string(),
+                MergeStrategy::Branch => format!("Work committed and kept on branch {}", branch),
             };
             show_toast(state, msg, ToastLevel::Success);
             if !worktree_removed {
@@ -187,11 +217,7 @@ pub(super) fn handle_merge_finished(
             show_toast(state, "Failed to commit worktree changes", ToastLevel::Error);
         }
         WorktreeMergeOutcome::MergeFailed => {
-            show_toast(
-                state,
-                "Merge failed — resolve conflicts manually",
-                ToastLevel::Error,
-            );
+            show_toast(state, "Merge failed", ToastLevel::Error);
         }
     }
 }
@@ -379,6 +405,70 @@ mod tests {
     use crate::models::{AgentType, Session, SessionStatus, Workspace};
     use std::path::PathBuf;
 
+    /// Keeping the work on its branch is a decision, not an accident: once
+    /// the worktree is gone the branch is remembered, and the sweep for
+    /// orphans leaves it alone.
+    #[test]
+    fn a_branch_kept_by_the_branch_strategy_is_not_an_orphan() {
+        use super::{handle_merge_finished, run_worktree_merge};
+        use crate::app::merge::Landing;
+        use crate::models::MergeStrategy;
+        use crate::worktree_gc::{find_orphans, Known};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = dir.path();
+        let git = |args: &[&str]| {
+            let out = std::process::Command::new("git")
+                .args(args)
+                .current_dir(repo)
+                .output()
+                .unwrap();
+            assert!(out.status.success(), "git {args:?}: {out:?}");
+        };
+        git(&["init"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::write(repo.join("a.txt"), "a\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "initial"]);
+        let worktree = crate::git::get_session_worktree_path(repo, "kept");
+        crate::git::create_worktree(repo, "agent-claude-kept", &worktree).unwrap();
+        std::fs::write(worktree.join("b.txt"), "b\n").unwrap();
+
+        let mut state = AppState::default();
+        let workspace = Workspace::new("repo".to_string(), repo.to_path_buf());
+        let workspace_id = workspace.id;
+        let session = Session::new_with_worktree(
+            workspace_id,
+            AgentType::Claude,
+            false,
+            worktree.clone(),
+            "agent-claude-kept".to_string(),
+        );
+        let session_id = session.id;
+        state.data.workspaces.push(workspace);
+        state.data.sessions.insert(workspace_id, vec![session]);
+
+        let landing = Landing {
+            strategy: MergeStrategy::Branch,
+            workspace_path: repo.to_path_buf(),
+            worktree_path: worktree.clone(),
+            branch: "agent-claude-kept".into(),
+            target: None,
+            commit_message: "auto-commit".into(),
+            fallback_subject: "work".into(),
+            report: None,
+        };
+        let outcome = run_worktree_merge(&landing).unwrap();
+        handle_merge_finished(&mut state, session_id, MergeStrategy::Branch, outcome);
+
+        assert!(!worktree.exists());
+        let known = Known::new(&state.data.workspaces, &state.data.sessions);
+        let orphans = find_orphans(repo, &known, std::time::Duration::ZERO).unwrap();
+        assert!(orphans.is_empty(), "{orphans:?}");
+        assert_eq!(state.data.workspaces[0].kept_branches, ["agent-claude-kept"]);
+    }
+
     #[test]
     fn merge_info_for_session_returns_workspace_worktree_and_branch() {
         let mut state = AppState::default();
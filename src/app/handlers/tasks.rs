@@ -8,6 +8,7 @@ use crate::app::utilities::load_utility_content;
 use crate::app::{
     tasks_view, Action, AppState, InputMode, TaskEdit, UtilityItem, UtilitySection,
 };
+use crate::models::BudgetWindow;
 use anyhow::Result;
 use tokio::sync::mpsc;
 use uuid::Uuid;
@@ -73,12 +74,21 @@ pub fn handle_task_action(
                 state.ui.set_task_status("Select an agent in Sessions");
                 return Ok(());
             };
-            // Editing needs an item under the cursor; adding does not.
+            // Rewriting needs an item under the cursor; nothing else does.
             let existing = tasks_view::selected_row(state).and_then(|row| row.todo_id());
-            if edit != TaskEdit::Add && existing.is_none() {
+            if edit == TaskEdit::Rewrite && existing.is_none() {
                 state.ui.set_task_status("Select a queued item first");
                 return Ok(());
             }
+            // A peer's question is its own to word; yours is to answer it or
+            // turn it down.
+            let consult = existing
+                .and_then(|id| tasks_view::todo_at(state, agent.session_id, id))
+                .is_some_and(|item| item.consult_ticket().is_some());
+            if edit == TaskEdit::Rewrite && consult {
+                state.ui.set_task_status("A peer's consult — delete it to refuse it");
+                return Ok(());
+            }
 
             state.ui.input_buffer = match (edit, existing) {
                 // Editing starts from the current text so a typo is a fix,
@@ -86,6 +96,11 @@ pub fn handle_task_action(
                 (TaskEdit::Rewrite, Some(id)) => tasks_view::todo_at(state, agent.session_id, id)
                     .map(|item| item.text.clone())
                     .unwrap_or_default(),
+                (TaskEdit::Budget | TaskEdit::WorkspaceBudget, _) => {
+                    budget_for(state, agent.session_id, edit)
+                        .map(|budget| budget.label())
+                        .unwrap_or_default()
+                }
                 _ => String::new(),
             };
             state.ui.task_edit = Some((agent.session_id, edit, String::new()));
@@ -100,6 +115,10 @@ pub fn handle_task_action(
             state.ui.input_buffer.clear();
 
             let text = text.trim().to_string();
+            if matches!(edit, TaskEdit::Budget | TaskEdit::WorkspaceBudget) {
+                set_budget(state, session_id, edit, &text);
+                return Ok(());
+            }
             if text.is_empty() {
                 return Ok(());
             }
@@ -120,6 +139,7 @@ pub fn handle_task_action(
                         state.ui.set_task_status("Updated");
                     }
                 }
+                TaskEdit::Budget | TaskEdit::WorkspaceBudget => {}
             }
             super::save_state(state, "failed to save the todo queue");
         }
@@ -131,10 +151,14 @@ pub fn handle_task_action(
                 state.ui.set_task_status("That row is the agent's, not yours");
                 return Ok(());
             };
-            if let Some(session) = state.get_session_mut(session_id) {
-                // Deleting the item the agent is working on only removes it
-                // from the queue; the turn it started is already out there.
-                session.todo_queue.remove(todo);
+            // Deleting the item the agent is working on only removes it
+            // from the queue; the turn it started is already out there.
+            let removed = state
+                .get_session_mut(session_id)
+                .and_then(|session| session.todo_queue.remove(todo));
+            if let Some(ticket) = removed.as_ref().and_then(|item| item.consult_ticket()) {
+                crate::app::comms_tick::decline(state, ticket);
+                state.ui.set_task_status("Consult refused");
             }
             let count = tasks_view::rows(state).len();
             state.ui.selected_task_row = state.ui.selected_task_row.min(count.saturating_sub(1));
@@ -211,6 +235,72 @@ pub fn handle_task_action(
     Ok(())
 }
 
+/// The cap a budget edit would change: the queue's own, or its workspace's.
+fn budget_for(
+    state: &AppState,
+    session_id: Uuid,
+    edit: TaskEdit,
+) -> Option<&crate::models::Budget> {
+    match edit {
+        TaskEdit::WorkspaceBudget => state
+            .workspace_id_for_session(session_id)
+            .and_then(|id| state.get_workspace(id))
+            .and_then(|ws| ws.budget.as_ref()),
+        _ => state
+            .get_session(session_id)
+            .and_then(|session| session.todo_queue.budget.as_ref()),
+    }
+}
+
+/// Apply a typed cap. Empty clears it; anything else must parse, and a typo
+/// says so rather than silently leaving the queue uncapped.
+fn set_budget(state: &mut AppState, session_id: Uuid, edit: TaskEdit, text: &str) {
+    let budget = if text.is_empty() {
+        None
+    } else {
+        match crate::models::Budget::parse(text) {
+            Ok(budget) => Some(budget),
+            Err(problem) => {
+                state.ui.set_task_status(problem);
+                return;
+            }
+        }
+    };
+    let status = match &budget {
+        Some(budget) => format!("Budget {}", budget.label()),
+        None => "Budget cleared".to_string(),
+    };
+    let slot = match edit {
+        TaskEdit::WorkspaceBudget => {
+            let Some(workspace_id) = state.workspace_id_for_session(session_id) else {
+                return;
+            };
+            state
+                .get_workspace_mut(workspace_id)
+                .map(|ws| &mut ws.budget)
+        }
+        _ => state
+            .get_session_mut(session_id)
+            .map(|session| &mut session.todo_queue.budget),
+    };
+    if let Some(slot) = slot {
+        let mut budget = budget;
+        // Raising today's cap is not a new day: keep the window already open
+        // so what was spent this morning still counts. Anything else opens
+        // on the next dispatch tick, against the meter as it reads then.
+        if let (Some(new), Some(old)) = (budget.as_mut(), slot.as_ref()) {
+            if new.window == BudgetWindow::Day && old.window == BudgetWindow::Day {
+                new.counted = old.counted;
+                new.last_meter = old.last_meter;
+                new.opened_at = old.opened_at;
+            }
+        }
+        *slot = budget;
+    }
+    state.ui.set_task_status(status);
+    super::save_state(state, "failed to save the budget");
+}
+
 /// Remember which provider conversation each session owns, so a restart can
 /// resume THAT conversation instead of the directory's most recent one (which
 /// several agents in one project would all land on).
@@ -0,0 +1,453 @@
+//! Landing an agent's worktree branch by the strategy picked in a merge
+//! confirm modal.
+//!
+//! Session worktrees and parallel attempts end the same way: commit what
+//! the agent left uncommitted, then bring the branch onto the workspace's
+//! branch — or, for `MergeStrategy::Branch`, don't. Both handlers build a
+//! [`Landing`] and run [`land`] on a blocking thread; what happens to the
+//! session and the worktree afterwards stays with them.
+//!
+//! A landing that stops on conflicts comes back as a [`MergeConflict`]
+//! instead (see `handlers::merge_conflict`). Whoever resolves it does so in
+//! the agent's own worktree: [`stage_in_worktree`] backs the workspace out
+//! and plays the same clash there, where the agent already works and a
+//! half-done merge gets in nobody else's way. Once it is resolved and
+//! committed the landing runs again and has nothing left to conflict on.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::git;
+use crate::models::MergeStrategy;
+
+/// The longest squash subject we write; git's own advice is 50, but a
+/// subject borrowed from a task prompt reads better cut a little later.
+const SUBJECT_CHARS: usize = 72;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Landing {
+    pub strategy: MergeStrategy,
+    pub workspace_path: PathBuf,
+    pub worktree_path: PathBuf,
+    pub branch: String,
+    /// Checked out in the workspace before landing. `None` lands on
+    /// whatever the workspace has checked out.
+    pub target: Option<String>,
+    /// What the agent's uncommitted work is committed as.
+    pub commit_message: String,
+    /// The squash subject when the agent never committed anything itself.
+    pub fallback_subject: String,
+    /// A parallel attempt's report, which goes into the squash body.
+    pub report: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum LandError {
+    /// The worktree's changes could not be committed; nothing moved.
+    Commit(anyhow::Error),
+    /// git refused the checkout, merge, squash, rebase or fast-forward.
+    Land(anyhow::Error),
+    /// The branch and the target changed the same lines.
+    Conflict(Conflict),
+}
+
+impl fmt::Display for LandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Commit(err) => write!(f, "commit failed: {err:#}"),
+            Self::Land(err) => write!(f, "{err:#}"),
+            Self::Conflict(conflict) => write!(f, "conflicts in {} files", conflict.files.len()),
+        }
+    }
+}
+
+/// Where a stopped landing stands.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conflict {
+    pub files: Vec<git::ConflictFile>,
+    /// The checkout git's half-done merge, squash or rebase is waiting in.
+    /// `None` when there is nothing to clean up — a rebase is backed out
+    /// the moment it stops.
+    pub in_progress: Option<PathBuf>,
+    /// The branch being landed on, by name where there is one.
+    pub target: String,
+}
+
+/// Whose branch stopped on conflicts, to land it again once resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MergeSource {
+    Session(Uuid),
+    Attempt(Uuid),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeConflict {
+    pub source: MergeSource,
+    pub landing: Landing,
+    pub conflict: Conflict,
+    /// The agent resolving it and the queue item that carried the
+    /// conflict to it; its turn ending is the cue to look again.
+    pub resolver: Option<(Uuid, Uuid)>,
+}
+
+impl MergeConflict {
+    /// Nothing is unmerged and no rebase is waiting to be continued.
+    pub fn resolved(&self) -> bool {
+        self.conflict.files.is_empty() && self.conflict.in_progress.is_none()
+    }
+
+    /// What the resolving agent is asked to do: which files clash and
+    /// how, and how to finish so that the landing can run again.
+    pub fn prompt(&self) -> String {
+        let landing = &self.landing;
+        let target = &self.conflict.target;
+        let (operation, finish) = match landing.strategy {
+            MergeStrategy::Rebase => (
+                format!("a rebase of `{}` onto `{target}`", landing.branch),
+                "then `git rebase --continue`, repeating for each commit it stops on \
+                 until the rebase completes",
+            ),
+            _ => (
+                format!("a merge of `{target}` into `{}`", landing.branch),
+                "then commit the merge with `git commit --no-edit`",
+            ),
+        };
+        let mut prompt = format!(
+            "Landing your branch `{}` on `{target}` stopped on conflicts. To resolve them where \
+             you work, {operation} is now in progress in {}, with these files conflicted:\n",
+            landing.branch,
+            landing.worktree_path.display(),
+        );
+        for file in &self.conflict.files {
+            prompt.push_str(&format!("\n### {}\n", file.path));
+            if file.hunks.is_empty() {
+                prompt.push_str("(no conflict markers — one side deleted or renamed it)\n");
+            }
+            for hunk in &file.hunks {
+                prompt.push_str(&format!("```\n{hunk}\n```\n"));
+            }
+        }
+        prompt.push_str(&format!(
+            "\nResolve each conflict keeping what both sides meant, `git add` the files, {finish}. \
+             Do not push, and do not merge into `{target}` yourself: when your turn ends workbench \
+             checks the branch again and finishes the landing."
+        ));
+        prompt
+    }
+}
+
+/// Commit the worktree, then land its branch. Blocks on git; run it off
+/// the event loop.
+pub fn land(landing: &Landing) -> Result<(), LandError> {
+    if git::worktree_has_changes(&landing.worktree_path) {
+        git::commit_all_changes(&landing.worktree_path, &landing.commit_message)
+            .map_err(LandError::Commit)?;
+    }
+    if !landing.strategy.lands() {
+        return Ok(());
+    }
+    integrate(landing)
+}
+
+/// Move a conflict into the agent's worktree: back the workspace out of
+/// the stopped merge, then merge the target into the branch there (or, for
+/// a rebase, start the rebase there and leave it stopped). What conflicts
+/// then is what the agent is asked to resolve. Blocks on git.
+pub fn stage_in_worktree(conflict: &mut MergeConflict) -> Result<()> {
+    let workspace = &conflict.landing.workspace_path;
+    let worktree = &conflict.landing.worktree_path;
+    if conflict.conflict.in_progress.as_ref() == Some(worktree) {
+        return recheck(conflict);
+    }
+    if let Some(dir) = conflict.conflict.in_progress.take() {
+        git::abort_operation(&dir)?;
+    }
+    let onto = git::get_head_commit(workspace)?;
+    // Either may stop on the conflict; that is the point. What it left
+    // behind is read back below.
+    let _ = match conflict.landing.strategy {
+        MergeStrategy::Rebase => git::rebase(worktree, &onto),
+        _ => git::merge_branch(worktree, &onto),
+    };
+    recheck(conflict)
+}
+
+/// Read the worktree's conflicts afresh — after staging them, or after
+/// the resolving agent's turn. Blocks on git.
+pub fn recheck(conflict: &mut MergeConflict) -> Result<()> {
+    let worktree = &conflict.landing.worktree_path;
+    conflict.conflict.files = git::conflicts(worktree)?;
+    // A merge whose files are all resolved is finished by `land`'s commit;
+    // a rebase has to be continued by whoever is resolving it.
+    conflict.conflict.in_progress = (!conflict.conflict.files.is_empty()
+        || git::rebase_in_progress(worktree))
+    .then(|| worktree.clone());
+    Ok(())
+}
+
+/// Back out of whatever the conflict left half done. Blocks on git.
+pub fn abort(conflict: &Conflict) -> Result<()> {
+    match &conflict.in_progress {
+        Some(dir) => git::abort_operation(dir),
+        None => Ok(()),
+    }
+}
+
+fn integrate(landing: &Landing) -> Result<(), LandError> {
+    let workspace = &landing.workspace_path;
+    if let Some(target) = &landing.target {
+        git::checkout_branch(workspace, target).map_err(LandError::Land)?;
+    }
+    let Err(err) = run_strategy(landing) else {
+        return Ok(());
+    };
+
+    let target = target_name(landing);
+    if let Some(stopped) = err.downcast_ref::<git::Conflicted>() {
+        let files = stopped
+            .files
+            .iter()
+            .map(|path| git::ConflictFile {
+                path: path.clone(),
+                hunks: Vec::new(),
+            })
+            .collect();
+        return Err(LandError::Conflict(Conflict {
+            files,
+            in_progress: None,
+            target,
+        }));
+    }
+    // Anything else that failed without leaving a merge behind — a dirty
+    // checkout, a missing branch — is a plain failure.
+    if !git::operation_in_progress(workspace) {
+        return Err(LandError::Land(err));
+    }
+    match git::conflicts(workspace) {
+        Ok(files) if !files.is_empty() => Err(LandError::Conflict(Conflict {
+            files,
+            in_progress: Some(workspace.clone()),
+            target,
+        })),
+        _ => Err(LandError::Land(err)),
+    }
+}
+
+fn target_name(landing: &Landing) -> String {
+    landing
+        .target
+        .clone()
+        .or_else(|| git::get_current_branch_fast(&landing.workspace_path))
+        .unwrap_or_else(|| "HEAD".to_string())
+}
+
+fn run_strategy(landing: &Landing) -> Result<()> {
+    let workspace = &landing.workspace_path;
+    match landing.strategy {
+        MergeStrategy::Merge => git::merge_branch(workspace, &landing.branch),
+        MergeStrategy::Squash => {
+            let onto = git::get_head_commit(workspace)?;
+            let messages = git::branch_messages(workspace, &onto, &landing.branch)?;
+            let messages: Vec<String> = messages
+                .into_iter()
+                .filter(|m| m.trim() != landing.commit_message.trim())
+                .collect();
+            let message =
+                squash_message(&messages, &landing.fallback_subject, landing.report.as_deref());
+            git::squash_merge(workspace, &landing.branch, &message)
+        }
+        MergeStrategy::Rebase => {
+            // By commit rather than name: a workspace on a detached HEAD
+            // has no branch to name, and inside the worktree `HEAD` would
+            // mean the agent's branch.
+            let onto = git::get_head_commit(workspace)?;
+            git::rebase_onto(&landing.worktree_path, &onto)?;
+            git::fast_forward(workspace, &landing.branch)
+        }
+        MergeStrategy::Branch => Ok(()),
+    }
+}
+
+/// The squash commit's message, from what the agent wrote: its first
+/// commit's subject, then each of its commits as a bullet when there was
+/// more than one, then its report. Our own auto-commit is left out by the
+/// caller — it says nothing about the change — so an agent that never
+/// committed is named by its report's first line instead, and only with
+/// no report either by `fallback`, the prompt it was given.
+pub fn squash_message(messages: &[String], fallback: &str, report: Option<&str>) -> String {
+    let first_line = |text: &str| text.lines().next().unwrap_or("").trim().to_string();
+    // A report usually opens on a Markdown heading; the words are the
+    // subject, the hashes are not.
+    let report_line = || {
+        let line = report?.lines().map(str::trim).find(|line| !line.is_empty())?;
+        Some(line.trim_start_matches('#').trim().to_string()).filter(|line| !line.is_empty())
+    };
+    let subject = messages
+        .first()
+        .map(|m| first_line(m))
+        .filter(|s| !s.is_empty())
+        .or_else(report_line)
+        .unwrap_or_else(|| first_line(fallback));
+    let mut subject: String = subject.chars().take(SUBJECT_CHARS).collect();
+    if subject.is_empty() {
+        subject = "Squashed agent work".to_string();
+    }
+
+    let mut body = Vec::new();
+    match messages {
+        [] => {}
+        [only] => {
+            let rest = only.split_once('\n').map(|(_, rest)| rest.trim()).unwrap_or("");
+            if !rest.is_empty() {
+                body.push(rest.to_string());
+            }
+        }
+        many => body.push(
+            many.iter()
+                .map(|m| format!("* {}", first_line(m)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+    }
+    if let Some(report) = report.map(str::trim).filter(|r| !r.is_empty()) {
+        body.push(report.to_string());
+    }
+
+    if body.is_empty() {
+        subject
+    } else {
+        format!("{subject}\n\n{}", body.join("\n\n"))
+    }
+}
+
+/// The strategy a merge confirm modal for `workspace_id` opens on.
+pub fn remembered_strategy(state: &AppState, workspace_id: Uuid) -> MergeStrategy {
+    state
+        .data
+        .workspaces
+        .iter()
+        .find(|w| w.id == workspace_id)
+        .map(|w| w.merge_strategy)
+        .unwrap_or_default()
+}
+
+/// Keep the strategy a merge was confirmed with for the workspace's next
+/// one. The caller saves.
+pub fn remember_strategy(state: &mut AppState, workspace_id: Uuid, strategy: MergeStrategy) {
+    if let Some(ws) = state.data.workspaces.iter_mut().find(|w| w.id == workspace_id) {
+        ws.merge_strategy = strategy;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A single commit is the message as the agent wrote it; several become
+    /// a summary led by the first; none falls back to the task; a report is
+    /// appended whichever way.
+    #[test]
+    fn squash_message_is_built_from_the_agents_own_words() {
+        let one = vec!["Fix parser\n\nIt dropped the last token.".to_string()];
+        assert_eq!(
+            squash_message(&one, "unused", None),
+            "Fix parser\n\nIt dropped the last token."
+        );
+
+        let many = vec!["Fix parser".to_string(), "Add test\n\ndetails".to_string()];
+        assert_eq!(
+            squash_message(&many, "unused", Some("## Report\nAll good.\n")),
+            "Fix parser\n\n* Fix parser\n* Add test\n\n## Report\nAll good."
+        );
+
+        let long_prompt = format!("{}\nsecond line", "x".repeat(100));
+        let message = squash_message(&[], &long_prompt, None);
+        assert_eq!(message, "x".repeat(SUBJECT_CHARS));
+    }
+
+    /// An agent that left committing to us has only our auto-commit on its
+    /// branch, which the caller drops; its report names the change before
+    /// the prompt does.
+    #[test]
+    fn squash_without_agent_commits_takes_its_subject_from_the_report() {
+        let report = "\n## Fix the parser's last token\n\nIt was dropped at EOF.\n";
+        assert_eq!(
+            squash_message(&[], "please fix the parser", Some(report)),
+            "Fix the parser's last token\n\n## Fix the parser's last token\n\n\
+             It was dropped at EOF."
+        );
+        assert_eq!(
+            squash_message(&[], "please fix the parser", Some("  \n")),
+            "please fix the parser"
+        );
+    }
+
+    /// The whole round trip: the landing stops on a clash, the clash is
+    /// moved out of the workspace into the agent's worktree, and once it
+    /// is resolved there the same landing goes through.
+    #[test]
+    fn a_conflict_staged_in_the_worktree_lands_once_resolved() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let workspace = dir.path().join("repo");
+        let worktree = dir.path().join("wt");
+        std::fs::create_dir(&workspace).unwrap();
+        let git = |dir: &std::path::Path, args: &[&str]| {
+            let out = std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .unwrap();
+            assert!(out.status.success(), "git {args:?}: {:?}", out);
+        };
+        git(&workspace, &["init"]);
+        git(&workspace, &["config", "user.email", "test@test.com"]);
+        git(&workspace, &["config", "user.name", "Test"]);
+        std::fs::write(workspace.join("a.txt"), "base\n").unwrap();
+        git(&workspace, &["add", "."]);
+        git(&workspace, &["commit", "-m", "initial"]);
+        git(&workspace, &["worktree", "add", "-b", "agent", worktree.to_str().unwrap()]);
+        for (dir, text) in [(&worktree, "agent\n"), (&workspace, "main\n")] {
+            std::fs::write(dir.join("a.txt"), text).unwrap();
+            git(dir, &["commit", "-am", text.trim()]);
+        }
+
+        let landing = Landing {
+            strategy: MergeStrategy::Merge,
+            workspace_path: workspace.clone(),
+            worktree_path: worktree.clone(),
+            branch: "agent".into(),
+            target: None,
+            commit_message: "wip".into(),
+            fallback_subject: "task".into(),
+            report: None,
+        };
+        let Err(LandError::Conflict(conflict)) = land(&landing) else {
+            panic!("expected the landing to stop on a conflict");
+        };
+        assert_eq!(conflict.in_progress.as_ref(), Some(&workspace));
+        assert_eq!(conflict.files[0].path, "a.txt");
+        assert!(conflict.files[0].hunks[0].contains("main"));
+
+        let mut conflict = MergeConflict {
+            source: MergeSource::Session(Uuid::new_v4()),
+            landing: landing.clone(),
+            conflict,
+            resolver: None,
+        };
+        stage_in_worktree(&mut conflict).unwrap();
+        assert_eq!(conflict.conflict.in_progress.as_ref(), Some(&worktree));
+        assert!(!git::operation_in_progress(&workspace));
+        assert!(conflict.prompt().contains("### a.txt"));
+
+        std::fs::write(worktree.join("a.txt"), "both\n").unwrap();
+        git(&worktree, &["add", "a.txt"]);
+        recheck(&mut conflict).unwrap();
+        assert!(conflict.resolved());
+        land(&landing).unwrap();
+        assert_eq!(std::fs::read_to_string(workspace.join("a.txt")).unwrap(), "both\n");
+    }
+}
@@ -3,6 +3,7 @@ pub mod agent_input;
 pub mod comms_tick;
 mod handler;
 pub mod handlers;
+pub mod merge;
 mod pty_ops;
 mod runtime;
 mod selection;
@@ -11,6 +12,7 @@ mod state;
 pub mod tasks_view;
 pub mod todo_dispatch;
 mod utilities;
+pub mod verify;
 mod workspace_nav;
 
 // Terminal buffer configuration
@@ -21,13 +23,14 @@ pub const PARSER_BUFFER_ROWS: u16 = 80;
 // are now configurable via UserConfig (loaded from ~/.config/workbench/user_config.toml).
 
 pub use action::{
-    Action, ParallelMergePlan, ParallelWorktreeSpec, UtilityContentPayload, WorktreeMergeOutcome,
+    Action, ParallelMergePlan, ParallelWorktreeSpec, SpawnOptions, UtilityContentPayload,
+    WorktreeMergeOutcome,
 };
 pub use runtime::run_tui;
 pub use tasks_view::TaskRow;
 pub use state::{
-    AppState, ConfigTab, Divider, FocusPanel, InputMode, PendingDelete,
-    PendingSessionStart, RawOutputBuffer, ReplayCache, SystemState, TextSelection,
+    AppState, ConfigTab, DiffNav, DiffSubject, DiffView, Divider, FocusPanel, InputMode,
+    PendingDelete, PendingSessionStart, RawOutputBuffer, ReplayCache, SystemState, TextSelection,
     TaskEdit, ThreadCache, TasksTab, Toast, ToastLevel, TranscriptBuffer, TranscriptLine, TranscriptSpan,
     UtilityItem, UtilitySection, WorkspaceAction,
 };
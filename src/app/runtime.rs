@@ -73,6 +73,7 @@ pub async fn run_tui(initial_workspace: Option<PathBuf>, use_alternate_screen: b
             for (ws_id, content) in persisted.notepad_content {
                 state.load_notepad_content(ws_id, content);
             }
+            crate::app::handlers::merge_conflict::restore(&mut state, persisted.merge_conflicts);
             // Select the first workspace in list order.
             let visual_order = state.workspace_visual_order();
             if let Some(&first_idx) = visual_order.first() {
@@ -12,6 +12,10 @@ pub struct DataState {
     /// Tracks when a session started its current work burst (first output after idle)
     /// Tracks when user last sent input to each session (to distinguish echo from agent output)
     pub last_send_input: HashMap<Uuid, Instant>,
+    /// When each session last had the keyboard — its pane focused, so that
+    /// what you type goes to it. Scripted keys are held off a session you
+    /// were just at (see `AppState::user_is_at`).
+    pub last_user_focus: HashMap<Uuid, Instant>,
     /// Idle session queue (sessions waiting for attention, across all workspaces)
     pub idle_queue: Vec<Uuid>,
     /// Notepad state (per workspace) - TextArea handles cursor, scrolling, undo/redo
@@ -25,6 +29,7 @@ impl DataState {
             sessions: HashMap::new(),
             last_activity: HashMap::new(),
             last_send_input: HashMap::new(),
+            last_user_focus: HashMap::new(),
             idle_queue: Vec::new(),
             notepads: HashMap::new(),
         }
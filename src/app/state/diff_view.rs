@@ -0,0 +1,221 @@
+//! State for the diff viewer (`InputMode::DiffViewer`).
+//!
+//! The viewer shows one file at a time, so all positions are per file: the
+//! selected file, and how many rows of it have scrolled off the top. Rows
+//! are laid out by `git::FileDiff::rows` — every hunk is its header followed
+//! by its lines — and the renderer draws exactly that, so scrolling and hunk
+//! jumps here always land where the user sees them.
+
+use crate::git::FileDiff;
+use uuid::Uuid;
+
+/// Whose branch is under review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffSubject {
+    /// A session running in its own worktree, against the workspace branch.
+    Session(Uuid),
+    /// One attempt of a parallel task, against the task's source commit.
+    Attempt(Uuid),
+}
+
+/// A move inside the viewer. Counts are signed: negative goes up/back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffNav {
+    /// By rows.
+    Scroll(isize),
+    /// By screenfuls of the diff area.
+    Page(isize),
+    /// To the next/previous hunk, spilling into the neighbouring file at
+    /// either end so `n` walks the whole change.
+    Hunk(isize),
+    File(isize),
+    Top,
+    Bottom,
+}
+
+#[derive(Debug)]
+pub struct DiffView {
+    pub subject: DiffSubject,
+    /// "<branch> vs <base>", for the frame title.
+    pub title: String,
+    /// Matches the `DiffLoaded` answer to the request that asked for it, so
+    /// a slow diff for a viewer that was closed and reopened on something
+    /// else is dropped.
+    pub request_id: u64,
+    /// `None` while git runs.
+    pub files: Option<Result<Vec<FileDiff>, String>>,
+    pub selected_file: usize,
+    pub scroll: usize,
+    /// Rows the diff area had at the last draw. Paging moves by this much,
+    /// and scrolling stops once the last row is at the bottom of the screen.
+    pub page: usize,
+}
+
+impl DiffView {
+    pub fn new(subject: DiffSubject, title: String, request_id: u64) -> Self {
+        Self {
+            subject,
+            title,
+            request_id,
+            files: None,
+            selected_file: 0,
+            scroll: 0,
+            page: 0,
+        }
+    }
+
+    pub fn files(&self) -> &[FileDiff] {
+        match &self.files {
+            Some(Ok(files)) => files,
+            _ => &[],
+        }
+    }
+
+    pub fn current_file(&self) -> Option<&FileDiff> {
+        self.files().get(self.selected_file)
+    }
+
+    fn max_scroll(&self) -> usize {
+        let rows = self.current_file().map(FileDiff::rows).unwrap_or(0);
+        rows.saturating_sub(self.page.max(1))
+    }
+
+    pub fn navigate(&mut self, nav: DiffNav) {
+        match nav {
+            DiffNav::Scroll(delta) => self.scroll_by(delta),
+            DiffNav::Page(pages) => self.scroll_by(pages * self.page.max(1) as isize),
+            DiffNav::Top => self.scroll = 0,
+            DiffNav::Bottom => self.scroll = self.max_scroll(),
+            DiffNav::File(delta) => {
+                self.select_file(delta);
+            }
+            DiffNav::Hunk(delta) if delta >= 0 => self.next_hunk(),
+            DiffNav::Hunk(_) => self.prev_hunk(),
+        }
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        let target = self.scroll.saturating_add_signed(delta);
+        self.scroll = target.min(self.max_scroll());
+    }
+
+    /// Step to a neighbouring file, scrolled to its top. Returns false at
+    /// either end of the list.
+    fn select_file(&mut self, delta: isize) -> bool {
+        let count = self.files().len();
+        let target = self.selected_file.saturating_add_signed(delta);
+        if count == 0 || target >= count || target == self.selected_file {
+            return false;
+        }
+        self.selected_file = target;
+        self.scroll = 0;
+        true
+    }
+
+    fn hunk_rows(&self) -> Vec<usize> {
+        self.current_file().map(FileDiff::hunk_rows).unwrap_or_default()
+    }
+
+    fn next_hunk(&mut self) {
+        let max = self.max_scroll();
+        // A hunk too close to the end to reach the top of the screen is
+        // already in view once we are scrolled to the bottom; stepping
+        // "to" it would go nowhere, so that counts as the last hunk.
+        let next = self
+            .hunk_rows()
+            .into_iter()
+            .map(|row| row.min(max))
+            .find(|&row| row > self.scroll);
+        match next {
+            Some(row) => self.scroll = row,
+            None => {
+                self.select_file(1);
+            }
+        }
+    }
+
+    fn prev_hunk(&mut self) {
+        let prev = self.hunk_rows().into_iter().rfind(|&row| row < self.scroll);
+        match prev {
+            Some(row) => self.scroll = row,
+            None => {
+                if self.select_file(-1) {
+                    let last = self.hunk_rows().last().copied().unwrap_or(0);
+                    self.scroll = last.min(self.max_scroll());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+
+    fn view(patch: &str, page: usize) -> DiffView {
+        let mut view = DiffView::new(DiffSubject::Session(Uuid::new_v4()), String::new(), 1);
+        view.files = Some(Ok(parse_diff(patch)));
+        view.page = page;
+        view
+    }
+
+    fn hunk(start: usize, lines: usize) -> String {
+        let mut out = format!("@@ -{start},{lines} +{start},{lines} @@\n");
+        for i in 0..lines {
+            out.push_str(&format!(" line {i}\n"));
+        }
+        out
+    }
+
+    /// `n` visits every hunk in order and then carries on into the next
+    /// file; `N` walks back the same way, landing on the previous file's
+    /// last hunk rather than its top.
+    #[test]
+    fn hunk_navigation_walks_hunks_then_crosses_into_neighbouring_files() {
+        let patch = format!(
+            "diff --git a/a b/a\n{}{}diff --git a/b b/b\n{}",
+            hunk(1, 3),
+            hunk(20, 3),
+            hunk(5, 2),
+        );
+        let mut view = view(&patch, 2);
+
+        view.navigate(DiffNav::Hunk(1));
+        assert_eq!((view.selected_file, view.scroll), (0, 4));
+        view.navigate(DiffNav::Hunk(1));
+        assert_eq!((view.selected_file, view.scroll), (1, 0));
+        view.navigate(DiffNav::Hunk(1));
+        assert_eq!((view.selected_file, view.scroll), (1, 0), "stops at the end");
+
+        view.navigate(DiffNav::Hunk(-1));
+        assert_eq!((view.selected_file, view.scroll), (0, 4));
+        view.navigate(DiffNav::Hunk(-1));
+        assert_eq!((view.selected_file, view.scroll), (0, 0));
+    }
+
+    /// Scrolling stops with the last row on the bottom line, and a hunk
+    /// that can never reach the top of the screen doesn't trap `n`.
+    #[test]
+    fn scrolling_stops_at_the_last_page_and_short_tails_do_not_trap_hunk_jumps() {
+        let patch = format!(
+            "diff --git a/a b/a\n{}{}diff --git a/b b/b\n{}",
+            hunk(1, 10),
+            hunk(40, 1),
+            hunk(1, 1),
+        );
+        // 13 rows, 5 visible: the furthest we can scroll is 8.
+        let mut view = view(&patch, 5);
+
+        view.navigate(DiffNav::Page(10));
+        assert_eq!(view.scroll, 8);
+        view.navigate(DiffNav::Top);
+        view.navigate(DiffNav::Scroll(-3));
+        assert_eq!(view.scroll, 0);
+
+        view.navigate(DiffNav::Hunk(1));
+        assert_eq!(view.scroll, 8, "the second hunk is as high as it goes");
+        view.navigate(DiffNav::Hunk(1));
+        assert_eq!((view.selected_file, view.scroll), (1, 0));
+    }
+}
@@ -1,10 +1,12 @@
 mod data;
+mod diff_view;
 mod file_browser;
 mod system;
 mod types;
 mod ui;
 
 pub use data::DataState;
+pub use diff_view::{DiffNav, DiffSubject, DiffView};
 pub use system::{
     PendingSessionStart, RawOutputBuffer, ReplayCache, SystemState, ThreadCache,
     TranscriptBuffer, TranscriptLine, TranscriptSpan,
@@ -562,6 +564,29 @@ impl AppState {
         // Remove activity tracking
         self.data.last_activity.remove(&session_id);
         self.data.last_send_input.remove(&session_id);
+        self.data.last_user_focus.remove(&session_id);
+    }
+
+    /// The session your typing goes to: the output pane's, or the focused
+    /// pinned pane's. `None` when the keyboard is on a list.
+    pub fn keyboard_session(&self) -> Option<Uuid> {
+        match self.ui.focus {
+            FocusPanel::OutputPane => self.active_session_id(),
+            FocusPanel::PinnedTerminalPane(index) => self.pinned_terminal_id_at(index),
+            _ => None,
+        }
+    }
+
+    /// You have the session's pane focused, or had it within `USER_GRACE`.
+    /// Keys from a script would land in the middle of whatever you were
+    /// typing, so they wait until you have been away a while.
+    pub fn user_is_at(&self, session_id: Uuid) -> bool {
+        self.keyboard_session() == Some(session_id)
+            || self
+                .data
+                .last_user_focus
+                .get(&session_id)
+                .is_some_and(|at| at.elapsed() < crate::app::todo_dispatch::USER_GRACE)
     }
 
     /// Check if a session is actively working (received output within last 2 seconds)
@@ -625,6 +650,37 @@ impl AppState {
         })
     }
 
+    /// What a session has spent, priced with the user's table. `None` until
+    /// its journal records any tokens — and always for agents whose stores do
+    /// not, so a Gemini row shows nothing rather than a confident `$0.00`.
+    pub fn session_cost(&self, session_id: Uuid) -> Option<crate::models::SessionCost> {
+        let tracker = self.system.agent_tasks.get(&session_id)?;
+        let cost =
+            crate::models::SessionCost::priced(tracker.spend(), &self.system.user_config.pricing);
+        (!cost.is_empty()).then_some(cost)
+    }
+
+    /// Everything spent by the sessions in one workspace.
+    pub fn workspace_cost(&self, workspace_id: Uuid) -> crate::models::SessionCost {
+        let mut total = crate::models::SessionCost::default();
+        for session in self.data.sessions.get(&workspace_id).into_iter().flatten() {
+            if let Some(cost) = self.session_cost(session.id) {
+                total += cost;
+            }
+        }
+        total
+    }
+
+    /// Everything spent by every session workbench knows about. A deleted
+    /// session takes its spend with it: this is what is on screen, not a bill.
+    pub fn total_cost(&self) -> crate::models::SessionCost {
+        let mut total = crate::models::SessionCost::default();
+        for workspace in &self.data.workspaces {
+            total += self.workspace_cost(workspace.id);
+        }
+        total
+    }
+
     /// What a session is doing, preferring what the agent reported over what
     /// its output looks like.
     ///
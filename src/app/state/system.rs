@@ -746,6 +746,14 @@ pub struct SystemState {
     pub forwarded: std::collections::HashSet<u16>,
     pub last_port_scan: Option<Instant>,
     pub port_scan_inflight: bool,
+    /// Attempts whose verify command is running right now. An attempt
+    /// whose persisted verification never finished but isn't in here was
+    /// cut off by a restart, and is run again (see `app::verify`).
+    pub verifying: std::collections::HashSet<Uuid>,
+    /// Conflicts handed to an agent, waiting for its turn to end (see
+    /// `handlers::merge_conflict`). Saved with the state, and waited on
+    /// again after a restart.
+    pub merge_conflicts: Vec<crate::app::merge::MergeConflict>,
     /// What each agent was doing last tick, so the phone is poked on a change
     /// rather than every tick a state persists.
     pub remote_seen: HashMap<String, String>,
@@ -831,6 +839,8 @@ impl SystemState {
             forwarded: Default::default(),
             last_port_scan: None,
             port_scan_inflight: false,
+            verifying: Default::default(),
+            merge_conflicts: Vec::new(),
             remote_seen: Default::default(),
             remote_working_since: Default::default(),
             remote_finished: Default::default(),
@@ -21,8 +21,10 @@ pub enum InputMode {
     CreateParallelTask,   // Modal for starting a parallel task
     ConfirmMergeWorktree, // Confirm commit and merge worktree
     ConfirmParallelMerge, // Confirm commit and merge parallel task worktree
+    MergeConflict,        // A landing stopped on conflicts: abort, or hand to an agent
     ConfigWindow,         // F1 configuration window
     CommandPalette,       // Ctrl+P command palette
+    DiffViewer,           // Full-screen review of an agent's branch
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -104,6 +106,10 @@ pub enum TaskEdit {
     Add,
     /// Replace the selected item's text.
     Rewrite,
+    /// Set or clear this queue's spending cap.
+    Budget,
+    /// Set or clear the cap shared by every queue in the workspace.
+    WorkspaceBudget,
 }
 
 /// Tab selection for the tasks pane
@@ -134,6 +140,7 @@ pub enum UtilityItem {
     PromptLog,
     Calendar,
     GitHistory,
+    Worktrees,
     Keybindings,
     PhoneQr,
     ToggleBanner,
@@ -153,6 +160,7 @@ impl UtilityItem {
             UtilityItem::PromptLog,
             UtilityItem::Calendar,
             UtilityItem::GitHistory,
+            UtilityItem::Worktrees,
             UtilityItem::Keybindings,
             UtilityItem::PhoneQr,
             UtilityItem::ToggleBanner,
@@ -181,6 +189,7 @@ impl UtilityItem {
             UtilityItem::PromptLog => "Prompt Log",
             UtilityItem::Calendar => "Calendar",
             UtilityItem::GitHistory => "Git History",
+            UtilityItem::Worktrees => "Orphaned Worktrees",
             UtilityItem::Keybindings => "Keybindings",
             UtilityItem::PhoneQr => "Phone QR",
             UtilityItem::ToggleBanner => "Banner Bar",
@@ -198,6 +207,7 @@ impl UtilityItem {
             UtilityItem::PromptLog => "\u{270E}",
             UtilityItem::Calendar => "\u{1F4C5}",
             UtilityItem::GitHistory => "\u{1F4DC}",
+            UtilityItem::Worktrees => "\u{1F333}",
             UtilityItem::Keybindings => "\u{2328}",
             UtilityItem::PhoneQr => "\u{25A6}",
             UtilityItem::ToggleBanner => "\u{1F4E2}",
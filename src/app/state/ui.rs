@@ -1,4 +1,4 @@
-use crate::models::{AgentType, Workspace, MAX_PINNED_TERMINALS};
+use crate::models::{AgentType, MergeStrategy, Workspace, MAX_PINNED_TERMINALS};
 use std::path::PathBuf;
 use uuid::Uuid;
 
@@ -110,6 +110,16 @@ pub struct ParallelTaskModalState {
     pub dangerous_mode: bool,           // Whether to skip permission prompts
     pub selected_report_idx: usize,     // Selected report in Reports tab
     pub request_id: u64,
+    /// Run in each attempt's worktree when its agent finishes; empty for
+    /// none. The last item Tab reaches, after the checkboxes.
+    pub verify_command: String,
+}
+
+impl ParallelTaskModalState {
+    /// Whether typing goes to the verify command rather than the prompt.
+    pub fn editing_verify(&self) -> bool {
+        self.agent_idx == self.agents.len() + 2
+    }
 }
 
 impl Default for ParallelTaskModalState {
@@ -127,6 +137,7 @@ impl Default for ParallelTaskModalState {
             dangerous_mode: true, // Default to dangerous mode for parallel tasks
             selected_report_idx: 0,
             request_id: 0,
+            verify_command: String::new(),
         }
     }
 }
@@ -218,6 +229,16 @@ pub struct UIState {
     pub editing_session_id: Option<Uuid>,
     pub merging_session_id: Option<Uuid>, // Session being merged (for ConfirmMergeWorktree modal)
     pub merging_parallel_attempt_id: Option<Uuid>, // Parallel attempt being merged
+    /// The strategy picked in whichever merge confirm modal is open; set
+    /// from the workspace's last choice when one opens.
+    pub merge_strategy: MergeStrategy,
+    /// What the check before `ConfirmMergeWorktree` found: uncommitted work
+    /// in the agent's worktree, and in the workspace it would land on.
+    pub merge_worktree_dirty: bool,
+    pub merge_workspace_dirty: bool,
+    /// The stopped landing `InputMode::MergeConflict` is asking about.
+    /// Saved with the state, so a restart asks again.
+    pub merge_conflict: Option<Box<crate::app::merge::MergeConflict>>,
 
     // Tasks pane: selection is an index into the flattened row list built by
     // `app::tasks_view::rows` (agent headers, prompts and tasks interleaved).
@@ -250,6 +271,10 @@ pub struct UIState {
     // Command palette
     pub palette: CommandPaletteState,
 
+    // Diff viewer: open while `input_mode` is `DiffViewer`
+    pub diff_view: Option<super::DiffView>,
+    pub diff_request_id: u64,
+
     // Toast notifications
     pub toasts: VecDeque<Toast>,
 }
@@ -288,6 +313,10 @@ impl UIState {
             editing_session_id: None,
             merging_session_id: None,
             merging_parallel_attempt_id: None,
+            merge_strategy: MergeStrategy::default(),
+            merge_worktree_dirty: false,
+            merge_workspace_dirty: false,
+            merge_conflict: None,
             selected_task_row: 0,
             selected_tasks_tab: TasksTab::default(),
             tasks_agent: None,
@@ -299,6 +328,8 @@ impl UIState {
             show_debug_overlay: false,
             config: ConfigWindowState::default(),
             palette: CommandPaletteState::default(),
+            diff_view: None,
+            diff_request_id: 0,
             toasts: VecDeque::new(),
         }
     }
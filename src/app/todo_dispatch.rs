@@ -14,6 +14,16 @@
 //! 2. You are talking to it. If you typed into that session recently, the
 //!    queue waits rather than interleaving with your conversation.
 //! 3. Something of ours is already in flight. One item at a time is the point.
+//!
+//! A fourth is yours to set: a spending cap on the queue or its workspace
+//! (see `models::budget`). Queues run overnight, and a cap is the only thing
+//! standing between one looping agent and a day's allowance.
+//!
+//! Peers' consults ride the same queue (see `TodoKind::Consult`), so the
+//! three rules hold for them too: a question from another agent used to go
+//! in at the first idle, seconds after you had typed into that pane or on
+//! top of an item about to go. A pause or a cap holds only your own work —
+//! the peer is blocked until its question is answered.
 
 use chrono::Utc;
 use std::time::Duration;
@@ -21,8 +31,8 @@ use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::agent_status::Activity;
-use crate::app::{Action, AppState};
-use crate::models::SessionStatus;
+use crate::app::{comms_tick, Action, AppState};
+use crate::models::{QueuedTodo, SessionStatus};
 
 /// How long the queue keeps quiet after you type into a session.
 ///
@@ -41,6 +51,8 @@ pub enum Holding {
     Running,
     /// You paused it.
     Paused,
+    /// The queue, or its workspace, has spent its cap.
+    OverBudget,
     /// The agent is stopped and waiting on you.
     AgentBlocked,
     /// The agent is mid-turn on something else.
@@ -57,6 +69,7 @@ impl Holding {
             Holding::Empty => "",
             Holding::Running => "running",
             Holding::Paused => "paused",
+            Holding::OverBudget => "held — over budget",
             Holding::AgentBlocked => "waiting — agent needs you",
             Holding::AgentBusy => "waiting — agent is busy",
             Holding::YouWereTyping => "waiting — you are typing",
@@ -78,8 +91,12 @@ pub fn holding(state: &AppState, session_id: Uuid) -> Holding {
     if queue.next_pending().is_none() {
         return Holding::Empty;
     }
-    if queue.paused {
-        return Holding::Paused;
+    if next_item(state, session_id).is_none() {
+        return if over_budget(state, session_id) {
+            Holding::OverBudget
+        } else {
+            Holding::Paused
+        };
     }
     if session.status != SessionStatus::Running {
         return Holding::NotRunning;
@@ -96,6 +113,85 @@ pub fn holding(state: &AppState, session_id: Uuid) -> Holding {
     Holding::Empty
 }
 
+/// The item that would go next: the head of the queue, or — while a pause
+/// holds your work — the first peer consult. A cap holds consults too:
+/// answering one is a turn like any other, and spends the same money.
+fn next_item(state: &AppState, session_id: Uuid) -> Option<&QueuedTodo> {
+    let queue = &state.get_session(session_id)?.todo_queue;
+    if over_budget(state, session_id) {
+        None
+    } else if queue.paused {
+        queue.next_consult()
+    } else {
+        queue.next_pending()
+    }
+}
+
+/// Whether the queue's own cap or its workspace's has been reached.
+pub fn over_budget(state: &AppState, session_id: Uuid) -> bool {
+    let Some(session) = state.get_session(session_id) else {
+        return false;
+    };
+    let queue_over = session.todo_queue.budget.as_ref().is_some_and(|budget| {
+        budget.exceeded(state.session_cost(session_id).unwrap_or_default())
+    });
+    let workspace_over = state
+        .workspace_id_for_session(session_id)
+        .and_then(|id| state.get_workspace(id).map(|ws| (id, ws)))
+        .and_then(|(id, ws)| ws.budget.as_ref().map(|budget| (id, budget)))
+        .is_some_and(|(id, budget)| budget.exceeded(state.workspace_cost(id)));
+    queue_over || workspace_over
+}
+
+/// Open each cap's window, or the next one when the day has turned over, and
+/// count what was spent since the last tick.
+///
+/// Not until the meter has been read: just after a restart every session's
+/// spend reads zero until its journal is parsed, and a window opened on that
+/// zero would charge the whole resumed conversation to today.
+fn roll_budgets(state: &mut AppState, session_id: Uuid) {
+    let Some(workspace_id) = state.workspace_id_for_session(session_id) else {
+        return;
+    };
+    if !meter_read(state, workspace_id) {
+        return;
+    }
+    let now = Utc::now();
+    let mut changed = false;
+
+    let queue_meter = state.session_cost(session_id).unwrap_or_default();
+    if let Some(budget) = state
+        .get_session_mut(session_id)
+        .and_then(|session| session.todo_queue.budget.as_mut())
+    {
+        changed |= budget.roll(queue_meter, now);
+    }
+    let workspace_meter = state.workspace_cost(workspace_id);
+    if let Some(budget) = state
+        .get_workspace_mut(workspace_id)
+        .and_then(|ws| ws.budget.as_mut())
+    {
+        changed |= budget.roll(workspace_meter, now);
+    }
+    if changed {
+        crate::app::handlers::save_state(state, "failed to save a budget window");
+    }
+}
+
+/// Every running agent in the workspace whose journal we can read has been
+/// read at least once.
+fn meter_read(state: &AppState, workspace_id: Uuid) -> bool {
+    state
+        .data
+        .sessions
+        .get(&workspace_id)
+        .into_iter()
+        .flatten()
+        .filter(|session| session.status == SessionStatus::Running)
+        .filter(|session| crate::agent_tasks::Provider::for_agent(&session.agent_type).is_some())
+        .all(|session| state.system.agent_tasks.contains_key(&session.id))
+}
+
 fn typed_recently(state: &AppState, session_id: Uuid) -> bool {
     state
         .data
@@ -119,6 +215,7 @@ pub fn tick(state: &mut AppState, action_tx: &mpsc::UnboundedSender<Action>) {
         .collect();
 
     for session_id in queued {
+        roll_budgets(state, session_id);
         retire_finished(state, session_id);
         dispatch_next(state, session_id, action_tx);
     }
@@ -165,13 +262,24 @@ fn dispatch_next(state: &mut AppState, session_id: Uuid, action_tx: &mpsc::Unbou
     if holding(state, session_id) != Holding::Empty {
         return;
     }
-    let Some(session) = state.get_session(session_id) else {
+    let Some(next) = next_item(state, session_id) else {
         return;
     };
-    let Some(next) = session.todo_queue.next_pending() else {
-        return;
+    let (id, ticket) = (next.id, next.consult_ticket().map(str::to_string));
+    let text = match &ticket {
+        // A consult that has gone — expired, or settled by a restart — has
+        // nobody waiting on the answer.
+        Some(ticket) => match comms_tick::consult_prompt(state, ticket) {
+            Some(text) => text,
+            None => {
+                if let Some(session) = state.get_session_mut(session_id) {
+                    session.todo_queue.remove(id);
+                }
+                return;
+            }
+        },
+        None => next.text.clone(),
     };
-    let (id, text) = (next.id, next.text.clone());
 
     if let Some(session) = state.get_session_mut(session_id) {
         session.todo_queue.mark_running(id);
@@ -183,6 +291,9 @@ fn dispatch_next(state: &mut AppState, session_id: Uuid, action_tx: &mpsc::Unbou
         .data
         .last_activity
         .insert(session_id, std::time::Instant::now());
+    if let Some(ticket) = ticket {
+        comms_tick::consult_delivered(state, &ticket);
+    }
 }
 
 fn send(action_tx: &mpsc::UnboundedSender<Action>, session_id: Uuid, text: &str) {
@@ -310,6 +421,219 @@ mod tests {
         assert!(rx.try_recv().is_err());
     }
 
+    /// Queue `ticket` as a pending consult for `id`, the way `comms_tick`
+    /// does on accepting one.
+    fn consult(state: &mut AppState, id: Uuid, ticket: &str, behind: bool) {
+        let workspace_id = state.data.workspaces[0].id;
+        state.system.comms.pending.push(comms_tick::PendingConsult {
+            ticket: ticket.into(),
+            workspace_id,
+            from_short: "aaaa1111".into(),
+            from_project: None,
+            group: None,
+            to_session: id,
+            to_short: "bbbb2222".into(),
+            question: "does this look right?".into(),
+            delivered: false,
+            transcript_base: 0,
+            activity_at_delivery: None,
+            journal_at_delivery: None,
+            delivered_at: None,
+            created: std::time::Instant::now(),
+            delivered_wall: None,
+            created_wall: chrono::Utc::now(),
+        });
+        state.get_session_mut(id).unwrap().todo_queue.add_consult(
+            ticket,
+            "aaaa1111",
+            "does this look right?",
+            behind,
+        );
+    }
+
+    /// A consult used to go in at the first idle, whatever else was going
+    /// on. Now it waits behind the same three gates as your own work — here,
+    /// your typing — and then goes, framed, ahead of the queued item.
+    #[test]
+    fn a_consult_waits_its_turn_and_goes_ahead_of_queued_work() {
+        let (mut state, id) = state_with_queue(&["do the thing"]);
+        report(&mut state, id, Activity::Idle);
+        consult(&mut state, id, "t1", false);
+        state
+            .data
+            .last_send_input
+            .insert(id, std::time::Instant::now());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        tick(&mut state, &tx);
+        assert_eq!(holding(&state, id), Holding::YouWereTyping);
+        assert!(rx.try_recv().is_err(), "not while you are typing");
+
+        state.data.last_send_input.clear();
+        tick(&mut state, &tx);
+
+        let running = queue(&state, id).running().unwrap();
+        assert_eq!(running.consult_ticket(), Some("t1"));
+        match rx.try_recv().unwrap() {
+            Action::SendInput(_, bytes) => {
+                let sent = String::from_utf8(bytes).unwrap();
+                assert!(sent.contains("[workbench consult t1 from agent aaaa1111]"), "{sent}");
+            }
+            other => panic!("expected input, got {other:?}"),
+        }
+        assert!(state.system.comms.pending[0].delivered);
+        assert_eq!(queue(&state, id).pending_count(), 1, "your item waits");
+    }
+
+    /// Pausing holds your work; a peer blocked on a question still gets its
+    /// answer — even one the policy puts behind the queue, which would
+    /// otherwise wait on items that are not going to run.
+    #[test]
+    fn a_paused_queue_still_answers_consults() {
+        let (mut state, id) = state_with_queue(&["do the thing"]);
+        report(&mut state, id, Activity::Idle);
+        consult(&mut state, id, "t1", true);
+        state.get_session_mut(id).unwrap().todo_queue.paused = true;
+        let (tx, _rx) = mpsc::unbounded_channel();
+
+        tick(&mut state, &tx);
+
+        assert_eq!(queue(&state, id).running().unwrap().consult_ticket(), Some("t1"));
+        assert_eq!(queue(&state, id).items[0].text, "do the thing");
+    }
+
+    /// A consult that expired, or that a restart settled, has nobody
+    /// waiting; its leftover queue item is dropped rather than sent.
+    #[test]
+    fn a_consult_nobody_waits_on_is_dropped_not_sent() {
+        let (mut state, id) = state_with_queue(&[]);
+        report(&mut state, id, Activity::Idle);
+        state
+            .get_session_mut(id)
+            .unwrap()
+            .todo_queue
+            .add_consult("gone", "aaaa1111", "still there?", false);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        tick(&mut state, &tx);
+
+        assert!(queue(&state, id).is_empty());
+        assert!(rx.try_recv().is_err());
+    }
+
+    /// Put `usd` worth of Opus input on the session's meter.
+    fn spent(state: &mut AppState, id: Uuid, usd: f64) {
+        let tokens = crate::models::TokenUsage {
+            input: (usd / 5.0 * 1_000_000.0) as u64,
+            ..Default::default()
+        };
+        state.system.agent_tasks.insert(
+            id,
+            crate::agent_tasks::TaskTracker::having_spent(
+                crate::agent_tasks::Provider::Claude,
+                "claude-opus-5",
+                tokens,
+            ),
+        );
+    }
+
+    /// The overnight failure this exists for: one agent keeps taking items
+    /// long after it has burned through what the day was meant to cost.
+    #[test]
+    fn a_queue_over_its_cap_stops_handing_out_work() {
+        let (mut state, id) = state_with_queue(&["first", "second"]);
+        report(&mut state, id, Activity::Idle);
+        state.get_session_mut(id).unwrap().todo_queue.budget =
+            Some(crate::models::Budget::parse("$5/queue").unwrap());
+        spent(&mut state, id, 1.0);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        // Opens the window at $1 and sends the first item.
+        tick(&mut state, &tx);
+        assert_eq!(queue(&state, id).running().unwrap().text, "first");
+        while rx.try_recv().is_ok() {}
+
+        spent(&mut state, id, 6.0);
+        state
+            .get_session_mut(id)
+            .unwrap()
+            .todo_queue
+            .finish_running();
+        tick(&mut state, &tx);
+
+        assert_eq!(holding(&state, id), Holding::OverBudget);
+        assert!(queue(&state, id).running().is_none());
+        assert!(rx.try_recv().is_err(), "nothing was sent");
+    }
+
+    /// A consult is a turn like any other: past the cap it waits with your
+    /// work, where a pause alone would let it through.
+    #[test]
+    fn a_queue_over_its_cap_holds_consults_too() {
+        let (mut state, id) = state_with_queue(&[]);
+        report(&mut state, id, Activity::Idle);
+        state.get_session_mut(id).unwrap().todo_queue.budget =
+            Some(crate::models::Budget::parse("$5/queue").unwrap());
+        spent(&mut state, id, 0.0);
+        roll_budgets(&mut state, id);
+        state.get_session_mut(id).unwrap().todo_queue.paused = true;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        spent(&mut state, id, 6.0);
+        consult(&mut state, id, "t1", false);
+        tick(&mut state, &tx);
+
+        assert_eq!(holding(&state, id), Holding::OverBudget);
+        assert!(queue(&state, id).running().is_none());
+        assert!(!state.system.comms.pending[0].delivered);
+        assert!(rx.try_recv().is_err(), "nothing was sent");
+    }
+
+    #[test]
+    fn a_workspace_cap_holds_every_queue_in_it() {
+        let (mut state, id) = state_with_queue(&["do the thing"]);
+        report(&mut state, id, Activity::Idle);
+        state.data.workspaces[0].budget = Some(crate::models::Budget::parse("$2/day").unwrap());
+        spent(&mut state, id, 0.0);
+        let (tx, _rx) = mpsc::unbounded_channel();
+        // Open the window without sending anything, so the queue is still
+        // deciding when the spend lands.
+        state.get_session_mut(id).unwrap().todo_queue.paused = true;
+        tick(&mut state, &tx);
+        state.get_session_mut(id).unwrap().todo_queue.paused = false;
+
+        spent(&mut state, id, 3.0);
+
+        assert_eq!(holding(&state, id), Holding::OverBudget);
+    }
+
+    /// Right after a restart the meter reads zero until the journal is
+    /// parsed; a window opened then would bill the whole resumed
+    /// conversation to today and hold the queue on the first real reading.
+    #[test]
+    fn a_budget_window_waits_for_the_meter_to_be_read() {
+        let (mut state, id) = state_with_queue(&["do the thing"]);
+        report(&mut state, id, Activity::Idle);
+        state.get_session_mut(id).unwrap().todo_queue.budget =
+            Some(crate::models::Budget::parse("$5/day").unwrap());
+        state.get_session_mut(id).unwrap().todo_queue.paused = true;
+        let (tx, _rx) = mpsc::unbounded_channel();
+
+        tick(&mut state, &tx);
+        let budget = queue(&state, id).budget.clone().unwrap();
+        assert!(budget.opened_at.is_none(), "no journal read yet");
+
+        spent(&mut state, id, 40.0);
+        tick(&mut state, &tx);
+
+        let budget = queue(&state, id).budget.clone().unwrap();
+        assert!(budget.opened_at.is_some());
+        assert!((budget.last_meter.estimated_cost_usd - 40.0).abs() < 0.01);
+        assert_eq!(budget.counted, crate::models::SessionCost::default());
+        state.get_session_mut(id).unwrap().todo_queue.paused = false;
+        assert_eq!(holding(&state, id), Holding::Empty, "yesterday's spend is not today's");
+    }
+
     #[test]
     fn only_one_item_is_in_flight_at_a_time() {
         let (mut state, id) = state_with_queue(&["first", "second"]);
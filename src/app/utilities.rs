@@ -173,6 +173,27 @@ pub fn load_utility_content(state: &mut AppState, action_tx: &mpsc::UnboundedSen
                 );
             });
         }
+        UtilityItem::Worktrees => {
+            state.ui.utility_content = loading_message("Orphaned Worktrees");
+            // From what is in memory rather than the state file: a session
+            // started a moment ago may not be saved yet.
+            let known =
+                crate::worktree_gc::Known::new(&state.data.workspaces, &state.data.sessions);
+            let action_tx = action_tx.clone();
+            task::spawn_blocking(move || {
+                let content = build_orphaned_worktrees(&workspace_path, &known);
+                queue_utility_content(
+                    &action_tx,
+                    UtilityContentPayload {
+                        request_id,
+                        content,
+                        pie_chart_data: Vec::new(),
+                        show_calendar: false,
+                    },
+                    "failed to load orphaned worktrees utility content",
+                );
+            });
+        }
         UtilityItem::Keybindings => {
             load_keybindings_info(state);
         }
@@ -386,6 +407,38 @@ fn load_calendar_content(state: &mut AppState) {
     state.ui.utility_content = content;
 }
 
+/// Report only: removing one is a question per orphan, which the CLI asks.
+fn build_orphaned_worktrees(
+    workspace_path: &Path,
+    known: &crate::worktree_gc::Known,
+) -> Vec<String> {
+    let mut content = vec![
+        String::new(),
+        "  Orphaned Worktrees".to_string(),
+        "  ==================".to_string(),
+        String::new(),
+    ];
+    let found = crate::worktree_gc::find_orphans(workspace_path, known, crate::worktree_gc::GRACE);
+    match found {
+        Ok(orphans) if orphans.is_empty() => {
+            content.push("  None: every worktree and agent branch has an owner.".to_string());
+        }
+        Ok(orphans) => {
+            for orphan in &orphans {
+                content.push(format!("  {}", orphan.title(workspace_path)));
+                for line in orphan.details() {
+                    content.push(format!("      {line}"));
+                }
+            }
+            content.push(String::new());
+            content.push("  Remove them with `workbench worktrees prune`,".to_string());
+            content.push("  which asks before each one.".to_string());
+        }
+        Err(err) => content.push(format!("  Could not check: {err:#}")),
+    }
+    content
+}
+
 /// Load git history for the workspace
 fn build_git_history(workspace_path: &Path) -> Vec<String> {
     let output = std::process::Command::new("git")
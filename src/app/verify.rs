@@ -0,0 +1,360 @@
+//! Running a parallel task's verify command in each finished attempt.
+//!
+//! A report says what an agent *thinks* it did; the test suite says whether
+//! it works. When a task has a verify command (`cargo test`, `npm test`, …)
+//! every attempt that completes gets it run in its own worktree, and the
+//! result — exit status, how long it took, the tail of its output — is kept
+//! on the attempt for the Reports tab to rank by.
+//!
+//! One attempt per task runs at a time. Three test suites compiling at once
+//! on a laptop take longer than three in a row, and the durations would
+//! measure contention rather than the change — and duration is the
+//! tiebreaker between attempts that all pass.
+
+use chrono::Utc;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::app::{Action, AppState};
+use crate::models::{AttemptStatus, ParallelTaskStatus, Verification, VerifyOutcome};
+
+/// A suite that hangs must not hold up the rest of the task's attempts.
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// How much of the output is kept: enough for a failing test's assertion
+/// and backtrace head, small enough to live in the state file.
+const TAIL_LINES: usize = 40;
+const TAIL_BYTES: usize = 4000;
+
+/// How long the output is waited for once the command is over. The pipe
+/// stays open as long as anything holds its end, and a suite may leave a
+/// server behind that does; what it printed so far is kept either way.
+const READER_GRACE: Duration = Duration::from_secs(2);
+
+/// Start the next verification each task is waiting on. Cheap when there
+/// is nothing to do; runs every tick.
+pub fn tick(state: &mut AppState, action_tx: &mpsc::UnboundedSender<Action>) {
+    let mut starts = Vec::new();
+    for ws in state.data.workspaces.iter_mut() {
+        for task in ws.parallel_tasks.iter_mut() {
+            let live = matches!(
+                task.status,
+                ParallelTaskStatus::Running | ParallelTaskStatus::AwaitingSelection
+            );
+            let Some(command) = task.verify_command.clone().filter(|_| live) else {
+                continue;
+            };
+            if task.attempts.iter().any(|a| state.system.verifying.contains(&a.id)) {
+                continue;
+            }
+            // Never started, or started and then cut off by a restart.
+            let next = task.attempts.iter_mut().find(|a| {
+                a.status == AttemptStatus::Completed
+                    && a.verification.as_ref().is_none_or(|v| v.outcome.is_none())
+            });
+            if let Some(attempt) = next {
+                attempt.verification = Some(Verification {
+                    command: command.clone(),
+                    started_at: Utc::now(),
+                    outcome: None,
+                });
+                starts.push((attempt.id, attempt.worktree_path.clone(), command));
+            }
+        }
+    }
+    if starts.is_empty() {
+        return;
+    }
+
+    for (attempt_id, worktree, command) in starts {
+        state.system.verifying.insert(attempt_id);
+        let tx = action_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let outcome = run(&worktree, &command, VERIFY_TIMEOUT);
+            if let Err(err) = tx.send(Action::VerificationFinished { attempt_id, outcome }) {
+                crate::app::handlers::report_background_error("failed to report verification", err);
+            }
+        });
+    }
+    crate::app::handlers::save_state(state, "failed to save verification start");
+}
+
+/// Record a finished run on its attempt.
+pub fn finished(state: &mut AppState, attempt_id: Uuid, outcome: VerifyOutcome) {
+    state.system.verifying.remove(&attempt_id);
+    let verification = state
+        .data
+        .workspaces
+        .iter_mut()
+        .flat_map(|ws| ws.parallel_tasks.iter_mut())
+        .flat_map(|task| task.attempts.iter_mut())
+        .find(|attempt| attempt.id == attempt_id)
+        .and_then(|attempt| attempt.verification.as_mut());
+    if let Some(verification) = verification {
+        verification.outcome = Some(outcome);
+        crate::app::handlers::save_state(state, "failed to save verification result");
+    }
+}
+
+/// Forget an attempt's result so the next tick runs the command again —
+/// for when the agent was sent back to fix something after it finished.
+pub fn rerun(state: &mut AppState, attempt_id: Uuid) -> bool {
+    if state.system.verifying.contains(&attempt_id) {
+        return false;
+    }
+    let attempt = state
+        .data
+        .workspaces
+        .iter_mut()
+        .flat_map(|ws| ws.parallel_tasks.iter_mut())
+        .filter(|task| task.verify_command.is_some())
+        .flat_map(|task| task.attempts.iter_mut())
+        .find(|attempt| attempt.id == attempt_id);
+    match attempt {
+        Some(attempt) => {
+            attempt.verification = None;
+            // A rerun means the agent is done again, even if it had been
+            // marked failed.
+            attempt.status = AttemptStatus::Completed;
+            crate::app::handlers::save_state(state, "failed to save verification rerun");
+            true
+        }
+        None => false,
+    }
+}
+
+/// Run `command` through the shell in `dir`, with stderr folded into
+/// stdout so the tail reads as it would in a terminal.
+fn run(dir: &Path, command: &str, timeout: Duration) -> VerifyOutcome {
+    let started = Instant::now();
+    let spawned = shell(command)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(err) => {
+            return VerifyOutcome {
+                exit_code: None,
+                duration_ms: 0,
+                output_tail: format!("could not start `{command}`: {err}"),
+            };
+        }
+    };
+
+    // Read on a thread of its own: a suite that prints more than a pipe
+    // holds would otherwise block forever waiting for us to drain it. The
+    // thread is never joined — see `READER_GRACE` — so it reads into a
+    // buffer we can take from whether or not it has finished.
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let (done_tx, done_rx) = std::sync::mpsc::channel();
+    let stdout = child.stdout.take();
+    let buffer = Arc::clone(&output);
+    std::thread::spawn(move || {
+        let mut chunk = [0u8; 8192];
+        if let Some(mut stdout) = stdout {
+            while let Ok(read @ 1..) = stdout.read(&mut chunk) {
+                if let Ok(mut buffer) = buffer.lock() {
+                    buffer.extend_from_slice(&chunk[..read]);
+                }
+            }
+        }
+        let _ = done_tx.send(());
+    });
+
+    let mut timed_out = false;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if started.elapsed() >= timeout => {
+                kill(&mut child);
+                timed_out = true;
+                break child.wait().ok();
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(200)),
+            Err(_) => break None,
+        }
+    };
+    let _ = done_rx.recv_timeout(READER_GRACE);
+    let output = output.lock().map(|output| output.clone()).unwrap_or_default();
+
+    let mut output_tail = tail(&String::from_utf8_lossy(&output));
+    if timed_out {
+        output_tail.push_str(&format!("\n[stopped after {} minutes]", timeout.as_secs() / 60));
+    }
+    VerifyOutcome {
+        exit_code: if timed_out { None } else { status.and_then(|s| s.code()) },
+        duration_ms: started.elapsed().as_millis() as u64,
+        output_tail,
+    }
+}
+
+/// Stop the command and everything it started. Killing only the shell
+/// would leave `cargo test` and its test binaries running, and holding the
+/// output pipe open.
+fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    // SAFETY: `shell` makes the child the leader of a group of its own, so
+    // its pid names that group and nothing outside it.
+    unsafe {
+        libc::killpg(child.id() as libc::pid_t, libc::SIGKILL);
+    }
+    let _ = child.kill();
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.args(["/C", &format!("{command} 2>&1")]);
+    shell
+}
+
+#[cfg(not(windows))]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    // A group, so `2>&1` covers every part of `a && b; c`.
+    shell.args(["-c", &format!("{{ {command}\n}} 2>&1")]);
+    // A process group of its own, so a timeout can stop all of it.
+    std::os::unix::process::CommandExt::process_group(&mut shell, 0);
+    shell
+}
+
+fn tail(output: &str) -> String {
+    let lines: Vec<&str> = output.trim_end().lines().collect();
+    let start = lines.len().saturating_sub(TAIL_LINES);
+    let joined = lines[start..].join("\n");
+    // Counted in bytes, as the state file stores them; the cut moves forward
+    // to the next whole character rather than split one.
+    let mut cut = joined.len().saturating_sub(TAIL_BYTES);
+    while !joined.is_char_boundary(cut) {
+        cut += 1;
+    }
+    joined[cut..].to_string()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::models::{AgentType, ParallelTask, ParallelTaskAttempt, Workspace};
+
+    /// The outcome is the command's own exit status and the end of what it
+    /// printed on either stream — what you'd look at in a terminal.
+    #[test]
+    fn run_keeps_exit_status_and_the_tail_of_both_streams() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let outcome = run(
+            dir.path(),
+            "for i in $(seq 1 100); do echo line $i; done; echo oops >&2; exit 3",
+            Duration::from_secs(30),
+        );
+        assert_eq!(outcome.exit_code, Some(3));
+        assert!(!outcome.passed());
+        let lines: Vec<&str> = outcome.output_tail.lines().collect();
+        assert_eq!(lines.len(), TAIL_LINES);
+        assert_eq!(lines.last(), Some(&"oops"));
+        assert_eq!(lines[0], "line 62");
+
+        let slow = run(dir.path(), "sleep 5", Duration::from_millis(300));
+        assert_eq!(slow.exit_code, None);
+        assert!(slow.output_tail.contains("stopped after"));
+    }
+
+    /// A timeout stops what the shell started as well as the shell, and
+    /// does not wait on a pipe that something outside the group still
+    /// holds open.
+    #[test]
+    fn a_timeout_stops_the_whole_command_and_does_not_wait_on_its_output() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let started = Instant::now();
+        let outcome = run(
+            dir.path(),
+            "echo begun; sleep 30 & sleep 30",
+            Duration::from_millis(300),
+        );
+        assert!(started.elapsed() < Duration::from_secs(10), "{:?}", started.elapsed());
+        assert_eq!(outcome.exit_code, None);
+        assert!(outcome.output_tail.starts_with("begun"), "{}", outcome.output_tail);
+
+        let started = Instant::now();
+        // A process group of its own, out of reach of the group kill, still
+        // holding the pipe; `setsid(1)` is not on macOS, so perl does it.
+        let escaped = run(
+            dir.path(),
+            "perl -e 'setpgrp; exec \"sleep\", 8'",
+            Duration::from_millis(300),
+        );
+        assert!(started.elapsed() < Duration::from_secs(6), "{:?}", started.elapsed());
+        assert!(escaped.output_tail.contains("stopped after"));
+    }
+
+    /// The tail's size is a byte count, and it never ends up splitting a
+    /// character to meet it.
+    #[test]
+    fn the_tail_is_cut_in_bytes_on_a_character_boundary() {
+        let wide = "é".repeat(TAIL_BYTES);
+        let kept = tail(&wide);
+        assert!(kept.len() <= TAIL_BYTES, "{}", kept.len());
+        assert!(kept.len() >= TAIL_BYTES - 1, "{}", kept.len());
+        assert!(kept.chars().all(|c| c == 'é'));
+        assert_eq!(tail("short"), "short");
+    }
+
+    /// Completed attempts are verified one at a time per task, in order;
+    /// running ones wait until their agent is done.
+    #[tokio::test]
+    async fn tick_verifies_one_finished_attempt_per_task_at_a_time() {
+        let mut state = AppState::default();
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut workspace = Workspace::new("ws".into(), dir.path().to_path_buf());
+        let mut task = ParallelTask::new(
+            workspace.id,
+            "fix it".into(),
+            "main".into(),
+            "abc".into(),
+            false,
+        );
+        task.verify_command = Some("true".into());
+        for (agent, status) in [
+            (AgentType::Claude, AttemptStatus::Running),
+            (AgentType::Codex, AttemptStatus::Completed),
+            (AgentType::Gemini, AttemptStatus::Completed),
+        ] {
+            let mut attempt = ParallelTaskAttempt::new(
+                task.id,
+                Uuid::new_v4(),
+                agent,
+                "b".into(),
+                dir.path().to_path_buf(),
+            );
+            attempt.status = status;
+            task.add_attempt(attempt);
+        }
+        let ids: Vec<Uuid> = task.attempts.iter().map(|a| a.id).collect();
+        workspace.add_parallel_task(task);
+        state.data.workspaces.push(workspace);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        tick(&mut state, &tx);
+        tick(&mut state, &tx);
+        assert_eq!(state.system.verifying.iter().collect::<Vec<_>>(), vec![&ids[1]]);
+
+        let Some(Action::VerificationFinished { attempt_id, outcome }) = rx.recv().await else {
+            panic!("expected a verification result");
+        };
+        assert!(outcome.passed());
+        finished(&mut state, attempt_id, outcome);
+        tick(&mut state, &tx);
+        assert_eq!(state.system.verifying.iter().collect::<Vec<_>>(), vec![&ids[2]]);
+
+        let task = &state.data.workspaces[0].parallel_tasks[0];
+        assert!(task.attempts[0].verification.is_none());
+        assert!(task.attempts[1].verification.as_ref().unwrap().outcome.is_some());
+    }
+}
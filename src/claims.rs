@@ -0,0 +1,306 @@
+//! Which agent is editing which files, for agents sharing one checkout.
+//!
+//! A worktree gives each agent its own copy; without one, nothing stops two
+//! agents rewriting the same file, and the loser only finds out when its
+//! edit fails to apply — or worse, applies over the other's. A claim is an
+//! agent saying "I am working in `src/parser/`" before it starts, so the next
+//! one can see it and pick something else.
+//!
+//! ```text
+//! <config>/workbench/comms/<workspace-id>/claims.json
+//! ```
+//!
+//! Claims are advisory: nothing stops an edit. What the registry adds is a
+//! record of the edits that went ahead anyway — the `PostToolUse` hook checks
+//! every file an agent edits against the other agents' claims, and a hit
+//! lands here as a conflict for the TUI to put on that agent's row.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::comms;
+
+/// A path one session has claimed: a file, or a directory and everything in
+/// it. Always absolute, so a claim made from one directory matches an edit
+/// reported from another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claim {
+    pub path: PathBuf,
+    /// The claimant's short session id.
+    pub session: String,
+    pub at: DateTime<Utc>,
+}
+
+/// An edit to a path someone else had claimed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Conflict {
+    /// Who edited it.
+    pub session: String,
+    pub path: PathBuf,
+    /// Who had claimed it.
+    pub holder: String,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Registry {
+    #[serde(default)]
+    pub claims: Vec<Claim>,
+    #[serde(default)]
+    pub conflicts: Vec<Conflict>,
+}
+
+/// What `claim` came to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Claimed(Vec<PathBuf>),
+    /// Nothing was claimed: these overlap what was asked for.
+    Held(Vec<Claim>),
+}
+
+pub fn registry_path(workspace_id: &str) -> Result<PathBuf> {
+    Ok(comms::workspace_dir(workspace_id)?.join("claims.json"))
+}
+
+pub fn load(path: &Path) -> Registry {
+    fs::read(path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+/// Read, change and write the registry under its lock.
+fn update<R>(path: &Path, change: impl FnOnce(&mut Registry) -> R) -> Result<R> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let _lock = comms::FileLock::take(&path.with_extension("lock"))?;
+    let mut registry = load(path);
+    let result = change(&mut registry);
+    comms::write_atomic(path, serde_json::to_string_pretty(&registry)?.as_bytes())?;
+    Ok(result)
+}
+
+/// `path` as an absolute path with `.` and `..` taken out, without touching
+/// the disk — a claim can name a file that does not exist yet.
+pub fn absolute(cwd: &Path, path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in cwd.join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// One covers the other: the same path, or one a directory holding the other.
+fn overlaps(a: &Path, b: &Path) -> bool {
+    a.starts_with(b) || b.starts_with(a)
+}
+
+impl Registry {
+    /// Another session's claim covering `path`, if there is one.
+    pub fn holder(&self, path: &Path, except: &str) -> Option<&Claim> {
+        self.claims
+            .iter()
+            .find(|claim| claim.session != except && path.starts_with(&claim.path))
+    }
+
+    /// The conflicts that still stand: the holder has not let go of the path
+    /// since. Once it has, the edit no longer steps on anyone.
+    pub fn standing_conflicts(&self) -> impl Iterator<Item = &Conflict> {
+        self.conflicts.iter().filter(|conflict| {
+            self.claims
+                .iter()
+                .any(|claim| claim.session == conflict.holder && conflict.path.starts_with(&claim.path))
+        })
+    }
+
+    /// Drop what belongs to sessions that are no longer running: their
+    /// claims protect nothing, and their conflicts are moot.
+    fn forget_dead(&mut self, is_live: &impl Fn(&str) -> bool) {
+        self.claims.retain(|claim| is_live(&claim.session));
+        self.conflicts
+            .retain(|conflict| is_live(&conflict.session) && is_live(&conflict.holder));
+    }
+}
+
+/// Claim `paths` for `session`, all or nothing: if any overlaps another live
+/// session's claim, none is taken and the overlapping claims are returned.
+/// Claiming again what you already hold just renews it.
+pub fn claim(
+    registry: &Path,
+    session: &str,
+    paths: &[PathBuf],
+    is_live: impl Fn(&str) -> bool,
+) -> Result<Outcome> {
+    update(registry, |registry| {
+        registry.forget_dead(&is_live);
+        let held: Vec<Claim> = registry
+            .claims
+            .iter()
+            .filter(|claim| claim.session != session)
+            .filter(|claim| paths.iter().any(|path| overlaps(path, &claim.path)))
+            .cloned()
+            .collect();
+        if !held.is_empty() {
+            return Outcome::Held(held);
+        }
+        let at = Utc::now();
+        registry
+            .claims
+            .retain(|claim| claim.session != session || !paths.contains(&claim.path));
+        for path in paths {
+            registry.claims.push(Claim {
+                path: path.clone(),
+                session: session.to_string(),
+                at,
+            });
+        }
+        Outcome::Claimed(paths.to_vec())
+    })
+}
+
+/// Let go of `session`'s claims on `paths`, or of all of them when `paths`
+/// is empty. Returns what was released.
+pub fn release(registry: &Path, session: &str, paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    update(registry, |registry| {
+        let mut released = Vec::new();
+        registry.claims.retain(|claim| {
+            let ours = claim.session == session
+                && (paths.is_empty() || paths.iter().any(|path| claim.path.starts_with(path)));
+            if ours {
+                released.push(claim.path.clone());
+            }
+            !ours
+        });
+        released
+    })
+}
+
+/// Called from the `PostToolUse` hook with the file an agent just edited.
+/// Quick when there is nothing to say — one read — and writes only when the
+/// edit landed in another session's claim.
+pub fn note_edit(registry: &Path, session: &str, path: &Path) -> Result<Option<Claim>> {
+    let Some(holder) = load(registry).holder(path, session).cloned() else {
+        return Ok(None);
+    };
+    update(registry, |registry| {
+        registry
+            .conflicts
+            .retain(|conflict| !(conflict.session == session && conflict.path == path));
+        registry.conflicts.push(Conflict {
+            session: session.to_string(),
+            path: path.to_path_buf(),
+            holder: holder.session.clone(),
+            at: Utc::now(),
+        });
+    })?;
+    Ok(Some(holder))
+}
+
+/// The file a hook payload says the agent edited: Claude's editing tools
+/// carry it in `tool_input`. Anything else — a shell command, a read — is not
+/// an edit we can attribute to a path.
+pub fn edited_path(payload: &serde_json::Value) -> Option<PathBuf> {
+    let tool = payload.get("tool_name")?.as_str()?;
+    let field = match tool {
+        "Edit" | "Write" | "MultiEdit" => "file_path",
+        "NotebookEdit" => "notebook_path",
+        _ => return None,
+    };
+    let path = payload.get("tool_input")?.get(field)?.as_str()?;
+    let cwd = payload
+        .get("cwd")
+        .and_then(serde_json::Value::as_str)
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    Some(absolute(&cwd, Path::new(path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn live(_: &str) -> bool {
+        true
+    }
+
+    /// A directory claim covers the files in it, and a file claim blocks a
+    /// claim on its directory — either way the two would edit the same file.
+    #[test]
+    fn claims_that_overlap_are_refused_whole() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = dir.path().join("claims.json");
+        let parser = PathBuf::from("/repo/src/parser");
+
+        let first = claim(&registry, "aaaa1111", std::slice::from_ref(&parser), live).unwrap();
+        assert_eq!(first, Outcome::Claimed(vec![parser.clone()]));
+
+        let lexer = PathBuf::from("/repo/src/lexer.rs");
+        let wanted = [lexer.clone(), PathBuf::from("/repo/src/parser/expr.rs")];
+        let Outcome::Held(held) = claim(&registry, "bbbb2222", &wanted, live).unwrap() else {
+            panic!("an overlapping claim was granted");
+        };
+        assert_eq!(held[0].session, "aaaa1111");
+        // All or nothing: the free half was not taken either.
+        assert!(load(&registry).holder(&lexer, "aaaa1111").is_none());
+
+        assert!(matches!(
+            claim(&registry, "bbbb2222", &[PathBuf::from("/repo/src")], live).unwrap(),
+            Outcome::Held(_)
+        ));
+    }
+
+    #[test]
+    fn a_dead_sessions_claims_do_not_hold_anyone_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = dir.path().join("claims.json");
+        let path = PathBuf::from("/repo/src/main.rs");
+        claim(&registry, "aaaa1111", std::slice::from_ref(&path), live).unwrap();
+
+        let outcome =
+            claim(&registry, "bbbb2222", std::slice::from_ref(&path), |s| s != "aaaa1111").unwrap();
+
+        assert_eq!(outcome, Outcome::Claimed(vec![path]));
+    }
+
+    /// An edit inside someone else's claim is recorded against the editor,
+    /// and stops counting once the holder lets go.
+    #[test]
+    fn an_edit_to_a_claimed_file_stands_until_the_claim_is_released() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = dir.path().join("claims.json");
+        claim(&registry, "aaaa1111", &[PathBuf::from("/repo/src/parser")], live).unwrap();
+
+        let edited = Path::new("/repo/src/parser/expr.rs");
+        assert!(note_edit(&registry, "aaaa1111", edited).unwrap().is_none());
+        let holder = note_edit(&registry, "bbbb2222", edited).unwrap().unwrap();
+        assert_eq!(holder.session, "aaaa1111");
+        assert_eq!(load(&registry).standing_conflicts().count(), 1);
+
+        release(&registry, "aaaa1111", &[]).unwrap();
+
+        assert_eq!(load(&registry).standing_conflicts().count(), 0);
+    }
+
+    #[test]
+    fn an_edit_is_read_off_the_hook_payload() {
+        let payload = serde_json::json!({
+            "tool_name": "Edit",
+            "cwd": "/repo",
+            "tool_input": {"file_path": "src/../src/main.rs", "old_string": "a"}
+        });
+        assert_eq!(edited_path(&payload), Some(PathBuf::from("/repo/src/main.rs")));
+
+        let bash = serde_json::json!({"tool_name": "Bash", "tool_input": {"command": "ls"}});
+        assert_eq!(edited_path(&bash), None);
+    }
+}
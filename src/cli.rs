@@ -2,7 +2,7 @@
 //! comms. These are pure file readers/writers against the comms directory —
 //! the running TUI does the live work (see `app::comms_tick`).
 
-use crate::comms::{self, InboxMessage, Reply};
+use crate::comms::{self, InboxMessage, Reply, Roster, RosterAgent};
 use anyhow::{anyhow, bail, Result};
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
@@ -29,19 +29,85 @@ fn caller_ctx() -> Result<CallerCtx> {
     })
 }
 
-pub fn cmd_agents() -> Result<()> {
+/// Find the peer `target` names: an agent in the caller's own roster, or —
+/// for `project:agent` — one in another project's. Crossing projects needs
+/// both ends to have opted in (`cross_project` in the user config); the TUI
+/// checks the same again when the consult arrives, since a roster on disk is
+/// only as fresh as its last refresh.
+fn resolve_peer(ctx: &CallerCtx, target: &str) -> Result<(Roster, RosterAgent)> {
+    let own = comms::load_roster(&ctx.workspace_id)?;
+    let (project, agent) = comms::split_address(target);
+    let Some(project) = project else {
+        let agent = comms::resolve_target(&own, agent, ctx.session.as_deref())
+            .map_err(|e| anyhow!(e))?
+            .clone();
+        return Ok((own, agent));
+    };
+    if !own.cross_project {
+        bail!(
+            "project {} does not take part in cross-project consults \
+             (add it to `cross_project` in user_config.toml)",
+            own.workspace_name
+        );
+    }
+    let rosters = comms::load_all_rosters()?;
+    let roster = comms::resolve_project(&rosters, project).map_err(|e| anyhow!(e))?;
+    // Only your own project can contain you.
+    let me = (roster.workspace_id == own.workspace_id)
+        .then_some(ctx.session.as_deref())
+        .flatten();
+    let agent = comms::resolve_target(roster, agent, me)
+        .map_err(|e| anyhow!(e))?
+        .clone();
+    Ok((roster.clone(), agent))
+}
+
+pub fn cmd_agents(all: bool) -> Result<()> {
     let ctx = caller_ctx()?;
     let roster = comms::load_roster(&ctx.workspace_id)?;
+    print_roster(&roster, ctx.session.as_deref());
+    if all {
+        if roster.cross_project {
+            let mut others: Vec<Roster> = comms::load_all_rosters()?
+                .into_iter()
+                .filter(|r| r.cross_project && r.workspace_id != roster.workspace_id)
+                .collect();
+            others.sort_by(|a, b| a.workspace_name.cmp(&b.workspace_name));
+            for other in &others {
+                println!();
+                print_roster(other, None);
+            }
+            if others.is_empty() {
+                println!("\nno other project takes cross-project consults");
+            }
+        } else {
+            println!(
+                "\n{} does not take part in cross-project consults \
+                 (add it to `cross_project` in user_config.toml)",
+                roster.workspace_name
+            );
+        }
+    }
+    println!("\naddress a peer by id or alias (provider name works when unique):");
+    println!("  workbench transcript <id> --lines 200");
+    println!("  workbench ask <id> \"question\"");
+    if all && roster.cross_project {
+        println!("  workbench ask <project>:<id> \"question\"   (another project)");
+    }
+    Ok(())
+}
+
+fn print_roster(roster: &Roster, me: Option<&str>) {
     println!(
         "workspace: {} ({})  updated: {}",
         roster.workspace_name, roster.workspace_path, roster.updated_at
     );
     if roster.agents.is_empty() {
         println!("no agent sessions");
-        return Ok(());
+        return;
     }
     for a in &roster.agents {
-        let you = if Some(&a.id) == ctx.session.as_ref() {
+        let you = if Some(a.id.as_str()) == me {
             "  (you)"
         } else {
             ""
@@ -57,17 +123,32 @@ pub fn cmd_agents() -> Result<()> {
             a.id, a.provider, alias, you, a.branch, a.status, consult, a.cwd
         );
     }
-    println!("\naddress a peer by id or alias (provider name works when unique):");
-    println!("  workbench transcript <id> --lines 200");
-    println!("  workbench ask <id> \"question\"");
-    Ok(())
+    if !roster.claims.is_empty() {
+        println!("claims:");
+        for claim in &roster.claims {
+            let path = claim
+                .path
+                .strip_prefix(&roster.workspace_path)
+                .unwrap_or(&claim.path);
+            println!("  {}  {}", claim.session, path.display());
+        }
+    }
+    if !roster.notes.is_empty() {
+        println!("notes:");
+        for note in &roster.notes {
+            println!(
+                "  {:<24} v{:<3} {}",
+                note.key,
+                note.version,
+                note.author.as_deref().unwrap_or("-")
+            );
+        }
+    }
 }
 
 pub fn cmd_transcript(target: String, lines: usize, all: bool) -> Result<()> {
     let ctx = caller_ctx()?;
-    let roster = comms::load_roster(&ctx.workspace_id)?;
-    let agent = comms::resolve_target(&roster, &target, ctx.session.as_deref())
-        .map_err(|e| anyhow!(e))?;
+    let (_, agent) = resolve_peer(&ctx, &target)?;
     let Some(path) = agent.transcript.as_ref().map(PathBuf::from) else {
         bail!(
             "{} ({}) has no transcript — it is not a transcript-capable agent",
@@ -102,8 +183,174 @@ workspace. Produce a structured handoff summary:\n\
 5. Gotchas — surprises, fragile spots, anything a successor would waste time rediscovering\n\
 Be concrete: real paths, real names. Skip process narration.";
 
-pub fn cmd_handoff(target: String, wait: bool, timeout_secs: u64) -> Result<()> {
-    cmd_ask(target, HANDOFF_PROMPT.to_string(), wait, timeout_secs)
+pub fn cmd_handoff(
+    target: String,
+    to: Option<String>,
+    worktree: bool,
+    stop: bool,
+    wait: bool,
+    timeout_secs: u64,
+) -> Result<()> {
+    match to {
+        Some(provider) => take_over(target, provider, worktree, stop, timeout_secs),
+        None => cmd_ask(target, HANDOFF_PROMPT.to_string(), wait, timeout_secs),
+    }
+}
+
+/// `handoff --to`: hand an agent's work to a new one. The summary is an
+/// ordinary handoff consult; the rest goes over the control socket, since
+/// starting a session and moving queue items are the TUI's to do.
+///
+/// The old agent's queue is paused first, so it does not pick up the next
+/// item while it writes the summary — the item it would start is one of the
+/// ones about to move. Whatever fails after that — no summary, a successor
+/// that would not start, a socket that went away — the queue is handed back
+/// as it was found.
+fn take_over(
+    target: String,
+    provider: String,
+    worktree: bool,
+    stop: bool,
+    timeout_secs: u64,
+) -> Result<()> {
+    use serde_json::{json, Value};
+
+    // Checked before anything is paused: a typo here should cost nothing,
+    // not a handoff summary nobody can use.
+    if !matches!(provider.as_str(), "claude" | "codex") {
+        bail!("no provider {provider} — claude or codex");
+    }
+    let ctx = caller_ctx()?;
+    let (roster, agent) = resolve_peer(&ctx, &target)?;
+    if ctx.session.as_deref() == Some(agent.id.as_str()) {
+        bail!("an agent cannot hand itself off — it would be waiting on its own summary");
+    }
+    if !agent.supports_consult {
+        bail!(
+            "{} ({}) cannot write a handoff summary (no transcript to read it from)",
+            agent.id,
+            agent.provider
+        );
+    }
+    let mut client = crate::control::Client::connect()?;
+    let queue = client.call("todo.list", json!({"agent": agent.id}))?;
+    let was_paused = queue.get("paused").and_then(Value::as_bool) == Some(true);
+    client.call("todo.pause", json!({"agent": agent.id}))?;
+
+    let handed = hand_over(&mut client, &ctx, &roster, &agent, &provider, worktree, timeout_secs);
+    let handed = handed.and_then(|handed| {
+        if stop {
+            client.call("agent.stop", json!({"agent": agent.id}))?;
+        }
+        Ok(handed)
+    });
+    let (successor, moved) = match handed {
+        Ok(handed) => handed,
+        Err(err) => {
+            if !was_paused {
+                let _ = client.call("todo.resume", json!({"agent": agent.id}));
+            }
+            return Err(err);
+        }
+    };
+    if !stop && !was_paused {
+        client.call("todo.resume", json!({"agent": agent.id}))?;
+    }
+    println!(
+        "{successor} ({provider}) took over from {} with {moved} queued item(s){}",
+        agent.id,
+        if stop { "; it has been stopped" } else { "" }
+    );
+    Ok(())
+}
+
+/// The part of a take-over that runs with the old agent's queue paused:
+/// its summary, the successor, and the queued tasks moved across. Answers
+/// the successor's id and how many tasks went with it.
+fn hand_over(
+    client: &mut crate::control::Client,
+    ctx: &CallerCtx,
+    roster: &Roster,
+    agent: &RosterAgent,
+    provider: &str,
+    worktree: bool,
+    timeout_secs: u64,
+) -> Result<(String, usize)> {
+    use serde_json::{json, Value};
+
+    let summary = handoff_summary(ctx, roster, agent, timeout_secs)?;
+
+    // What is still waiting, read now rather than before the summary: the
+    // queue may have grown while the agent wrote it.
+    let queue = client.call("todo.list", json!({"agent": agent.id}))?;
+    let pending: Vec<(String, String)> = queue
+        .get("items")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|item| item.get("state").and_then(Value::as_str) == Some("pending"))
+        // A peer's consult is for the agent it asked, not its successor.
+        .filter(|item| item.pointer("/kind/type").and_then(Value::as_str) == Some("task"))
+        .filter_map(|item| {
+            let id = item.get("id")?.as_str()?.to_string();
+            let text = item.get("text")?.as_str()?.to_string();
+            Some((id, text))
+        })
+        .collect();
+
+    let checkout = if worktree {
+        "You have a worktree of your own, on a branch started from its branch; \
+         anything it had not committed is not in it."
+    } else {
+        "You are in its checkout, so run `git status` first: \
+         what it had not committed yet is there too."
+    };
+    let prompt = format!(
+        "You are taking over from agent {} ({}). Its handoff summary:\n\n{}\n\n\
+         Carry on from the Remaining steps. {checkout}",
+        agent.id,
+        agent.provider,
+        summary.trim()
+    );
+    let started = client.call(
+        "agent.new",
+        json!({
+            "project": roster.workspace_id,
+            "provider": provider,
+            "worktree": worktree,
+            "successor_of": agent.id,
+            "prompt": prompt,
+            "todos": pending.iter().map(|(_, text)| text).collect::<Vec<_>>(),
+        }),
+    )?;
+    let successor = started.get("agent").and_then(Value::as_str).unwrap_or("?").to_string();
+
+    for (id, _) in &pending {
+        client.call("todo.remove", json!({"agent": agent.id, "todo": id}))?;
+    }
+    Ok((successor, pending.len()))
+}
+
+/// Ask `agent` for its handoff summary and wait for it. From a plain shell
+/// there is no session to ask from, so the consult comes from "user".
+fn handoff_summary(
+    ctx: &CallerCtx,
+    roster: &Roster,
+    agent: &RosterAgent,
+    timeout_secs: u64,
+) -> Result<String> {
+    let from = ctx.session.clone().unwrap_or_else(|| "user".to_string());
+    let ticket = send_ask(ctx, from, roster, agent, HANDOFF_PROMPT.to_string())?;
+    match poll_reply(&ctx.workspace_id, &ticket, Duration::from_secs(timeout_secs))? {
+        Some(reply) if reply.status == "answered" => Ok(reply.reply.unwrap_or_default()),
+        Some(reply) => bail!(
+            "{} did not hand off ({}): {}",
+            agent.id,
+            reply.status,
+            reply.reason.unwrap_or_default()
+        ),
+        None => bail!("no handoff from {} within {timeout_secs}s", agent.id),
+    }
 }
 
 pub fn cmd_ask(target: String, message: String, wait: bool, timeout_secs: u64) -> Result<()> {
@@ -114,9 +361,7 @@ pub fn cmd_ask(target: String, message: String, wait: bool, timeout_secs: u64) -
             comms::ENV_SESSION
         );
     };
-    let roster = comms::load_roster(&ctx.workspace_id)?;
-    let agent =
-        comms::resolve_target(&roster, &target, Some(from.as_str())).map_err(|e| anyhow!(e))?;
+    let (roster, agent) = resolve_peer(&ctx, &target)?;
     if !agent.supports_consult {
         bail!(
             "{} ({}) does not support consults; read its files or transcript instead",
@@ -128,29 +373,176 @@ pub fn cmd_ask(target: String, message: String, wait: bool, timeout_secs: u64) -
         bail!("empty message");
     }
 
+    let ticket = send_ask(&ctx, from, &roster, &agent, message)?;
+    if wait {
+        wait_for_reply(&ctx.workspace_id, &ticket, timeout_secs)
+    } else {
+        println!("collect with: workbench replies {ticket} --wait");
+        Ok(())
+    }
+}
+
+/// Queue one consult for `agent` and say so; the ticket to collect it by.
+fn send_ask(
+    ctx: &CallerCtx,
+    from: String,
+    roster: &Roster,
+    agent: &RosterAgent,
+    message: String,
+) -> Result<String> {
     let ticket = comms::new_ticket();
     let msg = InboxMessage::Ask {
         ticket: ticket.clone(),
         from,
         to: agent.id.clone(),
+        to_workspace: (roster.workspace_id != ctx.workspace_id).then(|| roster.workspace_id.clone()),
+        group: None,
         message,
         created_at: chrono::Utc::now().to_rfc3339(),
     };
     comms::write_inbox(&ctx.workspace_id, &msg)?;
     println!(
-        "queued consult {ticket} for {} ({}) — delivered when it is idle",
-        agent.id, agent.provider
+        "queued consult {ticket} for {} ({}{}) — delivered when it is idle",
+        agent.id,
+        agent.provider,
+        if roster.workspace_id == ctx.workspace_id {
+            String::new()
+        } else {
+            format!(" in {}", roster.workspace_name)
+        }
+    );
+    Ok(ticket)
+}
+
+/// Ask several peers the same question at once: the ones named in `to`, or
+/// with `all_peers` every live peer in this project that can take a consult.
+///
+/// Each copy is an ordinary consult with its own ticket; what ties them
+/// together is the group written first, which `workbench replies <group>`
+/// reads back. Naming the same peer twice asks it once.
+pub fn cmd_ask_group(
+    to: Vec<String>,
+    all_peers: bool,
+    message: String,
+    wait: bool,
+    quorum: Option<usize>,
+    timeout_secs: u64,
+) -> Result<()> {
+    let ctx = caller_ctx()?;
+    let Some(from) = ctx.session.clone() else {
+        bail!(
+            "`workbench ask` must run inside a workbench agent session ({} is not set)",
+            comms::ENV_SESSION
+        );
+    };
+    if message.trim().is_empty() {
+        bail!("empty message");
+    }
+
+    let mut peers: Vec<(Roster, RosterAgent)> = Vec::new();
+    if all_peers {
+        let roster = comms::load_roster(&ctx.workspace_id)?;
+        for agent in comms::broadcast_peers(&roster, Some(from.as_str())) {
+            peers.push((roster.clone(), agent.clone()));
+        }
+    } else {
+        for target in to.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            let (roster, agent) = resolve_peer(&ctx, target)?;
+            if !agent.supports_consult {
+                bail!(
+                    "{} ({}) does not support consults; leave it out of --to",
+                    agent.id,
+                    agent.provider
+                );
+            }
+            peers.push((roster, agent));
+        }
+    }
+    peers.sort_by(|a, b| (&a.0.workspace_id, &a.1.id).cmp(&(&b.0.workspace_id, &b.1.id)));
+    peers.dedup_by(|a, b| a.0.workspace_id == b.0.workspace_id && a.1.id == b.1.id);
+    if peers.is_empty() {
+        bail!("no live peer here can take a consult (see `workbench agents`)");
+    }
+    let quorum = group_quorum(quorum, peers.len())?;
+
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let group = comms::ConsultGroup {
+        ticket: comms::new_group_ticket(),
+        from: from.clone(),
+        question: message.clone(),
+        members: peers
+            .iter()
+            .map(|(roster, agent)| comms::GroupMember {
+                ticket: comms::new_ticket(),
+                agent: agent.id.clone(),
+                provider: agent.provider.clone(),
+                project: (roster.workspace_id != ctx.workspace_id)
+                    .then(|| roster.workspace_name.clone()),
+            })
+            .collect(),
+        created_at: created_at.clone(),
+    };
+    comms::write_group(&ctx.workspace_id, &group)?;
+    for ((roster, _), member) in peers.iter().zip(&group.members) {
+        comms::write_inbox(
+            &ctx.workspace_id,
+            &InboxMessage::Ask {
+                ticket: member.ticket.clone(),
+                from: from.clone(),
+                to: member.agent.clone(),
+                to_workspace: (roster.workspace_id != ctx.workspace_id)
+                    .then(|| roster.workspace_id.clone()),
+                group: Some(group.ticket.clone()),
+                message: message.clone(),
+                created_at: created_at.clone(),
+            },
+        )?;
+    }
+
+    println!(
+        "queued consult group {} for {} — each delivered when it is idle",
+        group.ticket,
+        group
+            .members
+            .iter()
+            .map(member_label)
+            .collect::<Vec<_>>()
+            .join(", ")
     );
     if wait {
-        wait_for_reply(&ctx.workspace_id, &ticket, timeout_secs)
+        wait_for_group(&ctx.workspace_id, &group, quorum, timeout_secs)
     } else {
-        println!("collect with: workbench replies {ticket} --wait");
+        println!("collect with: workbench replies {} --wait", group.ticket);
         Ok(())
     }
 }
 
-pub fn cmd_replies(ticket: String, wait: bool, timeout_secs: u64) -> Result<()> {
+fn group_quorum(quorum: Option<usize>, members: usize) -> Result<usize> {
+    match quorum {
+        None => Ok(members),
+        Some(n) if (1..=members).contains(&n) => Ok(n),
+        Some(n) => bail!("--quorum {n} is out of range: the group has {members} member(s)"),
+    }
+}
+
+fn member_label(member: &comms::GroupMember) -> String {
+    match &member.project {
+        Some(project) => format!("{}:{} ({})", project, member.agent, member.provider),
+        None => format!("{} ({})", member.agent, member.provider),
+    }
+}
+
+pub fn cmd_replies(ticket: String, wait: bool, quorum: Option<usize>, timeout_secs: u64) -> Result<()> {
     let ctx = caller_ctx()?;
+    if let Ok(group) = comms::load_group(&ctx.workspace_id, &ticket) {
+        let quorum = group_quorum(quorum, group.members.len())?;
+        if wait {
+            return wait_for_group(&ctx.workspace_id, &group, quorum, timeout_secs);
+        }
+        let replies = group_replies(&ctx.workspace_id, &group)?;
+        print_group(&group, &replies);
+        return Ok(());
+    }
     if wait {
         wait_for_reply(&ctx.workspace_id, &ticket, timeout_secs)
     } else {
@@ -191,6 +583,299 @@ pub fn cmd_alias(name: String) -> Result<()> {
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// File claims (see `crate::claims`)
+// ---------------------------------------------------------------------------
+
+/// Whether a session is still running, as the roster last saw it. A claim
+/// by an agent that has stopped protects nothing and is dropped. Without a
+/// roster every session counts as running: holding a stale claim a little
+/// longer is cheaper than dropping a real one.
+fn live_sessions(workspace_id: &str) -> impl Fn(&str) -> bool {
+    let roster = comms::load_roster(workspace_id).ok();
+    move |session: &str| {
+        roster.as_ref().is_none_or(|roster| {
+            roster
+                .agents
+                .iter()
+                .any(|a| a.id.eq_ignore_ascii_case(session) && a.status != "stopped")
+        })
+    }
+}
+
+pub fn cmd_claim(paths: Vec<PathBuf>) -> Result<()> {
+    let ctx = caller_ctx()?;
+    let registry = crate::claims::registry_path(&ctx.workspace_id)?;
+    if paths.is_empty() {
+        let claims = crate::claims::load(&registry).claims;
+        let live = live_sessions(&ctx.workspace_id);
+        let mut any = false;
+        for claim in claims.iter().filter(|claim| live(&claim.session)) {
+            any = true;
+            println!("{}  {}", claim.session, claim.path.display());
+        }
+        if !any {
+            println!("no claims");
+        }
+        return Ok(());
+    }
+    let Some(session) = ctx.session.clone() else {
+        bail!(
+            "`workbench claim` must run inside a workbench agent session ({} is not set)",
+            comms::ENV_SESSION
+        );
+    };
+    let cwd = std::env::current_dir()?;
+    let paths: Vec<PathBuf> = paths
+        .iter()
+        .map(|path| crate::claims::absolute(&cwd, path))
+        .collect();
+    match crate::claims::claim(&registry, &session, &paths, live_sessions(&ctx.workspace_id))? {
+        crate::claims::Outcome::Claimed(paths) => {
+            for path in paths {
+                println!("claimed {}", path.display());
+            }
+        }
+        crate::claims::Outcome::Held(held) => {
+            eprintln!("nothing claimed — already held:");
+            for claim in held {
+                eprintln!("  {}  {}", claim.session, claim.path.display());
+            }
+            std::process::exit(EXIT_CONFLICT);
+        }
+    }
+    Ok(())
+}
+
+pub fn cmd_release(paths: Vec<PathBuf>) -> Result<()> {
+    let ctx = caller_ctx()?;
+    let Some(session) = ctx.session.clone() else {
+        bail!(
+            "`workbench release` must run inside a workbench agent session ({} is not set)",
+            comms::ENV_SESSION
+        );
+    };
+    let cwd = std::env::current_dir()?;
+    let paths: Vec<PathBuf> = paths
+        .iter()
+        .map(|path| crate::claims::absolute(&cwd, path))
+        .collect();
+    let registry = crate::claims::registry_path(&ctx.workspace_id)?;
+    let released = crate::claims::release(&registry, &session, &paths)?;
+    if released.is_empty() {
+        println!("nothing to release");
+    }
+    for path in released {
+        println!("released {}", path.display());
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Shared notes (see `crate::notes`)
+// ---------------------------------------------------------------------------
+
+/// Exit code for a `note put --if-version` that lost the race. Distinct from
+/// a failure for the same reason `EXIT_TIMEOUT` is: the script's next move
+/// (read again, or back off) is different.
+pub const EXIT_CONFLICT: i32 = 4;
+
+pub fn cmd_note_put(key: String, value: String, if_version: Option<u64>) -> Result<()> {
+    let ctx = caller_ctx()?;
+    let value = if value == "-" {
+        use std::io::Read;
+        let mut stdin = String::new();
+        std::io::stdin().read_to_string(&mut stdin)?;
+        stdin.trim_end().to_string()
+    } else {
+        value
+    };
+    let dir = crate::notes::notes_dir(&ctx.workspace_id)?;
+    match crate::notes::put(&dir, &key, &value, ctx.session.as_deref(), if_version)? {
+        crate::notes::Put::Written(note) => println!("{} = v{}", note.key, note.version),
+        crate::notes::Put::Conflict { current } => {
+            eprintln!(
+                "{key} is at v{current}, not v{} — read it again before writing",
+                if_version.unwrap_or_default()
+            );
+            std::process::exit(EXIT_CONFLICT);
+        }
+    }
+    Ok(())
+}
+
+pub fn cmd_note_get(key: String, json: bool) -> Result<()> {
+    crate::notes::check_key(&key)?;
+    let ctx = caller_ctx()?;
+    let dir = crate::notes::notes_dir(&ctx.workspace_id)?;
+    let Some(note) = crate::notes::get(&dir, &key) else {
+        bail!("no note {key} (see `workbench note list`)");
+    };
+    print_note(&note, json)
+}
+
+pub fn cmd_note_list() -> Result<()> {
+    let ctx = caller_ctx()?;
+    let notes = crate::notes::list(&crate::notes::notes_dir(&ctx.workspace_id)?);
+    if notes.is_empty() {
+        println!("no notes");
+    }
+    for note in &notes {
+        let first_line = note.value.lines().next().unwrap_or("");
+        println!(
+            "{:<24} v{:<3} {:<9} {}",
+            note.key,
+            note.version,
+            note.author.as_deref().unwrap_or("-"),
+            first_line
+        );
+    }
+    Ok(())
+}
+
+/// Block until `key` is past version `since` — by default the version it is
+/// at now, so the command means "tell me when this next changes". A key that
+/// does not exist yet is at 0, and its first write ends the wait.
+pub fn cmd_note_watch(key: String, since: Option<u64>, timeout_secs: u64, json: bool) -> Result<()> {
+    crate::notes::check_key(&key)?;
+    let ctx = caller_ctx()?;
+    let dir = crate::notes::notes_dir(&ctx.workspace_id)?;
+    let version = |dir: &std::path::Path| crate::notes::get(dir, &key).map_or(0, |n| n.version);
+    let since = since.unwrap_or_else(|| version(&dir));
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(timeout_secs) {
+        if let Some(note) = crate::notes::get(&dir, &key).filter(|n| n.version > since) {
+            return print_note(&note, json);
+        }
+        std::thread::sleep(Duration::from_millis(500));
+    }
+    eprintln!("{key} did not change past v{since} within {timeout_secs}s");
+    std::process::exit(EXIT_TIMEOUT);
+}
+
+fn print_note(note: &crate::notes::Note, json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(note)?);
+    } else {
+        println!("{}", note.value);
+    }
+    Ok(())
+}
+
+/// `workbench grep`'s pattern and window, read before anything is searched
+/// so a typo fails fast.
+pub fn grep_query(
+    pattern: &str,
+    ignore_case: bool,
+    since: Option<&str>,
+    context: usize,
+) -> Result<crate::grep::Query> {
+    Ok(crate::grep::Query {
+        pattern: crate::grep::pattern(pattern, ignore_case).map_err(|e| anyhow!(e))?,
+        since: since
+            .map(|since| crate::grep::parse_since(since, chrono::Utc::now()))
+            .transpose()
+            .map_err(|e| anyhow!(e))?,
+        context,
+    })
+}
+
+/// Search what the agents have said (see `crate::grep`). The caller's own
+/// project by default; `--project` names another, or `all` for every one
+/// reachable — which, as for a consult, means both ends opted in to
+/// `cross_project`.
+pub fn cmd_grep(
+    query: crate::grep::Query,
+    project: Option<String>,
+    agent: Option<String>,
+    max: usize,
+    json: bool,
+) -> Result<()> {
+    let ctx = caller_ctx()?;
+    let own = comms::load_roster(&ctx.workspace_id)?;
+    let rosters = match project.as_deref() {
+        None => vec![own],
+        Some(name)
+            if name.eq_ignore_ascii_case(&own.workspace_name)
+                || own.workspace_id.starts_with(&name.to_lowercase()) =>
+        {
+            vec![own]
+        }
+        Some(name) => {
+            if !own.cross_project {
+                bail!(
+                    "project {} does not take part in cross-project consults \
+                     (add it to `cross_project` in user_config.toml)",
+                    own.workspace_name
+                );
+            }
+            let all = comms::load_all_rosters()?;
+            if name == "all" {
+                all.into_iter()
+                    .filter(|r| r.cross_project || r.workspace_id == own.workspace_id)
+                    .collect()
+            } else {
+                vec![comms::resolve_project(&all, name).map_err(|e| anyhow!(e))?.clone()]
+            }
+        }
+    };
+
+    let hits = crate::grep::search(&rosters, agent.as_deref(), &query).map_err(|e| anyhow!(e))?;
+    // The newest are the ones kept: a search for what was said recently is
+    // the common one, and `--since` narrows the rest.
+    let older = hits.len().saturating_sub(max);
+    let shown = &hits[older..];
+    if json {
+        println!("{}", serde_json::to_string_pretty(shown)?);
+        return Ok(());
+    }
+    if hits.is_empty() {
+        println!("no matches");
+        return Ok(());
+    }
+    if older > 0 {
+        println!("… {older} older matches not shown (raise --max, or narrow with --since)\n");
+    }
+    let many_projects = rosters.len() > 1;
+    for (i, hit) in shown.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_hit(hit, many_projects);
+    }
+    Ok(())
+}
+
+fn print_hit(hit: &crate::grep::Hit, with_project: bool) {
+    use crate::grep::Origin;
+    use crate::remote::Role;
+    let project = if with_project {
+        format!("{}:", hit.project)
+    } else {
+        String::new()
+    };
+    let alias = hit
+        .alias
+        .as_deref()
+        .map(|alias| format!(" ({alias})"))
+        .unwrap_or_default();
+    let at = hit
+        .at
+        .map(|at| at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let said = match (hit.origin, hit.role) {
+        (Origin::Transcript, _) => "transcript, exported",
+        (Origin::Journal, Some(Role::You)) => "prompt",
+        (Origin::Journal, Some(Role::Tool)) => "tool",
+        (Origin::Journal, _) => "agent",
+    };
+    println!("{project}{}{alias} {}  {said} {at}", hit.agent, hit.provider);
+    for line in &hit.lines {
+        let mark = if line.matched { '>' } else { '|' };
+        println!("  {mark} {}", line.text);
+    }
+}
+
 fn read_reply(workspace_id: &str, ticket: &str) -> Result<Option<Reply>> {
     let path = comms::reply_path(workspace_id, ticket)?;
     match std::fs::read(&path) {
@@ -223,6 +908,61 @@ fn wait_for_reply(workspace_id: &str, ticket: &str, timeout_secs: u64) -> Result
     Ok(())
 }
 
+fn group_replies(workspace_id: &str, group: &comms::ConsultGroup) -> Result<Vec<Option<Reply>>> {
+    group
+        .members
+        .iter()
+        .map(|member| read_reply(workspace_id, &member.ticket))
+        .collect()
+}
+
+/// Wait until the group is settled (see `comms::group_settled`), then print
+/// every reply in. Falling short of the quorum — by refusals or the clock —
+/// is a failure, but what did arrive is printed first: two answers out of
+/// three are still worth reading.
+fn wait_for_group(
+    workspace_id: &str,
+    group: &comms::ConsultGroup,
+    quorum: usize,
+    timeout_secs: u64,
+) -> Result<()> {
+    let start = Instant::now();
+    let replies = loop {
+        let replies = group_replies(workspace_id, group)?;
+        if comms::group_settled(&replies, quorum) || start.elapsed() > Duration::from_secs(timeout_secs) {
+            break replies;
+        }
+        std::thread::sleep(Duration::from_millis(500));
+    };
+    print_group(group, &replies);
+    let answered = replies
+        .iter()
+        .flatten()
+        .filter(|reply| reply.status == "answered")
+        .count();
+    if answered < quorum {
+        bail!(
+            "{answered} of {} answered, short of the {quorum} asked for; \
+             check later with: workbench replies {}",
+            group.members.len(),
+            group.ticket
+        );
+    }
+    Ok(())
+}
+
+fn print_group(group: &comms::ConsultGroup, replies: &[Option<Reply>]) {
+    for (i, (member, reply)) in group.members.iter().zip(replies).enumerate() {
+        if i > 0 {
+            println!("\n---\n");
+        }
+        match reply {
+            Some(reply) => print_reply(reply),
+            None => println!("consult {} pending: {}", member.ticket, member_label(member)),
+        }
+    }
+}
+
 fn print_reply(reply: &Reply) {
     match reply.status.as_str() {
         "answered" => {
@@ -285,6 +1025,17 @@ pub fn cmd_hook(event: Option<&str>) {
     if let Some(status) = crate::agent_status::interpret(event, payload.as_ref()) {
         let _ = crate::agent_status::record(&workspace_id, &session, &status);
     }
+
+    // An edit that landed in another agent's claim. Recorded, not blocked:
+    // the edit has already happened, and the TUI shows it on this row.
+    if event == "PostToolUse" {
+        if let (Some(path), Ok(registry)) = (
+            payload.as_ref().and_then(crate::claims::edited_path),
+            crate::claims::registry_path(&workspace_id),
+        ) {
+            let _ = crate::claims::note_edit(&registry, &session, &path);
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -416,3 +1167,190 @@ pub fn cmd_wait(
     );
     std::process::exit(EXIT_TIMEOUT);
 }
+
+// ---------------------------------------------------------------------------
+// Raw calls (over the control socket)
+// ---------------------------------------------------------------------------
+
+/// Make one control-socket call and print its answer. With `follow`, keep
+/// going and print each event as it arrives, one JSON object per line, until
+/// workbench goes away — or the reader does.
+///
+/// `events.subscribe` always follows: a subscription nobody reads is not
+/// worth making. For any other method the subscription is taken *before* the
+/// call, for the reason `wait` gives: the events a call causes can arrive
+/// faster than a second request could ask for them.
+pub fn cmd_ctl(method: String, params: Vec<String>, follow: bool) -> Result<()> {
+    use std::io::Write;
+
+    let params = crate::control::parse_params(&params)?;
+    let mut client = crate::control::Client::connect()?;
+
+    let answer = if method == "events.subscribe" {
+        client.subscribe_to(params)?
+    } else {
+        if follow {
+            client.subscribe()?;
+        }
+        client.call(&method, params)?
+    };
+    println!("{}", serde_json::to_string_pretty(&answer)?);
+    if !follow && method != "events.subscribe" {
+        return Ok(());
+    }
+
+    // The first events out are any that arrived before the answer did —
+    // the ones the call itself set off, and the reason to follow at all.
+    let mut out = std::io::stdout();
+    loop {
+        let Some(event) = client.next_event()? else {
+            continue;
+        };
+        // `| head` closing the pipe is a reader that has had enough, not an
+        // error worth a panic.
+        if writeln!(out, "{event}").is_err() {
+            return Ok(());
+        }
+    }
+}
+
+/// `workbench worktrees prune`: what `worktree_gc` finds in each project,
+/// each removed only on a `y` — or all of them with `--yes`. Commits on a
+/// branch that HEAD doesn't have are a second question, or with `--yes`
+/// kept unless `--drop-unmerged` says otherwise; uncommitted changes in a
+/// checkout the same, with `--drop-dirty`. Reads the saved state, so
+/// it runs with or without the TUI; a worktree the TUI is still setting up
+/// is inside `worktree_gc::GRACE` and left alone.
+pub fn cmd_worktrees_prune(
+    dry_run: bool,
+    yes: bool,
+    drop_unmerged: bool,
+    drop_dirty: bool,
+    project: Option<String>,
+) -> Result<()> {
+    use crate::worktree_gc::{self, Known};
+    use std::io::{BufRead, Write};
+
+    let state = crate::persistence::load()?;
+    let known = Known::new(&state.workspaces, &state.sessions);
+    let workspaces: Vec<_> = state
+        .workspaces
+        .iter()
+        .filter(|ws| {
+            project.as_deref().is_none_or(|name| {
+                ws.name.eq_ignore_ascii_case(name) || ws.id.to_string().starts_with(name)
+            })
+        })
+        .collect();
+    if let Some(name) = project.as_deref().filter(|_| workspaces.is_empty()) {
+        bail!("no project named {name}");
+    }
+
+    let (mut found, mut removed) = (0, 0);
+    let mut stdin = std::io::stdin().lock();
+    let mut ask = |question: String| -> Result<bool> {
+        print!("    {question} [y/N] ");
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        stdin.read_line(&mut answer)?;
+        Ok(answer.trim().eq_ignore_ascii_case("y"))
+    };
+    for ws in workspaces {
+        if !crate::git::is_git_repo(&ws.path) {
+            continue;
+        }
+        let orphans = match worktree_gc::find_orphans(&ws.path, &known, worktree_gc::GRACE) {
+            Ok(orphans) => orphans,
+            Err(err) => {
+                eprintln!("{}: {err:#}", ws.name);
+                continue;
+            }
+        };
+        if orphans.is_empty() {
+            continue;
+        }
+        println!("{} ({})", ws.name, ws.path.display());
+        for orphan in &orphans {
+            found += 1;
+            println!("  {}", orphan.title(&ws.path));
+            for line in orphan.details() {
+                println!("      {line}");
+            }
+            if dry_run {
+                continue;
+            }
+            if !yes && !ask("remove?".to_string())? {
+                continue;
+            }
+            // Removal is forced, and uncommitted changes are in no commit
+            // anywhere: losing them is asked about on its own, as commits are.
+            if orphan.dirty && orphan.kind == worktree_gc::OrphanKind::Worktree {
+                let drop_changes = if yes {
+                    drop_dirty
+                } else {
+                    ask("it has uncommitted changes — lose them?".to_string())?
+                };
+                if !drop_changes {
+                    println!("    kept, with its uncommitted changes");
+                    continue;
+                }
+            }
+            let unmerged = orphan.branch.as_deref().filter(|_| orphan.ahead > 0);
+            let drop_commits = match unmerged {
+                Some(branch) if !yes => ask(format!(
+                    "{branch} has {} commit(s) HEAD doesn't — delete them too?",
+                    orphan.ahead
+                ))?,
+                _ => drop_unmerged,
+            };
+            let kept = unmerged.filter(|_| !drop_commits);
+            if kept.is_some() && orphan.kind == worktree_gc::OrphanKind::Branch {
+                println!("    kept");
+                continue;
+            }
+            match worktree_gc::remove(&ws.path, orphan, drop_commits) {
+                Ok(()) => {
+                    removed += 1;
+                    match kept {
+                        Some(branch) => println!("    removed; {branch} kept with its commits"),
+                        None => println!("    removed"),
+                    }
+                }
+                Err(err) => eprintln!("    could not remove it: {err:#}"),
+            }
+        }
+    }
+
+    match (found, dry_run) {
+        (0, _) => println!("no orphaned worktrees or branches"),
+        (n, true) => println!("\n{n} orphaned; run without --dry-run to remove them"),
+        (n, false) => println!("\n{removed} of {n} removed"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A key is a file name under the notes directory, so reading one is
+    /// checked the same as writing one — before anything else is looked up.
+    #[test]
+    fn note_get_and_watch_refuse_a_key_that_leaves_the_notes_directory() {
+        let get = cmd_note_get("../x".into(), false).unwrap_err();
+        let watch = cmd_note_watch("../x".into(), None, 0, false).unwrap_err();
+        for err in [get, watch] {
+            assert!(err.to_string().starts_with("note keys are"), "{err}");
+        }
+    }
+
+    /// An unknown provider is refused before anything is asked of the
+    /// agent or its queue — there is no socket or roster in a test, so
+    /// getting as far as either would fail differently.
+    #[test]
+    fn a_handoff_to_an_unknown_provider_is_refused_first() {
+        let err = cmd_handoff("aaaa1111".into(), Some("gemini".into()), false, false, false, 1)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "no provider gemini — claude or codex");
+    }
+}
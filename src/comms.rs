@@ -10,17 +10,23 @@
 //!   transcripts/<provider>-<shortid>.md   exported on each idle transition
 //!   inbox/<ticket>.json  messages written by the `workbench` CLI
 //!   replies/<ticket>.json  consult outcomes written by the TUI
+//!   groups/<group>.json  the consults one `ask --to` fanned out
+//!   pending.json         consults the TUI has yet to settle
+//!   notes/<key>.json     shared notes (see `crate::notes`)
+//!   claims.json          files agents have claimed (see `crate::claims`)
 //! ```
 //!
 //! The TUI is the only writer of roster/transcripts/replies and the only
 //! reader of the inbox; the CLI (run by agents inside their PTYs) does the
-//! inverse. Self-identity travels via the `WORKBENCH_SESSION` /
+//! inverse. Notes and claims are written by the CLI alone, under a
+//! `FileLock`, and only read by the TUI. Self-identity travels via the `WORKBENCH_SESSION` /
 //! `WORKBENCH_WORKSPACE` env vars injected at PTY spawn.
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 pub const ENV_SESSION: &str = "WORKBENCH_SESSION";
 pub const ENV_WORKSPACE: &str = "WORKBENCH_WORKSPACE";
@@ -43,7 +49,7 @@ pub fn workspace_dir(workspace_id: &str) -> Result<PathBuf> {
 
 pub fn ensure_workspace_dirs(workspace_id: &str) -> Result<PathBuf> {
     let dir = workspace_dir(workspace_id)?;
-    for sub in ["transcripts", "inbox", "replies"] {
+    for sub in ["transcripts", "inbox", "replies", "groups"] {
         fs::create_dir_all(dir.join(sub))?;
     }
     Ok(dir)
@@ -57,6 +63,53 @@ pub fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
     Ok(())
 }
 
+/// A lock file held across one read-modify-write, removed on drop.
+///
+/// `write_atomic` keeps a reader from seeing half a file, but two writers
+/// can still both read the old one and the second write wins. Where that
+/// matters — a note written with `--if-version`, a file claim — the writer
+/// takes this first. The CLI processes that write are short-lived, so a lock
+/// older than `STALE` belongs to one that died holding it and is broken.
+pub struct FileLock(PathBuf);
+
+impl FileLock {
+    const STALE: Duration = Duration::from_secs(10);
+    const WAIT: Duration = Duration::from_secs(3);
+
+    pub fn take(path: &Path) -> Result<FileLock> {
+        let start = Instant::now();
+        loop {
+            match fs::OpenOptions::new().write(true).create_new(true).open(path) {
+                Ok(_) => return Ok(FileLock(path.to_path_buf())),
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let stale = fs::metadata(path)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|at| at.elapsed().ok())
+                        .is_some_and(|age| age > Self::STALE);
+                    if stale {
+                        let _ = fs::remove_file(path);
+                        continue;
+                    }
+                    if start.elapsed() > Self::WAIT {
+                        bail!("locked by another writer ({})", path.display());
+                    }
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+                Err(err) => {
+                    return Err(err).with_context(|| format!("cannot lock {}", path.display()))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Roster
 // ---------------------------------------------------------------------------
@@ -79,6 +132,23 @@ pub struct RosterAgent {
     pub transcript: Option<String>,
     /// Whether `workbench ask` can target this agent (transcript-capable).
     pub supports_consult: bool,
+    /// The agent's own journal, when workbench has found one it can read.
+    #[serde(default)]
+    pub journal: Option<RosterJournal>,
+}
+
+/// Where an agent's journal is, for a reader outside the TUI (`workbench
+/// grep`). The TUI finds it by tracking the agent (see `agent_tasks`); a
+/// one-shot CLI has no way to repeat that, so it is written down here.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RosterJournal {
+    /// `Provider::name` — which format the journal is in.
+    pub provider: String,
+    /// A JSONL file, or the SQLite store holding the conversation.
+    pub path: String,
+    /// The conversation's id inside a SQLite store; none for a file.
+    #[serde(default)]
+    pub session: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -88,6 +158,29 @@ pub struct Roster {
     pub workspace_path: String,
     pub updated_at: String,
     pub agents: Vec<RosterAgent>,
+    /// This project takes part in cross-project consults (`cross_project` in
+    /// the user config). Carried here because the CLI cannot read the TUI's
+    /// config; the TUI checks it again when the consult arrives.
+    #[serde(default)]
+    pub cross_project: bool,
+    /// The workspace's shared notes (see `crate::notes`), without their
+    /// values: enough for a newcomer to see what has been claimed or
+    /// decided, and to `workbench note get` what it needs.
+    #[serde(default)]
+    pub notes: Vec<RosterNote>,
+    /// Paths the workspace's running agents have claimed (see
+    /// `crate::claims`).
+    #[serde(default)]
+    pub claims: Vec<crate::claims::Claim>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RosterNote {
+    pub key: String,
+    pub version: u64,
+    #[serde(default)]
+    pub author: Option<String>,
+    pub updated_at: String,
 }
 
 pub fn roster_path(workspace_id: &str) -> Result<PathBuf> {
@@ -105,6 +198,21 @@ pub fn load_roster(workspace_id: &str) -> Result<Roster> {
     Ok(serde_json::from_slice(&bytes)?)
 }
 
+/// Every roster under the comms root, in no particular order. A directory
+/// whose roster is missing or unreadable belongs to a workspace that is not
+/// open, and is skipped.
+pub fn load_all_rosters() -> Result<Vec<Roster>> {
+    let root = comms_root()?;
+    let mut rosters = Vec::new();
+    for entry in fs::read_dir(&root).with_context(|| format!("no comms data at {}", root.display()))? {
+        let ws_id = entry?.file_name().to_string_lossy().to_string();
+        if let Ok(roster) = load_roster(&ws_id) {
+            rosters.push(roster);
+        }
+    }
+    Ok(rosters)
+}
+
 /// Locate the workspace whose path contains `cwd` (used when the CLI runs
 /// outside a workbench PTY and `WORKBENCH_WORKSPACE` is unset). Picks the
 /// longest matching workspace path so nested workspaces resolve correctly.
@@ -195,6 +303,68 @@ pub fn resolve_target<'a>(
     }
 }
 
+/// Split `project:agent` into its halves. No colon means the caller's own
+/// project; neither ids, aliases nor provider names can contain one, so the
+/// form cannot be misread.
+pub fn split_address(query: &str) -> (Option<&str>, &str) {
+    match query.split_once(':') {
+        Some((project, agent)) => (Some(project), agent),
+        None => (None, query),
+    }
+}
+
+/// Resolve the project half of a `project:agent` address: an exact
+/// workspace id, then a name, then an id prefix — with the same refusal of
+/// ambiguity as agents, since two projects called `api` is not rare.
+/// Projects that have not opted in to cross-project consults are not
+/// candidates at all, and are named as such when nothing else matches.
+pub fn resolve_project<'a>(rosters: &'a [Roster], query: &str) -> Result<&'a Roster, String> {
+    let q = query.to_lowercase();
+    let shared: Vec<&Roster> = rosters.iter().filter(|r| r.cross_project).collect();
+
+    if let Some(roster) = shared.iter().find(|r| r.workspace_id.to_lowercase() == q) {
+        return Ok(roster);
+    }
+    let mut matches: Vec<&Roster> = shared
+        .iter()
+        .filter(|r| r.workspace_name.to_lowercase() == q)
+        .copied()
+        .collect();
+    if matches.is_empty() {
+        matches = shared
+            .iter()
+            .filter(|r| r.workspace_id.to_lowercase().starts_with(&q))
+            .copied()
+            .collect();
+    }
+    let describe = |rosters: &[&Roster]| {
+        rosters
+            .iter()
+            .map(|r| format!("  {}  {}  ({})", &r.workspace_id[..8.min(r.workspace_id.len())], r.workspace_name, r.workspace_path))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    match matches.len() {
+        1 => Ok(matches[0]),
+        0 if rosters
+            .iter()
+            .any(|r| !r.cross_project && r.workspace_name.to_lowercase() == q) =>
+        {
+            Err(format!(
+                "project '{query}' does not take cross-project consults (add it to `cross_project` in user_config.toml)"
+            ))
+        }
+        0 => Err(format!(
+            "no project matches '{query}'. Projects taking cross-project consults:\n{}",
+            describe(&shared)
+        )),
+        _ => Err(format!(
+            "'{query}' is ambiguous — address the project by id:\n{}",
+            describe(&matches)
+        )),
+    }
+}
+
 pub fn describe_agents(agents: &[&RosterAgent]) -> String {
     agents
         .iter()
@@ -227,6 +397,14 @@ pub enum InboxMessage {
         ticket: String,
         from: String,
         to: String,
+        /// The target's workspace, when it is not the asker's own (a
+        /// `project:agent` address).
+        #[serde(default)]
+        to_workspace: Option<String>,
+        /// The group ticket, when this is one of several copies of a question
+        /// asked with `--to` or `--all-peers`.
+        #[serde(default)]
+        group: Option<String>,
         message: String,
         created_at: String,
     },
@@ -252,12 +430,28 @@ pub struct Reply {
     pub reason: Option<String>,
 }
 
+/// 1-24 characters of `[a-zA-Z0-9_-]`: something a peer can type in a
+/// command line without quoting.
+pub fn alias_is_valid(alias: &str) -> bool {
+    !alias.is_empty()
+        && alias.len() <= 24
+        && alias
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
 pub fn new_ticket() -> String {
     use rand::Rng;
     let n: u32 = rand::thread_rng().gen_range(0x1000..0xFFFF_FFFF);
     format!("c{n:x}")
 }
 
+/// A group ticket reads differently from a consult's (`g…` against `c…`), so
+/// `workbench replies` can tell which one it was handed without guessing.
+pub fn new_group_ticket() -> String {
+    format!("g{}", &new_ticket()[1..])
+}
+
 pub fn write_inbox(workspace_id: &str, msg: &InboxMessage) -> Result<PathBuf> {
     let ticket = match msg {
         InboxMessage::Ask { ticket, .. } => ticket,
@@ -281,6 +475,83 @@ pub fn write_reply(workspace_id: &str, reply: &Reply) -> Result<()> {
     write_atomic(&path, serde_json::to_string_pretty(reply)?.as_bytes())
 }
 
+/// Consults the TUI accepted from this workspace's inbox and has not settled
+/// yet (see `app::comms_tick`), kept beside the inbox they came from so a
+/// restart does not leave their askers waiting on a reply nobody will write.
+pub fn pending_path(workspace_id: &str) -> Result<PathBuf> {
+    Ok(workspace_dir(workspace_id)?.join("pending.json"))
+}
+
+// ---------------------------------------------------------------------------
+// Consult groups
+// ---------------------------------------------------------------------------
+
+/// One question fanned out to several peers. Each copy is an ordinary consult
+/// with its own ticket and its own reply; the group is only the list of them,
+/// written by the CLI before any copy is queued so `workbench replies <group>`
+/// never finds a ticket it cannot account for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsultGroup {
+    pub ticket: String,
+    pub from: String,
+    pub question: String,
+    pub members: Vec<GroupMember>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupMember {
+    pub ticket: String,
+    pub agent: String,
+    pub provider: String,
+    /// The member's project name, when it is not the asker's.
+    #[serde(default)]
+    pub project: Option<String>,
+}
+
+pub fn group_path(workspace_id: &str, group: &str) -> Result<PathBuf> {
+    Ok(workspace_dir(workspace_id)?
+        .join("groups")
+        .join(format!("{group}.json")))
+}
+
+pub fn write_group(workspace_id: &str, group: &ConsultGroup) -> Result<()> {
+    let dir = ensure_workspace_dirs(workspace_id)?;
+    let path = dir.join("groups").join(format!("{}.json", group.ticket));
+    write_atomic(&path, serde_json::to_string_pretty(group)?.as_bytes())
+}
+
+pub fn load_group(workspace_id: &str, group: &str) -> Result<ConsultGroup> {
+    let path = group_path(workspace_id, group)?;
+    let bytes = fs::read(&path).with_context(|| format!("no consult group {group}"))?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Whether waiting on a group can stop: `quorum` members have answered, or
+/// so many have settled otherwise (refused, timed out) that the quorum can no
+/// longer be reached. `replies` holds one entry per member, `None` while that
+/// member is still out. A refusal settles its member — it is never going to
+/// answer — but only an answer counts toward the quorum.
+pub fn group_settled(replies: &[Option<Reply>], quorum: usize) -> bool {
+    let answered = replies
+        .iter()
+        .flatten()
+        .filter(|reply| reply.status == "answered")
+        .count();
+    let outstanding = replies.iter().filter(|reply| reply.is_none()).count();
+    answered >= quorum || answered + outstanding < quorum
+}
+
+/// The peers `--all-peers` asks: every live, consultable agent in the roster
+/// except the asker.
+pub fn broadcast_peers<'a>(roster: &'a Roster, self_id: Option<&str>) -> Vec<&'a RosterAgent> {
+    roster
+        .agents
+        .iter()
+        .filter(|a| a.status != "stopped" && a.supports_consult && Some(a.id.as_str()) != self_id)
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Transcript helpers
 // ---------------------------------------------------------------------------
@@ -315,9 +586,14 @@ The `workbench` CLI lets you discover and communicate with peers:
 
 - `workbench agents` — list agent sessions here (id, provider, alias, branch, idle/busy)
 - `workbench transcript <id|alias> --lines 200` — read a peer's recent conversation (exported each time it goes idle)
+- `workbench grep <pattern> --since 1d` — search what every agent here has said, to find who already worked on something before asking or redoing it (`--agent`, `-i`, `-C 5`)
 - `workbench ask <id|alias> "question" --wait` — deliver a question to a live peer and collect its answer (or collect later: `workbench replies <ticket> --wait`)
+- `workbench ask --to <a>,<b> "question" --wait` — ask several peers at once (or `--all-peers`); replies collect under one group ticket, and `--quorum N` returns once N have answered
 - `workbench handoff <id|alias> --wait` — ask a peer for a structured summary of its work (done/remaining/decisions/gotchas) before taking over or building on it
+- `workbench handoff <id|alias> --to <claude|codex> [--worktree] [--stop]` — only when the user asks to move a peer's work to a new agent: starts the successor seeded with the summary and the peer's queued TODOs
 - `workbench alias <name>` — set your own alias
+- `workbench claim <paths>` — claim the files or directories you are about to edit; `workbench release` when done. In a checkout shared with other agents (no worktree), claim BEFORE editing, and if a path is already claimed, work elsewhere or ask its holder — never edit it anyway
+- `workbench note put|get|list|watch <key>` — shared notes for this workspace: record what you have claimed, decided or finished where peers will look, without interrupting them (`put <key> <value> --if-version 0` claims a key only if nobody has)
 - `workbench wait <id|alias>` — block until a peer stops working (add `--json` for a parseable line, `--state idle` to insist it is not merely blocked)
 
 Address peers by id or alias. A provider name like `codex` also works when
@@ -325,6 +601,10 @@ only one such agent runs in this project — and you are never a match for
 your own provider, so `codex` from a codex agent means the other one. What
 is still ambiguous is refused with the candidates named, never guessed at.
 
+Agents in other projects are out of reach unless the user has opened both
+projects to each other; then `workbench agents --all` lists them and
+`project:agent` (e.g. `backend:reviewer`) addresses one.
+
 A consult costs the peer a full model turn — consult when the user asks you
 to or you are genuinely blocked, not by default.
 
@@ -339,7 +619,9 @@ Match the user's request to what is known to work; if it fits, just do it:
   author (its self-summary carries decisions a transcript reader must
   guess); read `transcript` only when the author is stopped or unresponsive.
 - CONSULTING: one broad question beats many narrow ones; a cross-provider
-  opinion (claude<->codex) is worth more than a same-provider one.
+  opinion (claude<->codex) is worth more than a same-provider one. For a
+  second opinion from each, ask them together with `--to` rather than in
+  turn, and weigh the answers yourself.
 - WAITING on a peer to finish: `workbench wait <id|alias>`, which returns the
   moment it stops working. Do not poll `workbench agents` in a loop, and do
   not sit in `sleep` guessing how long a turn takes.
@@ -496,6 +778,7 @@ mod tests {
             status: status.into(),
             transcript: None,
             supports_consult: true,
+            journal: None,
         }
     }
 
@@ -506,6 +789,9 @@ mod tests {
             workspace_path: "/tmp/w".into(),
             updated_at: "now".into(),
             agents,
+            cross_project: false,
+            notes: Vec::new(),
+            claims: Vec::new(),
         }
     }
 
@@ -658,6 +944,76 @@ mod tests {
         assert!(resolve_target(&r, "aaaa1111", Some("aaaa1111")).is_err());
     }
 
+    #[test]
+    fn a_project_is_addressed_by_name_or_id_when_it_has_opted_in() {
+        let project = |id: &str, name: &str, shared: bool| Roster {
+            workspace_id: id.into(),
+            workspace_name: name.into(),
+            cross_project: shared,
+            ..roster(Vec::new())
+        };
+        let rosters = vec![
+            project("1111aaaa-0000", "backend", true),
+            project("2222bbbb-0000", "api", true),
+            project("3333cccc-0000", "api", true),
+            project("4444dddd-0000", "secrets", false),
+        ];
+
+        assert_eq!(split_address("backend:reviewer"), (Some("backend"), "reviewer"));
+        assert_eq!(split_address("reviewer"), (None, "reviewer"));
+
+        assert_eq!(resolve_project(&rosters, "Backend").unwrap().workspace_id, "1111aaaa-0000");
+        assert_eq!(resolve_project(&rosters, "2222").unwrap().workspace_id, "2222bbbb-0000");
+        let err = resolve_project(&rosters, "api").unwrap_err();
+        assert!(err.contains("ambiguous") && err.contains("2222bbbb") && err.contains("3333cccc"));
+        // A project that has not opted in is never a target, and says why.
+        let err = resolve_project(&rosters, "secrets").unwrap_err();
+        assert!(err.contains("does not take cross-project consults"), "{err}");
+        assert!(resolve_project(&rosters, "4444").is_err());
+    }
+
+    /// A group waits for its quorum of *answers*; a peer that refused is
+    /// settled but does not count, and once the quorum is out of reach there
+    /// is nothing left worth waiting for.
+    #[test]
+    fn a_group_is_settled_by_its_quorum_of_answers() {
+        let reply = |status: &str| {
+            Some(Reply {
+                ticket: "c1".into(),
+                status: status.into(),
+                from: "a".into(),
+                to: "b".into(),
+                question: String::new(),
+                reply: None,
+                reason: None,
+            })
+        };
+
+        assert!(!group_settled(&[reply("answered"), None, None], 3));
+        assert!(group_settled(&[reply("answered"), None, reply("answered")], 2));
+        assert!(!group_settled(&[reply("refused"), None, None], 2));
+        assert!(group_settled(&[reply("refused"), reply("timeout"), None], 2));
+        assert!(group_settled(&[reply("refused"), reply("answered")], 2));
+    }
+
+    #[test]
+    fn a_broadcast_skips_the_asker_and_agents_that_cannot_answer() {
+        let mut roster = roster(vec![
+            agent("aaaa1111", "claude", None, "idle"),
+            agent("bbbb2222", "codex", None, "busy"),
+            agent("cccc3333", "codex", None, "stopped"),
+            agent("dddd4444", "gemini", None, "idle"),
+        ]);
+        roster.agents[3].supports_consult = false;
+
+        let peers: Vec<&str> = broadcast_peers(&roster, Some("aaaa1111"))
+            .iter()
+            .map(|a| a.id.as_str())
+            .collect();
+
+        assert_eq!(peers, ["bbbb2222"]);
+    }
+
     #[test]
     fn tail_lines_slices_from_the_end() {
         let text = "a\nb\nc\nd";
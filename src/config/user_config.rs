@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 
@@ -55,6 +55,176 @@ fn default_true() -> bool {
     true
 }
 
+/// What one model charges, in dollars per million tokens of each kind.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    #[serde(default)]
+    pub cache_read: f64,
+    #[serde(default)]
+    pub cache_write: f64,
+}
+
+impl ModelPrice {
+    pub const fn per_million(input: f64, output: f64, cache_read: f64, cache_write: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_read,
+            cache_write,
+        }
+    }
+
+    pub fn cost(&self, tokens: &crate::models::TokenUsage) -> f64 {
+        (tokens.input as f64 * self.input
+            + tokens.output as f64 * self.output
+            + tokens.cache_read as f64 * self.cache_read
+            + tokens.cache_write as f64 * self.cache_write)
+            / 1_000_000.0
+    }
+}
+
+// List prices as published on Anthropic's pricing page
+// (docs.anthropic.com/en/docs/about-claude/pricing; cache writes at the
+// 5-minute rate) and OpenAI's (openai.com/api/pricing) when this table was
+// last updated.
+// Opus dropped to $5/$25 with Opus 4.5; the 4.0 and 4.1 ids keep their old
+// rate. Prices drift and a plan's discount is invisible from here, so treat
+// the total as an estimate — and add to `[pricing]` in user_config.toml to
+// correct it.
+const PRICING_DEFAULTS: [(&str, ModelPrice); 9] = [
+    ("claude-opus", ModelPrice::per_million(5.0, 25.0, 0.5, 6.25)),
+    ("claude-opus-4-0", ModelPrice::per_million(15.0, 75.0, 1.5, 18.75)),
+    ("claude-opus-4-1", ModelPrice::per_million(15.0, 75.0, 1.5, 18.75)),
+    ("claude-opus-4-2025", ModelPrice::per_million(15.0, 75.0, 1.5, 18.75)),
+    ("claude-sonnet", ModelPrice::per_million(3.0, 15.0, 0.3, 3.75)),
+    ("claude-haiku", ModelPrice::per_million(1.0, 5.0, 0.1, 1.25)),
+    ("gpt-5", ModelPrice::per_million(1.25, 10.0, 0.125, 0.0)),
+    ("gpt-4.1", ModelPrice::per_million(2.0, 8.0, 0.5, 0.0)),
+    ("o3", ModelPrice::per_million(2.0, 8.0, 0.5, 0.0)),
+];
+
+fn default_pricing() -> BTreeMap<String, ModelPrice> {
+    PRICING_DEFAULTS
+        .iter()
+        .map(|(prefix, price)| ((*prefix).to_string(), *price))
+        .collect()
+}
+
+/// The user's `[pricing]` is laid over the defaults rather than swapped for
+/// them: pricing one new model must not leave every other at $0, which the
+/// meter would show without a word.
+fn pricing_over_defaults<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<String, ModelPrice>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let mut pricing = default_pricing();
+    pricing.extend(BTreeMap::<String, ModelPrice>::deserialize(deserializer)?);
+    Ok(pricing)
+}
+
+/// And only what the user changed is written back. Saving the whole table
+/// would turn today's defaults into overrides, and the next correction to
+/// a default would never reach anyone who had saved a setting since.
+fn pricing_unlike_defaults<S>(
+    pricing: &BTreeMap<String, ModelPrice>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let defaults = default_pricing();
+    let changed: BTreeMap<&String, &ModelPrice> = pricing
+        .iter()
+        .filter(|(model, price)| defaults.get(*model) != Some(*price))
+        .collect();
+    changed.serialize(serializer)
+}
+
+/// Who may consult an agent, how often, and whether it answers before or
+/// after its queued TODO items — one `[[consult_policy]]` table per agent
+/// or kind of agent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsultPolicy {
+    /// The agents this governs: an alias, a provider (`codex`), or `*`. An
+    /// alias beats a provider, which beats `*`.
+    pub agent: String,
+    /// Askers let through, matched the same way (and by short id). Empty
+    /// lets everyone through.
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Askers turned away, even if `allow` names them.
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// Consults accepted per rolling hour. An agent that every peer leans on
+    /// otherwise spends its day answering instead of working.
+    #[serde(default)]
+    pub per_hour: Option<u32>,
+    #[serde(default)]
+    pub order: ConsultOrder,
+}
+
+/// Where a consult goes relative to the target's own TODO queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsultOrder {
+    /// Ahead of queued work, behind earlier consults: a peer is blocked on it.
+    #[default]
+    Ahead,
+    /// At the back of the queue, after the work already in it.
+    BehindTodos,
+}
+
+/// An agent as a policy sees it.
+#[derive(Debug, Clone, Copy)]
+pub struct PolicySubject<'a> {
+    pub short_id: &'a str,
+    pub alias: Option<&'a str>,
+    pub provider: &'a str,
+}
+
+impl PolicySubject<'_> {
+    fn is(&self, pattern: &str) -> bool {
+        pattern == "*"
+            || pattern.eq_ignore_ascii_case(self.short_id)
+            || pattern.eq_ignore_ascii_case(self.provider)
+            || self.alias.is_some_and(|alias| pattern.eq_ignore_ascii_case(alias))
+    }
+}
+
+impl ConsultPolicy {
+    /// Why `asker` may not consult under this policy, if it may not.
+    pub fn refuses(&self, asker: PolicySubject) -> Option<String> {
+        if self.deny.iter().any(|pattern| asker.is(pattern)) {
+            return Some(format!("{} does not take consults from {}", self.agent, asker.short_id));
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(|pattern| asker.is(pattern)) {
+            return Some(format!(
+                "{} only takes consults from {}",
+                self.agent,
+                self.allow.join(", ")
+            ));
+        }
+        None
+    }
+}
+
+/// The policy governing `target`: the one naming its alias, else its
+/// provider, else `*`.
+pub fn consult_policy_for<'a>(
+    policies: &'a [ConsultPolicy],
+    target: PolicySubject,
+) -> Option<&'a ConsultPolicy> {
+    let by = |matches: &dyn Fn(&str) -> bool| policies.iter().find(|p| matches(&p.agent));
+    by(&|agent| target.alias.is_some_and(|alias| agent.eq_ignore_ascii_case(alias)))
+        .or_else(|| by(&|agent| agent.eq_ignore_ascii_case(target.short_id)))
+        .or_else(|| by(&|agent| agent.eq_ignore_ascii_case(target.provider)))
+        .or_else(|| by(&|agent| agent == "*"))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct UserConfig {
@@ -80,6 +250,26 @@ pub struct UserConfig {
     pub expose_dev_servers: bool,
     #[serde(default = "default_true")]
     pub use_alternate_screen: bool,
+    /// Dollars per million tokens, keyed by model-id prefix (see
+    /// `models::cost::price_for`). Agents journal the tokens they spend; this
+    /// is what turns them into the estimate on screen, so a new model can be
+    /// priced by adding a line here rather than waiting for a release.
+    #[serde(
+        default = "default_pricing",
+        deserialize_with = "pricing_over_defaults",
+        serialize_with = "pricing_unlike_defaults"
+    )]
+    pub pricing: BTreeMap<String, ModelPrice>,
+    /// Projects, by name or path, whose agents may consult — and be consulted
+    /// by — agents in other projects as `project:agent`. Empty by default:
+    /// an agent in one repository reading another's conversation should be
+    /// something you asked for, and both sides have to be listed.
+    #[serde(default)]
+    pub cross_project: Vec<String>,
+    /// Per-agent consult rules (see `ConsultPolicy`). None by default: any
+    /// peer may consult any agent that can answer, as often as it likes.
+    #[serde(default, rename = "consult_policy")]
+    pub consult_policies: Vec<ConsultPolicy>,
 
     // Legacy fields — ignored on load, derived from scrollback_mb
     #[serde(skip)]
@@ -266,6 +456,17 @@ impl UserConfig {
         // Claude scrollback at 500/MB lines and silently ate early history.
         self.transcript_max_lines = (mb * 4000).clamp(4000, 64_000);
     }
+
+    /// The project named `name` at `path` is listed in `cross_project`.
+    /// Names compare case-insensitively; a path must match exactly, give or
+    /// take a trailing slash.
+    pub fn shares_across_projects(&self, name: &str, path: &std::path::Path) -> bool {
+        self.cross_project.iter().any(|entry| {
+            let entry = entry.trim();
+            entry.eq_ignore_ascii_case(name)
+                || std::path::Path::new(entry.trim_end_matches('/')) == path
+        })
+    }
 }
 
 impl Default for UserConfig {
@@ -278,6 +479,9 @@ impl Default for UserConfig {
             remote_token: String::new(),
             expose_dev_servers: true,
             use_alternate_screen: default_true(),
+            pricing: default_pricing(),
+            cross_project: Vec::new(),
+            consult_policies: Vec::new(),
             scrollback_buffer_kb: 0,
             replay_parser_rows: 0,
             live_scrollback_rows: 0,
@@ -400,6 +604,110 @@ mod tests {
         assert_eq!(hotkeys.get("CycleNextSession"), Some(&"F5".to_string()));
     }
 
+    /// A config that prices one new model, or re-prices one we know, gets
+    /// its own lines on top of the defaults — every other model keeps its
+    /// price instead of quietly costing $0.
+    #[test]
+    fn pricing_from_the_config_file_is_laid_over_the_defaults() {
+        let config: UserConfig = toml::from_str(
+            r#"
+            [pricing."claude-fable"]
+            input = 4.0
+            output = 20.0
+
+            [pricing."claude-sonnet"]
+            input = 2.0
+            output = 10.0
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.pricing.len(), PRICING_DEFAULTS.len() + 1);
+        let price = config.pricing["claude-fable"];
+        assert_eq!(price, ModelPrice::per_million(4.0, 20.0, 0.0, 0.0));
+        assert_eq!(config.pricing["claude-sonnet"].input, 2.0);
+        assert_eq!(config.pricing["claude-opus"], default_pricing()["claude-opus"]);
+    }
+
+    /// Saving writes the user's own prices and none of the defaults, so a
+    /// default corrected in a later release still reaches them.
+    #[test]
+    fn saving_pricing_writes_only_what_differs_from_the_defaults() {
+        let mut config = UserConfig::default();
+        config.pricing.get_mut("claude-sonnet").unwrap().input = 2.0;
+        config
+            .pricing
+            .insert("claude-fable".into(), ModelPrice::per_million(4.0, 20.0, 0.0, 0.0));
+
+        let saved = toml::to_string_pretty(&config).unwrap();
+        assert!(saved.contains("claude-sonnet") && saved.contains("claude-fable"), "{saved}");
+        assert!(!saved.contains("claude-opus"), "{saved}");
+        assert!(!saved.contains("gpt-5"), "{saved}");
+
+        let loaded: UserConfig = toml::from_str(&saved).unwrap();
+        assert_eq!(loaded.pricing, config.pricing);
+        let untouched = toml::to_string_pretty(&UserConfig::default()).unwrap();
+        assert!(!untouched.contains("claude-"), "{untouched}");
+    }
+
+    #[test]
+    fn cross_project_consults_are_off_until_a_project_is_listed() {
+        let config: UserConfig = toml::from_str("").unwrap();
+        assert!(!config.shares_across_projects("api", std::path::Path::new("/src/api")));
+
+        let config: UserConfig =
+            toml::from_str("cross_project = [\"API\", \"/src/web/\"]").unwrap();
+        assert!(config.shares_across_projects("api", std::path::Path::new("/elsewhere")));
+        assert!(config.shares_across_projects("frontend", std::path::Path::new("/src/web")));
+        assert!(!config.shares_across_projects("docs", std::path::Path::new("/src/docs")));
+    }
+
+    #[test]
+    fn the_most_specific_consult_policy_governs_and_deny_beats_allow() {
+        let config: UserConfig = toml::from_str(
+            r#"
+            [[consult_policy]]
+            agent = "*"
+            per_hour = 10
+
+            [[consult_policy]]
+            agent = "codex"
+            allow = ["claude"]
+            deny = ["intern"]
+            order = "behind_todos"
+
+            [[consult_policy]]
+            agent = "reviewer"
+            per_hour = 2
+            "#,
+        )
+        .unwrap();
+        let policies = &config.consult_policies;
+        let agent = |short_id, alias, provider| PolicySubject {
+            short_id,
+            alias,
+            provider,
+        };
+
+        let reviewer = consult_policy_for(policies, agent("a1", Some("reviewer"), "codex")).unwrap();
+        assert_eq!(reviewer.per_hour, Some(2));
+        let codex = consult_policy_for(policies, agent("b2", None, "codex")).unwrap();
+        assert_eq!(codex.order, ConsultOrder::BehindTodos);
+        assert_eq!(consult_policy_for(policies, agent("c3", None, "claude")).unwrap().agent, "*");
+
+        assert!(codex.refuses(agent("d4", None, "claude")).is_none());
+        assert!(codex.refuses(agent("e5", Some("intern"), "claude")).is_some());
+        let refusal = codex.refuses(agent("f6", None, "gemini")).unwrap();
+        assert!(refusal.contains("only takes consults from claude"), "{refusal}");
+    }
+
+    #[test]
+    fn a_config_without_pricing_gets_the_default_table() {
+        let config: UserConfig = toml::from_str("scrollback_mb = 4").unwrap();
+
+        assert_eq!(config.pricing, default_pricing());
+    }
+
     #[test]
     fn normalize_global_hotkeys_clears_duplicates_deterministically() {
         let mut hotkeys = default_global_hotkeys();
@@ -12,6 +12,8 @@ use serde_json::{Value, json};
 use std::io::{BufRead, BufReader, Write};
 #[cfg(unix)]
 use std::os::unix::net::UnixStream;
+#[cfg(unix)]
+use std::collections::VecDeque;
 use std::time::Duration;
 
 #[cfg(unix)]
@@ -37,6 +39,9 @@ impl Client {
     pub fn subscribe(&mut self) -> Result<()> {
         bail!("unsupported")
     }
+    pub fn subscribe_to(&mut self, _filter: Value) -> Result<Value> {
+        bail!("unsupported")
+    }
     pub fn next_event(&mut self) -> Result<Option<Value>> {
         bail!("unsupported")
     }
@@ -48,8 +53,17 @@ pub struct Client {
     stream: UnixStream,
     reader: BufReader<UnixStream>,
     next_id: u64,
+    /// Events that arrived while `call` was waiting for its answer, oldest
+    /// first, for `next_event` to hand out before it reads any more.
+    pending: VecDeque<Value>,
 }
 
+/// A caller that subscribes and then only makes calls would otherwise hold
+/// every event for the life of the connection. Matches what the server
+/// queues for a reader that has stopped.
+#[cfg(unix)]
+const MAX_PENDING_EVENTS: usize = 256;
+
 #[cfg(unix)]
 impl Client {
     /// Connect to the workbench on this machine.
@@ -69,6 +83,7 @@ impl Client {
             stream,
             reader,
             next_id: 1,
+            pending: VecDeque::new(),
         })
     }
 
@@ -87,12 +102,17 @@ impl Client {
         writeln!(self.stream, "{request}")?;
         self.stream.flush()?;
 
-        // Events and replies share the stream once subscribed, so skip past
-        // anything that is not the answer to this request.
+        // Events and replies share the stream once subscribed. One that
+        // arrives ahead of the answer is kept for `next_event`: it happened
+        // after the subscription, which is exactly what a follower asked for.
         loop {
             let line = self.read_line()?;
             let value: Value = serde_json::from_str(&line)?;
             if value.get("event").is_some() {
+                if self.pending.len() == MAX_PENDING_EVENTS {
+                    self.pending.pop_front();
+                }
+                self.pending.push_back(value);
                 continue;
             }
             if let Some(error) = value.get("error") {
@@ -110,13 +130,24 @@ impl Client {
     /// Ask to be told when things change. Events then arrive interleaved with
     /// replies, which `call` and `next_event` each step over.
     pub fn subscribe(&mut self) -> Result<()> {
-        self.call("events.subscribe", json!({}))?;
+        self.subscribe_to(json!({}))?;
         Ok(())
     }
 
-    /// The next event, skipping any reply that arrives first. `None` on
+    /// Subscribe with the server's filters (`project`, `agent`, `event`,
+    /// `since`), returning its answer — which carries the `seq` to resume
+    /// from and whether anything was lost.
+    pub fn subscribe_to(&mut self, filter: Value) -> Result<Value> {
+        self.call("events.subscribe", filter)
+    }
+
+    /// The next event — first any that `call` stepped over, in order — then
+    /// from the stream, skipping any reply that arrives first. `None` on
     /// timeout; `Err` if the connection went away.
     pub fn next_event(&mut self) -> Result<Option<Value>> {
+        if let Some(event) = self.pending.pop_front() {
+            return Ok(Some(event));
+        }
         loop {
             match self.read_line() {
                 Ok(line) => {
@@ -311,6 +342,38 @@ pub fn resolve_agent(agents: &[Value], target: &str, scope: &Scope) -> Result<St
     bail!("no agent matches `{target}`")
 }
 
+/// Build request params from the pairs typed after `--param`.
+///
+/// `key=value` is always a string — a short id that happens to be all digits
+/// must not turn into a number on the way. `key:=value` is JSON, for the
+/// params that are not strings (`by:=-1`, `since:=42`). A key given twice
+/// becomes a list, which is how a filter names several agents or events.
+pub fn parse_params(pairs: &[String]) -> Result<Value> {
+    let mut params = serde_json::Map::new();
+    for pair in pairs {
+        let Some((key, raw)) = pair.split_once('=') else {
+            bail!("`{pair}` is not key=value (or key:=json)");
+        };
+        let value = match key.strip_suffix(':') {
+            Some(_) => serde_json::from_str(raw)
+                .map_err(|err| anyhow::anyhow!("`{pair}`: not JSON ({err})"))?,
+            None => Value::String(raw.to_string()),
+        };
+        let key = key.trim_end_matches(':').trim();
+        if key.is_empty() {
+            bail!("`{pair}` has no key");
+        }
+        match params.get_mut(key) {
+            None => {
+                params.insert(key.to_string(), value);
+            }
+            Some(Value::Array(list)) => list.push(value),
+            Some(first) => *first = Value::Array(vec![first.take(), value]),
+        }
+    }
+    Ok(Value::Object(params))
+}
+
 /// Turn a project name into its workspace id, for `--project`.
 pub fn resolve_project(projects: &[Value], name: &str) -> Result<String> {
     let wanted = name.trim().to_lowercase();
@@ -343,6 +406,51 @@ mod tests {
         })
     }
 
+    /// What a shell hands over is all strings; what the server wants is not
+    /// always. `:=` says which is which, so neither side has to guess — a
+    /// guess would turn the short id `12345678` into a number.
+    #[test]
+    fn params_typed_on_the_command_line_keep_their_types() {
+        let params = parse_params(&[
+            "agent=12345678".into(),
+            "by:=-2".into(),
+            "text=ship it = now".into(),
+            "event=todo.done".into(),
+            "event=consult.answered".into(),
+        ])
+        .unwrap();
+
+        assert_eq!(params["agent"], "12345678");
+        assert_eq!(params["by"], -2);
+        assert_eq!(params["text"], "ship it = now");
+        assert_eq!(params["event"], json!(["todo.done", "consult.answered"]));
+        assert!(parse_params(&["agent".into()]).is_err());
+        assert!(parse_params(&["by:=minus one".into()]).is_err());
+    }
+
+    /// An event that lands between subscribing and the answer to the next
+    /// call is handed out by `next_event`, ahead of anything newer.
+    #[cfg(unix)]
+    #[test]
+    fn events_read_while_waiting_for_an_answer_are_kept() {
+        let (stream, mut server) = UnixStream::pair().unwrap();
+        let mut client = Client {
+            reader: BufReader::new(stream.try_clone().unwrap()),
+            stream,
+            next_id: 1,
+            pending: VecDeque::new(),
+        };
+        writeln!(server, "{}", json!({"seq": 1, "event": "todo.done"})).unwrap();
+        writeln!(server, "{}", json!({"id": 1, "result": "ok"})).unwrap();
+        writeln!(server, "{}", json!({"seq": 2, "event": "agent.idle"})).unwrap();
+
+        assert_eq!(client.call("agent.prompt", json!({})).unwrap(), "ok");
+        let first = client.next_event().unwrap().unwrap();
+        assert_eq!(first["event"], "todo.done");
+        let second = client.next_event().unwrap().unwrap();
+        assert_eq!(second["event"], "agent.idle");
+    }
+
     fn anywhere() -> Scope {
         Scope::default()
     }
@@ -19,7 +19,10 @@
 //!
 //! Events are the reason this exists rather than a polling endpoint. The tick
 //! diffs each published snapshot against the last and pushes what moved, so a
-//! caller can wait on `agent.status_changed` instead of asking every second.
+//! caller can wait on `agent.status_changed` instead of asking every second —
+//! or on a queued item being handed over and finished (`todo.*`), a consult
+//! reaching its target and being answered (`consult.*`), or an agent stopping
+//! on a question it needs answered (`agent.blocked`).
 //!
 //! Unix-only: the socket is a Unix domain socket, and workbench's PTY layer is
 //! already POSIX. The module compiles to nothing elsewhere.
@@ -31,16 +34,18 @@ use serde_json::{Value, json};
 use std::io::{BufRead, BufReader, Read, Write};
 #[cfg(unix)]
 use std::os::unix::net::{UnixListener, UnixStream};
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{Receiver, Sender, channel};
-use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{Receiver, SyncSender, sync_channel};
+use std::sync::{Arc, Mutex, Weak};
 use tokio::sync::mpsc::UnboundedSender;
 
 mod client;
 
-pub use client::{resolve_agent, resolve_project, Client, Scope};
+pub use client::{parse_params, resolve_agent, resolve_project, Client, Scope};
 
-use crate::remote::{RemoteCommand, Shared, Snapshot};
+use crate::models::TodoState;
+use crate::remote::{Prompt, RemoteCommand, Shared, Snapshot};
 
 /// Refuse a line longer than this. A control message is a few hundred bytes;
 /// anything at this scale is a mistake or a wedge, and reading it to the end
@@ -49,11 +54,20 @@ use crate::remote::{RemoteCommand, Shared, Snapshot};
 const MAX_LINE_BYTES: u64 = 1 << 20;
 
 /// A subscriber's backlog. A caller that stops reading while workbench keeps
-/// ticking must not grow the queue without bound — past this it is dropped,
-/// which it discovers as a closed socket.
-#[cfg(unix)]
+/// ticking must not grow the queue without bound — past this it is dropped.
+/// It is told so in a last line carrying the sequence number it got to, and
+/// can reconnect with `since` to pick up from there (see `REPLAY_EVENTS`).
 const MAX_PENDING_EVENTS: usize = 256;
 
+/// Events kept for a caller that reconnects with `since`. A reconnect takes
+/// seconds, and a tick rarely says more than a few things, so this covers any
+/// ordinary gap; a longer one is reported as a gap rather than papered over.
+const REPLAY_EVENTS: usize = 1024;
+
+/// How long `agent.screen` waits for the event loop, which answers on its
+/// next pass — well under a second unless something is badly wrong.
+const SCREEN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
 // ---------------------------------------------------------------------------
 // Events
 // ---------------------------------------------------------------------------
@@ -61,36 +75,164 @@ const MAX_PENDING_EVENTS: usize = 256;
 /// Fan-out to whoever has subscribed. Cloned into every connection thread.
 #[derive(Clone, Default)]
 pub struct EventHub {
-    subscribers: Arc<Mutex<Vec<Sender<String>>>>,
+    inner: Arc<Mutex<Hub>>,
+}
+
+#[derive(Default)]
+struct Hub {
+    subscribers: Vec<Subscriber>,
+    /// The sequence number of the last event emitted. The first is 1, so a
+    /// caller that has seen nothing can say `since: 0`.
+    seq: u64,
+    /// The last `REPLAY_EVENTS` events, oldest first.
+    replay: VecDeque<Recorded>,
+}
+
+struct Subscriber {
+    filter: EventFilter,
+    /// Each event's line, with its number so the writer knows how far the
+    /// caller got.
+    feed: SyncSender<(u64, String)>,
+    /// Held by the connection that subscribed for as long as it is open.
+    /// Its writer thread only finds out the caller is gone when it next
+    /// writes — and for a filter that matches nothing for hours, that is
+    /// never.
+    connection: Weak<()>,
+}
+
+struct Recorded {
+    seq: u64,
+    event: String,
+    data: Value,
+    line: String,
+}
+
+/// What a subscriber wants to hear about. Every list is "any of these", and
+/// an empty one is no restriction — so no params at all is every event.
+#[derive(Debug, Clone, Default)]
+struct EventFilter {
+    /// Project names or ids.
+    projects: Vec<String>,
+    /// Short agent ids. A consult matches either end of it.
+    agents: Vec<String>,
+    /// Event names; `todo.*` takes the whole family.
+    events: Vec<String>,
+}
+
+impl EventFilter {
+    fn from_params(params: &Value) -> std::result::Result<EventFilter, (&'static str, String)> {
+        Ok(EventFilter {
+            projects: list_param(params, "project")?,
+            agents: list_param(params, "agent")?,
+            events: list_param(params, "event")?,
+        })
+    }
+
+    fn matches(&self, event: &str, data: &Value) -> bool {
+        let field = |key: &str| data.get(key).and_then(Value::as_str);
+        let any_of = |wanted: &[String], keys: &[&str]| {
+            wanted.is_empty()
+                || keys.iter().filter_map(|key| field(key)).any(|value| {
+                    wanted.iter().any(|want| want.eq_ignore_ascii_case(value))
+                })
+        };
+        let named = self.events.is_empty()
+            || self.events.iter().any(|want| match want.strip_suffix('*') {
+                Some(family) => event.starts_with(family),
+                None => want == event,
+            });
+        named
+            && any_of(&self.projects, &["project", "project_id"])
+            && any_of(&self.agents, &["agent", "from", "to"])
+    }
+}
+
+/// A new subscription: what was missed, then the live feed.
+struct Subscription {
+    backlog: Vec<(u64, String)>,
+    events: Receiver<(u64, String)>,
+    /// The last event emitted before this subscription began.
+    seq: u64,
+    /// `since` reached back further than the replay buffer does — or past
+    /// the last event, which means it was quoted from a previous run of
+    /// workbench, whose numbering this one does not continue.
+    gap: bool,
 }
 
 impl EventHub {
-    fn subscribe(&self) -> Receiver<String> {
-        let (tx, rx) = channel();
-        if let Ok(mut subscribers) = self.subscribers.lock() {
-            subscribers.push(tx);
+    /// Subscribe, replaying anything after `since` first. Both happen under
+    /// the one lock, so an event is either in the backlog or in the feed —
+    /// never both, and never neither. The subscription lasts as long as
+    /// `connection` is held, or until its reader falls too far behind.
+    fn subscribe(
+        &self,
+        filter: EventFilter,
+        since: Option<u64>,
+        connection: &Arc<()>,
+    ) -> Subscription {
+        let (tx, rx) = sync_channel(MAX_PENDING_EVENTS);
+        let Ok(mut hub) = self.inner.lock() else {
+            return Subscription {
+                backlog: Vec::new(),
+                events: rx,
+                seq: 0,
+                gap: since.is_some(),
+            };
+        };
+        let (backlog, gap) = match since {
+            None => (Vec::new(), false),
+            Some(since) => {
+                let oldest = hub.replay.front().map_or(hub.seq + 1, |event| event.seq);
+                let backlog = hub
+                    .replay
+                    .iter()
+                    .filter(|event| event.seq > since)
+                    .filter(|event| filter.matches(&event.event, &event.data))
+                    .map(|event| (event.seq, event.line.clone()))
+                    .collect();
+                (backlog, since + 1 < oldest || since > hub.seq)
+            }
+        };
+        let seq = hub.seq;
+        hub.subscribers.push(Subscriber {
+            filter,
+            feed: tx,
+            connection: Arc::downgrade(connection),
+        });
+        Subscription {
+            backlog,
+            events: rx,
+            seq,
+            gap,
         }
-        rx
     }
 
     fn emit(&self, event: &str, data: Value) {
-        let Ok(mut subscribers) = self.subscribers.lock() else {
+        let Ok(mut hub) = self.inner.lock() else {
             return;
         };
-        if subscribers.is_empty() {
-            return;
+        hub.seq += 1;
+        let seq = hub.seq;
+        let line = json!({"seq": seq, "event": event, "data": data}).to_string();
+        // Every emit prunes the subscribers whose connection has closed,
+        // whatever they asked to hear; a send fails once the writer thread
+        // has dropped its receiver or once a subscriber has fallen
+        // `MAX_PENDING_EVENTS` behind, and those go too. Dropping the sender
+        // is what ends the writer thread.
+        hub.subscribers.retain(|subscriber| {
+            subscriber.connection.strong_count() > 0
+                && (!subscriber.filter.matches(event, &data)
+                    || subscriber.feed.try_send((seq, line.clone())).is_ok())
+        });
+        hub.replay.push_back(Recorded {
+            seq,
+            event: event.to_string(),
+            data,
+            line,
+        });
+        while hub.replay.len() > REPLAY_EVENTS {
+            hub.replay.pop_front();
         }
-        let line = json!({"event": event, "data": data}).to_string();
-        // A send fails once the connection thread has dropped its receiver,
-        // which is how a closed socket is noticed: prune on the way past.
-        subscribers.retain(|subscriber| subscriber.send(line.clone()).is_ok());
-    }
-
-    fn is_idle(&self) -> bool {
-        self.subscribers
-            .lock()
-            .map(|subscribers| subscribers.is_empty())
-            .unwrap_or(true)
     }
 }
 
@@ -99,15 +241,21 @@ impl EventHub {
 #[derive(Clone, PartialEq, Eq)]
 struct AgentMark {
     project: String,
+    project_id: String,
     status: String,
     model: Option<String>,
     reason: Option<String>,
+    prompt: Option<Prompt>,
 }
 
 /// What the last tick published, so this one can say what moved.
 #[derive(Default)]
 pub struct EventState {
     agents: Vec<(String, AgentMark)>,
+    /// Every queue item by id. Ids are uuids, so one map serves every agent.
+    todos: HashMap<String, TodoState>,
+    /// Every consult in view by ticket, with its status.
+    consults: HashMap<String, String>,
 }
 
 /// Compare the snapshot just published with the one before it and push the
@@ -116,11 +264,11 @@ pub struct EventState {
 /// Diffing is what keeps this honest: the alternative is emitting from the
 /// dozen places that can change an agent's state, and the one that gets
 /// forgotten is a caller waiting forever.
+///
+/// Events are numbered and kept whether or not anyone is listening: the
+/// caller that most needs the last few seconds is the one that just lost its
+/// connection and is on its way back with `since`.
 pub fn publish_events(hub: &EventHub, previous: &mut EventState, snapshot: &Snapshot) {
-    // Nobody is listening: keep the marks current so the first subscriber does
-    // not receive the entire backlog as "changes", but skip the formatting.
-    let quiet = hub.is_idle();
-
     let current: Vec<(String, AgentMark)> = snapshot
         .agents
         .iter()
@@ -129,52 +277,166 @@ pub fn publish_events(hub: &EventHub, previous: &mut EventState, snapshot: &Snap
                 agent.id.clone(),
                 AgentMark {
                     project: agent.project.clone(),
+                    project_id: agent.project_id.clone(),
                     status: agent.status.clone(),
                     model: agent.model.clone(),
                     reason: agent.reason.clone(),
+                    prompt: agent.prompt.clone(),
                 },
             )
         })
         .collect();
+    let todos: HashMap<String, TodoState> = snapshot
+        .agents
+        .iter()
+        .flat_map(|agent| agent.todos.iter())
+        .map(|todo| (todo.id.clone(), todo.state))
+        .collect();
+    let consults: HashMap<String, String> = snapshot
+        .consults
+        .iter()
+        .map(|consult| (consult.ticket.clone(), consult.status.clone()))
+        .collect();
 
-    if !quiet {
-        for (id, mark) in &current {
-            match previous.agents.iter().find(|(known, _)| known == id) {
-                None => hub.emit(
+    for (id, mark) in &current {
+        match previous.agents.iter().find(|(known, _)| known == id) {
+            None => {
+                hub.emit(
                     "agent.added",
-                    json!({"agent": id, "project": mark.project, "status": mark.status}),
-                ),
-                Some((_, was)) if was != mark => {
-                    if was.status != mark.status {
-                        hub.emit(
-                            "agent.status_changed",
-                            json!({
-                                "agent": id,
-                                "project": mark.project,
-                                "from": was.status,
-                                "to": mark.status,
-                                "reason": mark.reason,
-                            }),
-                        );
-                    }
-                    if was.model != mark.model {
-                        hub.emit(
-                            "agent.model_changed",
-                            json!({"agent": id, "project": mark.project, "model": mark.model}),
-                        );
-                    }
+                    json!({
+                        "agent": id,
+                        "project": mark.project,
+                        "project_id": mark.project_id,
+                        "status": mark.status,
+                    }),
+                );
+                if mark.status == "blocked" {
+                    emit_blocked(hub, id, mark);
                 }
-                Some(_) => {}
             }
-        }
-        for (id, mark) in &previous.agents {
-            if !current.iter().any(|(known, _)| known == id) {
-                hub.emit("agent.removed", json!({"agent": id, "project": mark.project}));
+            Some((_, was)) if was != mark => {
+                if was.status != mark.status {
+                    hub.emit(
+                        "agent.status_changed",
+                        json!({
+                            "agent": id,
+                            "project": mark.project,
+                            "project_id": mark.project_id,
+                            "from": was.status,
+                            "to": mark.status,
+                            "reason": mark.reason,
+                        }),
+                    );
+                }
+                if was.model != mark.model {
+                    hub.emit(
+                        "agent.model_changed",
+                        json!({
+                            "agent": id,
+                            "project": mark.project,
+                            "project_id": mark.project_id,
+                            "model": mark.model,
+                        }),
+                    );
+                }
+                // On the way into "blocked", and again for a different
+                // question while still there: answering one permission
+                // prompt often just uncovers the next.
+                let new_question = mark.prompt.is_some() && was.prompt != mark.prompt;
+                if mark.status == "blocked" && (was.status != "blocked" || new_question) {
+                    emit_blocked(hub, id, mark);
+                }
             }
+            Some(_) => {}
+        }
+    }
+    for (id, mark) in &previous.agents {
+        if !current.iter().any(|(known, _)| known == id) {
+            hub.emit(
+                "agent.removed",
+                json!({"agent": id, "project": mark.project, "project_id": mark.project_id}),
+            );
+        }
+    }
+
+    for agent in &snapshot.agents {
+        // An agent seen for the first time brings its queue as it stands;
+        // only what happens to it from here on is news.
+        let known = previous.agents.iter().any(|(id, _)| *id == agent.id);
+        for todo in &agent.todos {
+            let was = match previous.todos.get(&todo.id) {
+                Some(state) => *state,
+                None if known => TodoState::Pending,
+                None => continue,
+            };
+            let event = match (was, todo.state) {
+                (TodoState::Pending, TodoState::Running) => "todo.dispatched",
+                (TodoState::Pending | TodoState::Running, TodoState::Done) => "todo.done",
+                _ => continue,
+            };
+            hub.emit(
+                event,
+                json!({
+                    "agent": agent.id,
+                    "project": agent.project,
+                    "project_id": agent.project_id,
+                    "todo": todo.id,
+                    "text": todo.text,
+                }),
+            );
         }
     }
 
+    for consult in &snapshot.consults {
+        // A consult only knows its project by id; the name rides along so a
+        // `project=<name>` filter hears consults as it does everything else.
+        let project = snapshot
+            .projects
+            .iter()
+            .find(|project| project.id == consult.project_id)
+            .map(|project| project.name.as_str());
+        let was = previous.consults.get(&consult.ticket).map(String::as_str);
+        if was == Some(consult.status.as_str()) {
+            continue;
+        }
+        let event = match consult.status.as_str() {
+            "queued" => continue,
+            "delivered" => "consult.delivered",
+            // Whatever the reply says — answered, timed out — the asker
+            // has something to collect.
+            _ => "consult.answered",
+        };
+        hub.emit(
+            event,
+            json!({
+                "ticket": consult.ticket,
+                "project": project,
+                "project_id": consult.project_id,
+                "from": consult.from,
+                "to": consult.to,
+                "status": consult.status,
+            }),
+        );
+    }
+
     previous.agents = current;
+    previous.todos = todos;
+    previous.consults = consults;
+}
+
+/// The question comes along, so a caller can answer with `agent.answer`
+/// without first asking what it was.
+fn emit_blocked(hub: &EventHub, id: &str, mark: &AgentMark) {
+    hub.emit(
+        "agent.blocked",
+        json!({
+            "agent": id,
+            "project": mark.project,
+            "project_id": mark.project_id,
+            "reason": mark.reason,
+            "prompt": mark.prompt,
+        }),
+    );
 }
 
 // ---------------------------------------------------------------------------
@@ -325,6 +587,9 @@ fn serve(
     let mut out = stream.try_clone()?;
     let mut reader = BufReader::new(stream);
     let mut subscribed = false;
+    // Dropped when this returns — the caller hung up — which is what tells
+    // the hub to let go of this connection's subscription.
+    let connection = Arc::new(());
 
     loop {
         // Capped per line rather than per connection: a control connection is
@@ -363,23 +628,43 @@ fn serve(
                 reply(&mut out, &id, Ok(json!({"subscribed": true})))?;
                 continue;
             }
+            let filter = match EventFilter::from_params(&params) {
+                Ok(filter) => filter,
+                Err(err) => {
+                    reply(&mut out, &id, Err(err))?;
+                    continue;
+                }
+            };
+            let since = match params.get("since") {
+                None | Some(Value::Null) => None,
+                Some(since) => match since.as_u64() {
+                    Some(since) => Some(since),
+                    None => {
+                        let err = ("bad_params", "`since` must be a sequence number".into());
+                        reply(&mut out, &id, Err(err))?;
+                        continue;
+                    }
+                },
+            };
             subscribed = true;
-            let events = hub.subscribe();
+            let subscription = hub.subscribe(filter, since, &connection);
+            // The answer goes first, and only then the thread that writes
+            // events, so the reply cannot land in the middle of the backlog.
+            reply(
+                &mut out,
+                &id,
+                Ok(json!({
+                    "subscribed": true,
+                    "seq": subscription.seq,
+                    "gap": subscription.gap,
+                })),
+            )?;
             let mut sink = out.try_clone()?;
             std::thread::spawn(move || {
-                let mut pending = 0usize;
-                for event in events {
-                    pending += 1;
-                    if pending > MAX_PENDING_EVENTS {
-                        break;
-                    }
-                    if writeln!(sink, "{event}").is_err() || sink.flush().is_err() {
-                        break;
-                    }
-                    pending = 0;
+                if write_events(&mut sink, subscription).is_ok() {
+                    let _ = sink.shutdown(std::net::Shutdown::Both);
                 }
             });
-            reply(&mut out, &id, Ok(json!({"subscribed": true})))?;
             continue;
         }
 
@@ -396,6 +681,34 @@ fn serve(
     Ok(())
 }
 
+/// Write a subscription out until the caller hangs up (an error) or the hub
+/// lets go of it for falling too far behind. The second is said out loud,
+/// with the number of the last event written, so the caller reconnects with
+/// that as `since` rather than waiting on a socket that will never speak
+/// again.
+fn write_events(sink: &mut impl Write, subscription: Subscription) -> std::io::Result<()> {
+    let Subscription {
+        backlog,
+        events,
+        seq,
+        ..
+    } = subscription;
+    // Everything up to the subscription's start was either replayed or not
+    // asked for; resuming from there loses nothing.
+    let mut written = seq;
+    for (seq, event) in backlog.into_iter().chain(events.iter()) {
+        writeln!(sink, "{event}")?;
+        sink.flush()?;
+        written = written.max(seq);
+    }
+    let notice = json!({
+        "seq": written,
+        "event": "events.dropped",
+        "data": {"reason": format!("fell {MAX_PENDING_EVENTS} events behind; subscribe again with `since`")},
+    });
+    writeln!(sink, "{notice}")
+}
+
 #[cfg(unix)]
 fn reply(
     out: &mut UnixStream,
@@ -429,16 +742,12 @@ fn dispatch(
         }),
         "agent.get" => {
             let wanted = text_param(params, "agent")?;
-            with_snapshot(shared, |snapshot| {
-                snapshot
-                    .agents
-                    .iter()
-                    .find(|agent| agent.id.eq_ignore_ascii_case(&wanted))
-                    .map(|agent| to_value(agent))
-                    .ok_or_else(|| ("no_such_agent", format!("no agent {wanted}")))
-            })
+            with_snapshot(shared, |snapshot| find_agent(snapshot, &wanted).map(to_value))
         }
         "projects.list" => with_snapshot(shared, |snapshot| Ok(to_value(&snapshot.projects))),
+        "agent.screen" => screen(params, shared, commands),
+        "agent.messages" => messages(params, shared),
+        "agents.grep" => grep(params, shared),
 
         "agent.prompt" => queue(
             commands,
@@ -454,6 +763,40 @@ fn dispatch(
                 text: text_param(params, "text")?,
             },
         ),
+        "todo.list" => {
+            let wanted = text_param(params, "agent")?;
+            with_snapshot(shared, |snapshot| {
+                let agent = find_agent(snapshot, &wanted)?;
+                Ok(json!({
+                    "agent": agent.id,
+                    "paused": agent.paused,
+                    "holding": agent.holding,
+                    "items": to_value(&agent.todos),
+                }))
+            })
+        }
+        "todo.remove" => {
+            let (agent, todo) = find_todo(shared, params)?;
+            queue(commands, RemoteCommand::TodoRemove { agent, todo })
+        }
+        "todo.move" => {
+            let by = int_param(params, "by")?;
+            let (agent, todo) = find_todo(shared, params)?;
+            queue(commands, RemoteCommand::TodoMove { agent, todo, by })
+        }
+        "todo.pause" | "todo.resume" => queue(
+            commands,
+            RemoteCommand::TodoPause {
+                agent: text_param(params, "agent")?,
+                paused: method == "todo.pause",
+            },
+        ),
+        "todo.clear_done" => queue(
+            commands,
+            RemoteCommand::TodoClearDone {
+                agent: text_param(params, "agent")?,
+            },
+        ),
         "agent.answer" => queue(
             commands,
             RemoteCommand::Answer {
@@ -467,13 +810,34 @@ fn dispatch(
                 agent: text_param(params, "agent")?,
             },
         ),
-        "agent.new" => queue(
-            commands,
-            RemoteCommand::NewAgent {
-                project: text_param(params, "project")?,
-                provider: text_param(params, "provider")?,
-            },
-        ),
+        "agent.new" => new_agent(params, shared, commands),
+        "agent.keys" => keys(params, shared, commands),
+        // Ending agents, with the session list's guards: a restart is for a
+        // stopped agent, a stop for a running one, and a delete — which takes
+        // the worktree with it — has to be confirmed, as the second `d` press
+        // confirms it at the desk.
+        "agent.stop" => {
+            let agent = agent_in_state(shared, params, |status| status != "stopped", "not_running")?;
+            queue(commands, RemoteCommand::Stop { agent })
+        }
+        "agent.restart" => {
+            let agent = agent_in_state(shared, params, |status| status == "stopped", "not_stopped")?;
+            queue(commands, RemoteCommand::Restart { agent })
+        }
+        "agent.kill" => {
+            let agent = agent_in_state(shared, params, |_| true, "")?;
+            queue(commands, RemoteCommand::Kill { agent })
+        }
+        "agent.delete" => {
+            let agent = agent_in_state(shared, params, |_| true, "")?;
+            if params.get("confirm").and_then(Value::as_bool) != Some(true) {
+                return Err((
+                    "unconfirmed",
+                    format!("deleting {agent} removes its worktree too; pass \"confirm\": true"),
+                ));
+            }
+            queue(commands, RemoteCommand::Delete { agent })
+        }
 
         // The hook fast path (see `src/bin/wbhook.rs`). Interpreting the
         // event stays here rather than in the little forwarder, so the rule
@@ -516,7 +880,203 @@ fn dispatch(
     }
 }
 
-fn with_snapshot(shared: &Shared, read: impl FnOnce(&Snapshot) -> Answer) -> Answer {
+/// Press keys in an agent's pane. `keys` is a list, each entry one of: a key
+/// name (`esc`, `ctrl-c`, `shift-tab`, … — see `agent_input::named_key`), a
+/// single character, `{"text": "…"}` for characters typed as they are, or
+/// `{"bytes": [27, …]}` for anything else. A bad entry refuses the lot: half
+/// a key sequence is worse than none.
+fn keys(params: &Value, shared: &Shared, commands: &UnboundedSender<RemoteCommand>) -> Answer {
+    let wanted = text_param(params, "agent")?;
+    let entries = match params.get("keys") {
+        Some(Value::Array(entries)) => entries.clone(),
+        Some(one @ (Value::String(_) | Value::Object(_))) => vec![one.clone()],
+        _ => return Err(("bad_params", "`keys` is required".into())),
+    };
+    let mut keys = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        let bytes = match entry {
+            Value::String(name) => crate::app::agent_input::named_key(name),
+            Value::Object(raw) => match (raw.get("text"), raw.get("bytes")) {
+                (Some(Value::String(text)), None) => Some(text.as_bytes().to_vec()),
+                (None, Some(Value::Array(bytes))) => bytes
+                    .iter()
+                    .map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok()))
+                    .collect(),
+                _ => None,
+            },
+            _ => None,
+        };
+        match bytes {
+            Some(bytes) if !bytes.is_empty() => keys.push(bytes),
+            _ => return Err(("bad_params", format!("not a key: {entry}"))),
+        }
+    }
+    let agent = with_snapshot(shared, |snapshot| {
+        let agent = find_agent(snapshot, &wanted)?;
+        if agent.status == "stopped" {
+            return Err(("not_running", format!("{} is stopped", agent.id)));
+        }
+        if agent.user_at {
+            return Err((
+                "user_focused",
+                format!("{} is in use at the desk; try again once it has been left a while", agent.id),
+            ));
+        }
+        Ok(agent.id.clone())
+    })?;
+    queue(commands, RemoteCommand::Keys { agent, keys })
+}
+
+/// What the agent's pane shows. The only read the snapshot cannot answer —
+/// publishing every screen every tick to serve the odd caller would cost far
+/// more than asking — so it goes to the event loop and waits briefly.
+fn screen(params: &Value, shared: &Shared, commands: &UnboundedSender<RemoteCommand>) -> Answer {
+    let wanted = text_param(params, "agent")?;
+    let agent = with_snapshot(shared, |snapshot| find_agent(snapshot, &wanted).map(|a| a.id.clone()))?;
+    let scrollback = match params.get("scrollback") {
+        None | Some(Value::Null) => 0,
+        Some(_) => int_param(params, "scrollback")?.clamp(0, u16::MAX as isize) as u16,
+    };
+    let (reply, answer) = std::sync::mpsc::channel();
+    commands
+        .send(RemoteCommand::Screen {
+            agent: agent.clone(),
+            scrollback,
+            reply,
+        })
+        .map_err(|_| ("shutting_down", "workbench is going away".to_string()))?;
+    match answer.recv_timeout(SCREEN_TIMEOUT) {
+        Ok(text) => Ok(json!({"agent": agent, "text": text})),
+        Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+            Err(("no_screen", format!("{agent} has no screen to read")))
+        }
+        Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+            Err(("unavailable", "the event loop did not answer in time".into()))
+        }
+    }
+}
+
+/// The conversation from the agent's journal, from where the caller's
+/// `cursor` left off. The cursor is a byte offset into the journal: absent
+/// (or 0) starts from the recent end, and the answer's `cursor` is what to
+/// send next time to be given only what was added since. `reset` says the
+/// journal was replaced underneath the cursor and this is a fresh read, not
+/// a continuation.
+fn messages(params: &Value, shared: &Shared) -> Answer {
+    let wanted = text_param(params, "agent")?;
+    let (agent, journal) = with_snapshot(shared, |snapshot| {
+        let agent = find_agent(snapshot, &wanted)?;
+        Ok((agent.id.clone(), agent.journal.clone()))
+    })?;
+    let Some((provider, path)) = journal else {
+        return Err((
+            "no_journal",
+            format!("{agent} has no conversation workbench can read; try agent.screen"),
+        ));
+    };
+    let from = match params.get("cursor") {
+        None | Some(Value::Null) => 0,
+        Some(value) => value
+            .as_u64()
+            .ok_or_else(|| ("bad_params", "`cursor` must be one a previous answer gave".to_string()))?,
+    };
+    let reset = from > std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    // The read is done here, on the socket's own thread: a first read parses
+    // megabytes of journal, which the event loop has no business waiting on.
+    let mut out = Vec::new();
+    let cursor = crate::remote::read_more(&path, provider, crate::remote::Cursor(from), &mut out);
+    if let Some(limit) = params.get("limit").and_then(Value::as_u64) {
+        let limit = limit as usize;
+        if out.len() > limit {
+            out.drain(..out.len() - limit);
+        }
+    }
+    Ok(json!({"agent": agent, "messages": out, "cursor": cursor.0, "reset": reset}))
+}
+
+/// `workbench grep` for a script: every open project, or the one `project`
+/// names, searched the same way (see `crate::grep`). Like `agent.messages`
+/// the reading happens on the socket's own thread — a search reads journals
+/// whole — and from the rosters on disk, since only they say where a
+/// SQLite-backed agent keeps its conversation.
+fn grep(params: &Value, shared: &Shared) -> Answer {
+    let ignore_case = params.get("ignore_case").and_then(Value::as_bool) == Some(true);
+    let pattern = crate::grep::pattern(&text_param(params, "pattern")?, ignore_case)
+        .map_err(|err| ("bad_params", err))?;
+    let since = match params.get("since").and_then(Value::as_str) {
+        Some(since) => Some(
+            crate::grep::parse_since(since, chrono::Utc::now()).map_err(|err| ("bad_params", err))?,
+        ),
+        None => None,
+    };
+    let context = params.get("context").and_then(Value::as_u64).unwrap_or(2) as usize;
+    let wanted = params.get("project").and_then(Value::as_str);
+    let projects = with_snapshot(shared, |snapshot| {
+        let ids: Vec<String> = snapshot
+            .projects
+            .iter()
+            .filter(|project| wanted.is_none_or(|wanted| project.id == wanted || project.name == wanted))
+            .map(|project| project.id.clone())
+            .collect();
+        match (wanted, ids.is_empty()) {
+            (Some(wanted), true) => Err(("no_such_project", format!("no project {wanted}"))),
+            _ => Ok(ids),
+        }
+    })?;
+    let rosters: Vec<_> = projects
+        .iter()
+        .filter_map(|id| crate::comms::load_roster(id).ok())
+        .collect();
+    let query = crate::grep::Query {
+        pattern,
+        since,
+        context,
+    };
+    let mut hits = crate::grep::search(&rosters, params.get("agent").and_then(Value::as_str), &query)
+        .map_err(|err| ("no_such_agent", err))?;
+    if let Some(limit) = params.get("limit").and_then(Value::as_u64) {
+        let limit = limit as usize;
+        if hits.len() > limit {
+            hits.drain(..hits.len() - limit);
+        }
+    }
+    Ok(json!({"hits": hits}))
+}
+
+/// Start an agent and answer with its short id once it is listed, so a script
+/// can create a worker and address it in the next line. Everything the event
+/// loop would reject quietly — an unknown project, a taken alias — is checked
+/// here first, so it comes back as an error rather than a timeout.
+fn new_agent(params: &Value, shared: &Shared, commands: &UnboundedSender<RemoteCommand>) -> Answer {
+    let wanted = text_param(params, "project")?;
+    let provider = text_param(params, "provider")?;
+    let options: crate::app::SpawnOptions = serde_json::from_value(params.clone())
+        .map_err(|err| ("bad_params", err.to_string()))?;
+    let project = with_snapshot(shared, |snapshot| {
+        crate::remote::vet_new_agent(snapshot, &wanted, &provider, &options)
+    })?;
+
+    let id = uuid::Uuid::new_v4();
+    queue(
+        commands,
+        RemoteCommand::NewAgent {
+            project,
+            provider,
+            id,
+            options,
+        },
+    )?;
+    let short = crate::models::Session::short_id_of(id);
+    if !crate::remote::wait_for_agent(shared, &short, crate::remote::AGENT_START_TIMEOUT) {
+        return Err(("not_started", format!("asked for {short}, but it has not started")));
+    }
+    Ok(json!({"accepted": true, "agent": short}))
+}
+
+fn with_snapshot<T>(
+    shared: &Shared,
+    read: impl FnOnce(&Snapshot) -> std::result::Result<T, (&'static str, String)>,
+) -> std::result::Result<T, (&'static str, String)> {
     match shared.lock() {
         Ok(snapshot) => read(&snapshot),
         Err(_) => Err(("unavailable", "state is momentarily unreadable".into())),
@@ -541,6 +1101,83 @@ fn text_param(params: &Value, key: &'static str) -> std::result::Result<String,
         .ok_or_else(|| ("bad_params", format!("`{key}` is required")))
 }
 
+/// A string or a list of them; absent is empty.
+fn list_param(params: &Value, key: &'static str) -> std::result::Result<Vec<String>, (&'static str, String)> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::String(one)) => Ok(vec![one.clone()]),
+        Some(Value::Array(many)) => many
+            .iter()
+            .map(|item| item.as_str().map(str::to_string))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| ("bad_params", format!("`{key}` must be strings"))),
+        Some(_) => Err(("bad_params", format!("`{key}` must be a string or a list of them"))),
+    }
+}
+
+fn int_param(params: &Value, key: &'static str) -> std::result::Result<isize, (&'static str, String)> {
+    params
+        .get(key)
+        .and_then(Value::as_i64)
+        .map(|n| n as isize)
+        .ok_or_else(|| ("bad_params", format!("`{key}` must be a whole number")))
+}
+
+/// The agent a lifecycle write names, if its status is one `allowed` accepts;
+/// otherwise the write is refused with `refusal`.
+fn agent_in_state(
+    shared: &Shared,
+    params: &Value,
+    allowed: impl Fn(&str) -> bool,
+    refusal: &'static str,
+) -> std::result::Result<String, (&'static str, String)> {
+    let wanted = text_param(params, "agent")?;
+    with_snapshot(shared, |snapshot| {
+        let agent = find_agent(snapshot, &wanted)?;
+        if !allowed(&agent.status) {
+            return Err((refusal, format!("{} is {}", agent.id, agent.status)));
+        }
+        Ok(agent.id.clone())
+    })
+}
+
+fn find_agent<'a>(
+    snapshot: &'a Snapshot,
+    wanted: &str,
+) -> std::result::Result<&'a crate::remote::AgentView, (&'static str, String)> {
+    snapshot
+        .agents
+        .iter()
+        .find(|agent| agent.id.eq_ignore_ascii_case(wanted))
+        .ok_or_else(|| ("no_such_agent", format!("no agent {wanted}")))
+}
+
+/// The agent and queue item a `todo.*` write names. Resolved here rather than
+/// in the event loop so a stale or mistyped id is an error the caller sees,
+/// not a no-op it has to guess at. Any unambiguous prefix of the id will do,
+/// the way git takes a short hash.
+fn find_todo(
+    shared: &Shared,
+    params: &Value,
+) -> std::result::Result<(String, uuid::Uuid), (&'static str, String)> {
+    let wanted = text_param(params, "agent")?;
+    let prefix = text_param(params, "todo")?.to_ascii_lowercase();
+    with_snapshot(shared, |snapshot| {
+        let agent = find_agent(snapshot, &wanted)?;
+        let matches: Vec<uuid::Uuid> = agent
+            .todos
+            .iter()
+            .filter(|todo| todo.id.starts_with(&prefix))
+            .filter_map(|todo| todo.id.parse().ok())
+            .collect();
+        match matches.as_slice() {
+            [id] => Ok((agent.id.clone(), *id)),
+            [] => Err(("no_such_todo", format!("{} has no item {prefix}", agent.id))),
+            _ => Err(("ambiguous_todo", format!("{prefix} matches {} items", matches.len()))),
+        }
+    })
+}
+
 fn to_value<T: Serialize>(value: T) -> Value {
     serde_json::to_value(value).unwrap_or(Value::Null)
 }
@@ -561,6 +1198,7 @@ fn summarize(agent: &crate::remote::AgentView) -> Value {
         "queued": agent.queued.len(),
         "paused": agent.paused,
         "blocked_on": agent.prompt.as_ref().map(|_| true).unwrap_or(false),
+        "cost_usd": agent.cost.map(|cost| cost.estimated_cost_usd),
     })
 }
 
@@ -574,21 +1212,40 @@ fn schema() -> Value {
             {"name": "agents.list", "params": [], "kind": "read"},
             {"name": "agent.get", "params": ["agent"], "kind": "read"},
             {"name": "projects.list", "params": [], "kind": "read"},
+            {"name": "agent.screen", "params": ["agent", "scrollback"], "kind": "read"},
+            {"name": "agent.messages", "params": ["agent", "cursor", "limit"], "kind": "read"},
+            {"name": "agents.grep", "params": ["pattern", "project", "agent", "since", "context", "ignore_case", "limit"], "kind": "read"},
             {"name": "agent.prompt", "params": ["agent", "text"], "kind": "write"},
             {"name": "agent.todo", "params": ["agent", "text"], "kind": "write"},
+            {"name": "todo.list", "params": ["agent"], "kind": "read"},
+            {"name": "todo.remove", "params": ["agent", "todo"], "kind": "write"},
+            {"name": "todo.move", "params": ["agent", "todo", "by"], "kind": "write"},
+            {"name": "todo.pause", "params": ["agent"], "kind": "write"},
+            {"name": "todo.resume", "params": ["agent"], "kind": "write"},
+            {"name": "todo.clear_done", "params": ["agent"], "kind": "write"},
             {"name": "agent.answer", "params": ["agent", "key"], "kind": "write"},
+            {"name": "agent.keys", "params": ["agent", "keys"], "kind": "write"},
             {"name": "agent.focus", "params": ["agent"], "kind": "write"},
-            {"name": "agent.new", "params": ["project", "provider"], "kind": "write"},
-            {"name": "events.subscribe", "params": [], "kind": "stream"},
+            {"name": "agent.stop", "params": ["agent"], "kind": "write"},
+            {"name": "agent.restart", "params": ["agent"], "kind": "write"},
+            {"name": "agent.kill", "params": ["agent"], "kind": "write"},
+            {"name": "agent.delete", "params": ["agent", "confirm"], "kind": "write"},
+            {"name": "agent.new", "params": ["project", "provider", "worktree", "dangerously_skip_permissions", "alias", "prompt", "todos", "successor_of"], "kind": "write"},
+            {"name": "events.subscribe", "params": ["project", "agent", "event", "since"], "kind": "stream"},
             {"name": "hook", "params": ["workspace", "session", "event", "payload"], "kind": "write"}
         ],
         "events": [
             "agent.added",
             "agent.removed",
             "agent.status_changed",
-            "agent.model_changed"
+            "agent.model_changed",
+            "agent.blocked",
+            "todo.dispatched",
+            "todo.done",
+            "consult.delivered",
+            "consult.answered"
         ],
-        "notes": "Reads answer from the last published snapshot (up to one tick old). Writes are queued for the event loop and answer {\"accepted\":true}."
+        "notes": "Reads answer from the last published snapshot (up to one tick old). Writes are queued for the event loop and answer {\"accepted\":true}. events.subscribe takes only optional params: project, agent and event narrow the feed (each a string or a list; `todo.*` names a family), and since replays buffered events numbered after it."
     })
 }
 
@@ -603,9 +1260,12 @@ mod tests {
                 id: "p1".into(),
                 name: "workbench".into(),
                 servers: Vec::new(),
+                cost: Default::default(),
             }],
             agents,
             open: None,
+            consults: Vec::new(),
+            cost: Default::default(),
             at: 0,
         }))
     }
@@ -623,6 +1283,7 @@ mod tests {
             running: None,
             steps: Vec::new(),
             queued: Vec::new(),
+            todos: Vec::new(),
             paused: false,
             holding: None,
             prompt: None,
@@ -631,6 +1292,9 @@ mod tests {
             msg_reset: false,
             tail: Vec::new(),
             finished_ago: None,
+            cost: None,
+            journal: None,
+            user_at: false,
         }
     }
 
@@ -701,12 +1365,270 @@ mod tests {
         );
     }
 
+    /// `agent.new` answers with the id of the agent it started, which only
+    /// exists once the event loop has made it: the answer has to wait for the
+    /// snapshot to list it.
+    #[test]
+    fn a_new_agent_is_answered_with_its_id_once_it_exists() {
+        let shared = snapshot_with(Vec::new());
+        let (tx, mut rx) = channel_pair();
+        let event_loop = {
+            let shared = shared.clone();
+            std::thread::spawn(move || {
+                let Some(RemoteCommand::NewAgent {
+                    project, id, options, ..
+                }) = rx.blocking_recv()
+                else {
+                    panic!("expected a new agent");
+                };
+                assert_eq!(project, "p1");
+                assert!(options.worktree);
+                assert_eq!(options.alias.as_deref(), Some("reviewer"));
+                assert_eq!(options.todos, vec!["read the diff".to_string()]);
+                let mut started = agent(&crate::models::Session::short_id_of(id), "idle");
+                started.alias = options.alias;
+                shared.lock().unwrap().agents.push(started);
+            })
+        };
+
+        let answer = dispatch(
+            "agent.new",
+            &json!({
+                "project": "workbench",
+                "provider": "claude",
+                "worktree": true,
+                "alias": "reviewer",
+                "todos": ["read the diff"],
+            }),
+            &shared,
+            &tx,
+        )
+        .unwrap();
+        event_loop.join().unwrap();
+
+        let short = answer["agent"].as_str().unwrap();
+        assert_eq!(shared.lock().unwrap().agents[0].id, short);
+
+        // The name is now taken in that project.
+        let again = dispatch(
+            "agent.new",
+            &json!({"project": "p1", "provider": "claude", "alias": "Reviewer"}),
+            &shared,
+            &tx,
+        );
+        assert_eq!(again.unwrap_err().0, "alias_taken");
+    }
+
+    #[test]
+    fn a_new_agent_that_could_never_start_is_refused_up_front() {
+        let shared = snapshot_with(Vec::new());
+        let (tx, mut rx) = channel_pair();
+        let new = |params: Value| dispatch("agent.new", &params, &shared, &tx).unwrap_err().0;
+
+        assert_eq!(new(json!({"project": "elsewhere", "provider": "claude"})), "no_such_project");
+        assert_eq!(new(json!({"project": "p1", "provider": "gemini"})), "bad_params");
+        assert_eq!(
+            new(json!({"project": "p1", "provider": "claude", "alias": "two words"})),
+            "bad_params"
+        );
+        assert_eq!(
+            new(json!({"project": "p1", "provider": "claude", "worktree": "yes"})),
+            "bad_params"
+        );
+        assert_eq!(
+            new(json!({"project": "p1", "provider": "codex", "successor_of": "nobody00"})),
+            "no_such_agent"
+        );
+        assert!(rx.try_recv().is_err(), "a refused spawn queues nothing");
+    }
+
+    /// Ending an agent over the socket has the session list's guards, and a
+    /// refusal says why instead of queuing a write the event loop would drop.
+    #[test]
+    fn agents_are_ended_with_the_guards_the_desk_has() {
+        let shared = snapshot_with(vec![agent("abc12345", "working"), agent("def67890", "stopped")]);
+        let (tx, mut rx) = channel_pair();
+        let call = |method: &str, params: Value| dispatch(method, &params, &shared, &tx);
+
+        assert_eq!(call("agent.restart", json!({"agent": "abc12345"})).unwrap_err().0, "not_stopped");
+        assert_eq!(call("agent.stop", json!({"agent": "def67890"})).unwrap_err().0, "not_running");
+        assert_eq!(call("agent.delete", json!({"agent": "def67890"})).unwrap_err().0, "unconfirmed");
+        assert!(rx.try_recv().is_err(), "a refused write queues nothing");
+
+        call("agent.stop", json!({"agent": "abc12345"})).unwrap();
+        call("agent.restart", json!({"agent": "def67890"})).unwrap();
+        call("agent.delete", json!({"agent": "def67890", "confirm": true})).unwrap();
+        assert!(matches!(rx.try_recv().unwrap(), RemoteCommand::Stop { agent } if agent == "abc12345"));
+        assert!(matches!(rx.try_recv().unwrap(), RemoteCommand::Restart { agent } if agent == "def67890"));
+        assert!(matches!(rx.try_recv().unwrap(), RemoteCommand::Delete { agent } if agent == "def67890"));
+    }
+
+    /// A script reading an agent's conversation pays for each message once:
+    /// the cursor it is handed back gets it only what was written since.
+    #[test]
+    fn messages_are_read_on_from_the_cursor() {
+        use std::io::Write as _;
+        let mut journal = tempfile::NamedTempFile::new().unwrap();
+        writeln!(journal, r#"{{"type":"user","message":{{"role":"user","content":"first"}}}}"#).unwrap();
+        let mut talking = agent("abc12345", "idle");
+        talking.journal = Some((crate::agent_tasks::Provider::Claude, journal.path().to_path_buf()));
+        let shared = snapshot_with(vec![talking, agent("def67890", "idle")]);
+        let (tx, _rx) = channel_pair();
+        let read = |params: Value| dispatch("agent.messages", &params, &shared, &tx);
+
+        let first = read(json!({"agent": "abc12345"})).unwrap();
+        assert_eq!(first["messages"][0]["text"], "first");
+        assert_eq!(first["reset"], false);
+
+        writeln!(journal, r#"{{"type":"user","message":{{"role":"user","content":"second"}}}}"#).unwrap();
+        let next = read(json!({"agent": "abc12345", "cursor": first["cursor"]})).unwrap();
+        let texts: Vec<&str> = next["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["text"].as_str().unwrap())
+            .collect();
+        assert_eq!(texts, vec!["second"]);
+
+        let stale = read(json!({"agent": "abc12345", "cursor": 1_000_000})).unwrap();
+        assert_eq!(stale["reset"], true);
+        assert_eq!(read(json!({"agent": "def67890"})).unwrap_err().0, "no_journal");
+    }
+
+    /// A mistyped search is refused before anything is read.
+    #[test]
+    fn a_search_with_a_broken_pattern_or_window_is_refused() {
+        let shared = snapshot_with(vec![agent("abc12345", "idle")]);
+        let (tx, _rx) = channel_pair();
+        let grep = |params: Value| dispatch("agents.grep", &params, &shared, &tx).unwrap_err();
+
+        assert_eq!(grep(json!({"pattern": "migr(ation"})).0, "bad_params");
+        assert_eq!(grep(json!({"pattern": "migration", "since": "lately"})).0, "bad_params");
+        assert_eq!(grep(json!({"pattern": "migration", "project": "nope"})).0, "no_such_project");
+    }
+
+    /// The screen lives with the event loop, so it is asked for and waited
+    /// on; an agent the loop cannot find answers as an error, not a hang.
+    #[test]
+    fn a_screen_is_fetched_from_the_event_loop() {
+        let shared = snapshot_with(vec![agent("abc12345", "working"), agent("def67890", "stopped")]);
+        let (tx, mut rx) = channel_pair();
+        let event_loop = std::thread::spawn(move || {
+            for _ in 0..2 {
+                match rx.blocking_recv() {
+                    Some(RemoteCommand::Screen {
+                        agent, scrollback, reply,
+                    }) if agent == "abc12345" => {
+                        assert_eq!(scrollback, 200);
+                        reply.send("$ cargo test\nok".into()).unwrap();
+                    }
+                    // Dropping the sender unanswered: no such screen.
+                    Some(RemoteCommand::Screen { .. }) => {}
+                    other => panic!("expected a screen read, got {other:?}"),
+                }
+            }
+        });
+
+        let read = |params: Value| dispatch("agent.screen", &params, &shared, &tx);
+        let screen = read(json!({"agent": "abc12345", "scrollback": 200})).unwrap();
+        assert_eq!(screen["text"], "$ cargo test\nok");
+        assert_eq!(read(json!({"agent": "def67890"})).unwrap_err().0, "no_screen");
+        event_loop.join().unwrap();
+    }
+
+    /// Keys are resolved to bytes before anything is queued, so a typo in
+    /// the middle of a sequence refuses it whole rather than sending the
+    /// first half. A pane you are at is left to you.
+    #[test]
+    fn keys_are_checked_whole_and_kept_off_a_pane_in_use() {
+        let mut watched = agent("def67890", "idle");
+        watched.user_at = true;
+        let shared = snapshot_with(vec![agent("abc12345", "blocked"), watched]);
+        let (tx, mut rx) = channel_pair();
+        let press = |agent: &str, keys: Value| {
+            dispatch("agent.keys", &json!({"agent": agent, "keys": keys}), &shared, &tx)
+        };
+
+        press("abc12345", json!(["esc", {"text": "/model"}, "enter", {"bytes": [3]}])).unwrap();
+        match rx.try_recv().unwrap() {
+            RemoteCommand::Keys { agent, keys } => {
+                assert_eq!(agent, "abc12345");
+                assert_eq!(keys, vec![vec![0x1b], b"/model".to_vec(), vec![b'\r'], vec![3]]);
+            }
+            other => panic!("expected keys, got {other:?}"),
+        }
+
+        assert_eq!(press("abc12345", json!(["esc", "escpae"])).unwrap_err().0, "bad_params");
+        assert_eq!(press("abc12345", json!([{"bytes": [300]}])).unwrap_err().0, "bad_params");
+        assert_eq!(press("def67890", json!(["ctrl-c"])).unwrap_err().0, "user_focused");
+        assert!(rx.try_recv().is_err(), "a refused sequence queues nothing");
+    }
+
+    /// A queue item is named by any unique prefix of its id, and resolved
+    /// before the write is queued — a stale id has to come back as an error,
+    /// because the event loop has no way to answer.
+    #[test]
+    fn a_todo_is_named_by_a_prefix_of_its_id() {
+        let first = uuid::Uuid::parse_str("aaaa1111-0000-0000-0000-000000000000").unwrap();
+        let second = uuid::Uuid::parse_str("aaaa2222-0000-0000-0000-000000000000").unwrap();
+        let mut busy = agent("abc12345", "working");
+        busy.todos = vec![
+            crate::remote::TodoView {
+                id: first.to_string(),
+                text: "write the tests".into(),
+                state: crate::models::TodoState::Running,
+                kind: Default::default(),
+            },
+            crate::remote::TodoView {
+                id: second.to_string(),
+                text: "update the docs".into(),
+                state: crate::models::TodoState::Pending,
+                kind: Default::default(),
+            },
+        ];
+        let shared = snapshot_with(vec![busy]);
+        let (tx, mut rx) = channel_pair();
+
+        let listed = dispatch("todo.list", &json!({"agent": "abc12345"}), &shared, &tx).unwrap();
+        assert_eq!(listed["items"][1]["id"], second.to_string());
+        assert_eq!(listed["items"][1]["state"], "pending");
+
+        dispatch(
+            "todo.move",
+            &json!({"agent": "abc12345", "todo": "AAAA2", "by": -1}),
+            &shared,
+            &tx,
+        )
+        .unwrap();
+        match rx.try_recv().unwrap() {
+            RemoteCommand::TodoMove { agent, todo, by } => {
+                assert_eq!(agent, "abc12345");
+                assert_eq!(todo, second);
+                assert_eq!(by, -1);
+            }
+            other => panic!("expected a move, got {other:?}"),
+        }
+
+        let remove = |todo: &str| {
+            dispatch(
+                "todo.remove",
+                &json!({"agent": "abc12345", "todo": todo}),
+                &shared,
+                &tx,
+            )
+        };
+        assert_eq!(remove("aaaa").unwrap_err().0, "ambiguous_todo");
+        assert_eq!(remove("ffff").unwrap_err().0, "no_such_todo");
+        assert!(rx.try_recv().is_err(), "a refused write queues nothing");
+    }
+
     /// The point of the socket: a caller can wait to be told, rather than ask
     /// every second. Only the fields an event is about may fire one.
     #[test]
     fn a_status_change_is_pushed_to_subscribers() {
         let hub = EventHub::default();
-        let events = hub.subscribe();
+        let connection = Arc::new(());
+        let events = hub.subscribe(EventFilter::default(), None, &connection).events;
         let mut marks = EventState::default();
 
         let first = Snapshot {
@@ -714,7 +1636,7 @@ mod tests {
             ..Default::default()
         };
         publish_events(&hub, &mut marks, &first);
-        let added: Value = serde_json::from_str(&events.recv().unwrap()).unwrap();
+        let added: Value = serde_json::from_str(&events.recv().unwrap().1).unwrap();
         assert_eq!(added["event"], "agent.added");
         assert_eq!(added["data"]["agent"], "abc12345");
 
@@ -743,22 +1665,266 @@ mod tests {
                 ..Default::default()
             },
         );
-        let changed: Value = serde_json::from_str(&events.recv().unwrap()).unwrap();
+        let changed: Value = serde_json::from_str(&events.recv().unwrap().1).unwrap();
         assert_eq!(changed["event"], "agent.status_changed");
         assert_eq!(changed["data"]["agent"], "abc12345");
         assert_eq!(changed["data"]["from"], "working");
         assert_eq!(changed["data"]["to"], "idle");
 
         publish_events(&hub, &mut marks, &Snapshot::default());
-        let removed: Value = serde_json::from_str(&events.recv().unwrap()).unwrap();
+        let removed: Value = serde_json::from_str(&events.recv().unwrap().1).unwrap();
         assert_eq!(removed["event"], "agent.removed");
     }
 
+    /// Progress, not just status: a script driving a queue wants to hear an
+    /// item was handed over and finished, and an asker wants to hear its
+    /// consult arrived and was answered — none of which moves `status`.
+    #[test]
+    fn queue_and_consult_progress_are_pushed_to_subscribers() {
+        let hub = EventHub::default();
+        let connection = Arc::new(());
+        let events = hub.subscribe(EventFilter::default(), None, &connection).events;
+        let by_name = EventFilter::from_params(&json!({"project": "workbench"})).unwrap();
+        let by_name = hub.subscribe(by_name, None, &connection).events;
+        let mut marks = EventState::default();
+        let todo = |state| crate::remote::TodoView {
+            id: "aaaa1111-0000-0000-0000-000000000000".into(),
+            text: "write the tests".into(),
+            state,
+            kind: Default::default(),
+        };
+        let consult = |status: &str| crate::remote::ConsultView {
+            ticket: "c1234".into(),
+            project_id: "p1".into(),
+            from: "abc12345".into(),
+            to: "def67890".into(),
+            status: status.into(),
+        };
+        let tick = |marks: &mut EventState, state, status: &str| {
+            let mut worker = agent("abc12345", "working");
+            worker.todos = vec![todo(state)];
+            publish_events(
+                &hub,
+                marks,
+                &Snapshot {
+                    agents: vec![worker],
+                    consults: vec![consult(status)],
+                    ..snapshot_with(Vec::new()).lock().unwrap().clone()
+                },
+            );
+        };
+        let next = || -> Value { serde_json::from_str(&events.try_recv().unwrap().1).unwrap() };
+
+        tick(&mut marks, TodoState::Pending, "queued");
+        assert_eq!(next()["event"], "agent.added");
+        assert!(events.try_recv().is_err(), "a queue as it stands is not news");
+
+        tick(&mut marks, TodoState::Running, "delivered");
+        let dispatched = next();
+        assert_eq!(dispatched["event"], "todo.dispatched");
+        assert_eq!(dispatched["data"]["text"], "write the tests");
+        let delivered = next();
+        assert_eq!(delivered["event"], "consult.delivered");
+        assert_eq!(delivered["data"]["to"], "def67890");
+        assert_eq!(delivered["data"]["project"], "workbench");
+
+        tick(&mut marks, TodoState::Done, "answered");
+        assert_eq!(next()["event"], "todo.done");
+        let answered = next();
+        assert_eq!(answered["event"], "consult.answered");
+        assert_eq!(answered["data"]["ticket"], "c1234");
+
+        tick(&mut marks, TodoState::Done, "answered");
+        assert!(events.try_recv().is_err(), "nothing moved, nothing said");
+
+        // A project filter given by name hears consults, which only carry
+        // their project's id of their own.
+        let heard: Vec<String> = by_name
+            .try_iter()
+            .map(|(_, line)| serde_json::from_str::<Value>(&line).unwrap()["event"].to_string())
+            .collect();
+        assert!(heard.contains(&"\"consult.delivered\"".to_string()), "{heard:?}");
+        assert!(heard.contains(&"\"consult.answered\"".to_string()), "{heard:?}");
+    }
+
+    /// The question travels with the event, so a caller can answer it
+    /// without a second round trip to find out what was asked.
+    #[test]
+    fn a_blocked_agent_says_what_it_is_asking() {
+        let hub = EventHub::default();
+        let connection = Arc::new(());
+        let events = hub.subscribe(EventFilter::default(), None, &connection).events;
+        let mut marks = EventState::default();
+        publish_events(
+            &hub,
+            &mut marks,
+            &Snapshot {
+                agents: vec![agent("abc12345", "working")],
+                ..Default::default()
+            },
+        );
+        events.try_recv().unwrap();
+
+        let mut asking = agent("abc12345", "blocked");
+        asking.prompt = Some(Prompt {
+            lines: vec!["Allow cargo test?".into()],
+            options: Vec::new(),
+        });
+        publish_events(
+            &hub,
+            &mut marks,
+            &Snapshot {
+                agents: vec![asking],
+                ..Default::default()
+            },
+        );
+
+        let changed: Value = serde_json::from_str(&events.try_recv().unwrap().1).unwrap();
+        assert_eq!(changed["event"], "agent.status_changed");
+        let blocked: Value = serde_json::from_str(&events.try_recv().unwrap().1).unwrap();
+        assert_eq!(blocked["event"], "agent.blocked");
+        assert_eq!(blocked["data"]["prompt"]["lines"][0], "Allow cargo test?");
+    }
+
+    /// A script watching one agent should not have to read, parse and throw
+    /// away every other agent's traffic to find it.
+    #[test]
+    fn a_subscription_hears_only_what_it_asked_for() {
+        let hub = EventHub::default();
+        let connection = Arc::new(());
+        let filter = EventFilter::from_params(&json!({
+            "agent": "ABC12345",
+            "event": ["todo.*", "consult.answered"],
+        }))
+        .unwrap();
+        let events = hub.subscribe(filter, None, &connection).events;
+
+        hub.emit("todo.done", json!({"agent": "def67890", "project": "workbench"}));
+        hub.emit("agent.blocked", json!({"agent": "abc12345", "project": "workbench"}));
+        hub.emit("todo.dispatched", json!({"agent": "abc12345", "project": "workbench"}));
+        hub.emit("consult.answered", json!({"from": "abc12345", "to": "def67890"}));
+
+        let heard: Vec<String> = events
+            .try_iter()
+            .map(|(_, line)| serde_json::from_str::<Value>(&line).unwrap()["event"].to_string())
+            .collect();
+        assert_eq!(heard, vec!["\"todo.dispatched\"", "\"consult.answered\""]);
+
+        assert_eq!(
+            EventFilter::from_params(&json!({"event": 7})).unwrap_err().0,
+            "bad_params"
+        );
+    }
+
+    /// A caller that lost its connection comes back with the last number it
+    /// saw and is handed what it missed — or told plainly that it cannot be,
+    /// rather than resuming as if nothing happened in between.
+    #[test]
+    fn a_reconnecting_subscriber_catches_up_from_since() {
+        let hub = EventHub::default();
+        let connection = Arc::new(());
+        for n in 0..5 {
+            hub.emit("todo.done", json!({"agent": "abc12345", "n": n}));
+        }
+
+        let resumed = hub.subscribe(EventFilter::default(), Some(3), &connection);
+        assert_eq!(resumed.seq, 5);
+        assert!(!resumed.gap);
+        let seqs: Vec<u64> = resumed
+            .backlog
+            .iter()
+            .map(|(_, line)| serde_json::from_str::<Value>(line).unwrap()["seq"].as_u64().unwrap())
+            .collect();
+        assert_eq!(seqs, vec![4, 5]);
+
+        // Live events carry on from there, numbered.
+        hub.emit("todo.done", json!({"agent": "abc12345"}));
+        let live: Value = serde_json::from_str(&resumed.events.try_recv().unwrap().1).unwrap();
+        assert_eq!(live["seq"], 6);
+
+        // Further back than the buffer reaches, or a number from a previous
+        // run of workbench: a gap, said out loud.
+        for _ in 0..REPLAY_EVENTS {
+            hub.emit("todo.done", json!({}));
+        }
+        assert!(hub.subscribe(EventFilter::default(), Some(3), &connection).gap);
+        assert!(hub.subscribe(EventFilter::default(), Some(1_000_000), &connection).gap);
+    }
+
+    /// A reader that stops reading is cut loose instead of queueing events
+    /// without end.
+    #[test]
+    fn a_subscriber_that_stops_reading_is_let_go() {
+        let hub = EventHub::default();
+        let connection = Arc::new(());
+        let events = hub.subscribe(EventFilter::default(), None, &connection).events;
+
+        for _ in 0..=MAX_PENDING_EVENTS {
+            hub.emit("todo.done", json!({}));
+        }
+
+        assert_eq!(events.try_iter().count(), MAX_PENDING_EVENTS);
+        assert!(matches!(
+            events.try_recv(),
+            Err(std::sync::mpsc::TryRecvError::Disconnected)
+        ));
+    }
+
+    /// The notice that ends a dropped feed says where to resume: the last
+    /// event written, whatever the filter let through.
+    #[test]
+    fn a_dropped_feed_says_where_to_resume() {
+        let hub = EventHub::default();
+        let connection = Arc::new(());
+        hub.emit("todo.done", json!({}));
+        let filter = EventFilter::from_params(&json!({"event": "todo.done"})).unwrap();
+        let subscription = hub.subscribe(filter, None, &connection);
+        for _ in 0..MAX_PENDING_EVENTS {
+            hub.emit("agent.added", json!({}));
+            hub.emit("todo.done", json!({}));
+        }
+        hub.emit("todo.done", json!({}));
+
+        let mut out = Vec::new();
+        write_events(&mut out, subscription).unwrap();
+        let lines: Vec<Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), MAX_PENDING_EVENTS + 1);
+        let notice = lines.last().unwrap();
+        assert_eq!(notice["event"], "events.dropped");
+        assert_eq!(notice["seq"], lines[MAX_PENDING_EVENTS - 1]["seq"]);
+        assert_eq!(notice["seq"], 1 + 2 * MAX_PENDING_EVENTS as u64);
+    }
+
+    /// A caller that hangs up is let go on the next event of any kind, not
+    /// only the next one its filter would have passed on.
+    #[test]
+    fn a_closed_connection_is_pruned_whatever_it_listened_for() {
+        let hub = EventHub::default();
+        let connection = Arc::new(());
+        let filter = EventFilter::from_params(&json!({"event": "consult.answered"})).unwrap();
+        let events = hub.subscribe(filter, None, &connection).events;
+
+        hub.emit("todo.done", json!({}));
+        assert!(matches!(events.try_recv(), Err(std::sync::mpsc::TryRecvError::Empty)));
+
+        drop(connection);
+        hub.emit("todo.done", json!({}));
+        assert!(matches!(
+            events.try_recv(),
+            Err(std::sync::mpsc::TryRecvError::Disconnected)
+        ));
+    }
+
     /// With nobody subscribed the marks still have to advance, or the first
     /// subscriber is handed every agent that ever existed as "news".
     #[test]
     fn a_late_subscriber_does_not_inherit_the_backlog() {
         let hub = EventHub::default();
+        let connection = Arc::new(());
         let mut marks = EventState::default();
         publish_events(
             &hub,
@@ -769,7 +1935,7 @@ mod tests {
             },
         );
 
-        let events = hub.subscribe();
+        let events = hub.subscribe(EventFilter::default(), None, &connection).events;
         publish_events(
             &hub,
             &mut marks,
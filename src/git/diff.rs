@@ -0,0 +1,369 @@
+//! Full diffs of an agent's branch, for reviewing it before a merge.
+//!
+//! `worktree::get_diff_shortstat` is enough for the +/- counts in the
+//! session list, but deciding whether to merge means reading the change.
+//! This module runs `git diff` in the agent's checkout and parses the
+//! unified output into files and hunks the diff viewer can page through.
+
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Untracked files larger than this are listed without their contents; the
+/// viewer is for reading code, not for paging through a generated blob.
+const MAX_UNTRACKED_BYTES: u64 = 256 * 1024;
+
+/// How a file changed on the branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Added,
+    Deleted,
+    Modified,
+    Renamed,
+}
+
+impl FileStatus {
+    /// One-letter marker for the file list, as `git status --short` has it.
+    pub fn marker(&self) -> char {
+        match self {
+            Self::Added => 'A',
+            Self::Deleted => 'D',
+            Self::Modified => 'M',
+            Self::Renamed => 'R',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+    /// `\ No newline at end of file` — shown, but neither side's content.
+    Note,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    /// The line without its leading `+`/`-`/space marker.
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// The `@@ -a,b +c,d @@ context` line, verbatim.
+    pub header: String,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    /// Path on the branch (the new path for a rename, the old one for a
+    /// deletion).
+    pub path: String,
+    /// Where a renamed file came from.
+    pub old_path: Option<String>,
+    pub status: FileStatus,
+    /// Git said "Binary files differ"; there are no hunks to show.
+    pub binary: bool,
+    pub hunks: Vec<Hunk>,
+}
+
+impl FileDiff {
+    fn new(path: String) -> Self {
+        Self {
+            path,
+            old_path: None,
+            status: FileStatus::Modified,
+            binary: false,
+            hunks: Vec::new(),
+        }
+    }
+
+    pub fn additions(&self) -> usize {
+        self.count(DiffLineKind::Added)
+    }
+
+    pub fn deletions(&self) -> usize {
+        self.count(DiffLineKind::Removed)
+    }
+
+    fn count(&self, kind: DiffLineKind) -> usize {
+        self.hunks
+            .iter()
+            .flat_map(|h| &h.lines)
+            .filter(|l| l.kind == kind)
+            .count()
+    }
+
+    /// Rows the file takes on screen: each hunk's header, then its lines.
+    /// The viewer scrolls and jumps in these units, so this and
+    /// [`FileDiff::hunk_rows`] are the one definition of the layout.
+    pub fn rows(&self) -> usize {
+        self.hunks.iter().map(|h| 1 + h.lines.len()).sum()
+    }
+
+    /// The row each hunk's header sits on.
+    pub fn hunk_rows(&self) -> Vec<usize> {
+        let mut row = 0;
+        self.hunks
+            .iter()
+            .map(|h| {
+                let start = row;
+                row += 1 + h.lines.len();
+                start
+            })
+            .collect()
+    }
+}
+
+/// Everything `checkout` has changed since it diverged from `base` — the
+/// branch's commits plus whatever is still uncommitted, untracked files
+/// included. A merge from workbench commits all of it first, so reviewing
+/// only the committed part (`base...branch`) would hide exactly the work an
+/// agent most often leaves lying around.
+pub fn branch_diff(checkout: &Path, base: &str) -> Result<Vec<FileDiff>> {
+    let merge_base = git(checkout, &["merge-base", base, "HEAD"])
+        .with_context(|| format!("no common history with {base}"))?;
+    let merge_base = merge_base.trim();
+
+    let patch = git(
+        checkout,
+        &["diff", "--no-color", "--no-ext-diff", "-M", merge_base, "--"],
+    )?;
+    let mut files = parse_diff(&patch);
+
+    let untracked = git(checkout, &["ls-files", "--others", "--exclude-standard", "-z"])?;
+    for path in untracked.split('\0').filter(|p| !p.is_empty()) {
+        files.push(untracked_file(checkout, path));
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| format!("Failed to execute git {}", args[0]))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git {} failed: {}", args[0], stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// An untracked file as the add it will become once committed.
+fn untracked_file(checkout: &Path, path: &str) -> FileDiff {
+    let mut file = FileDiff::new(path.to_string());
+    file.status = FileStatus::Added;
+
+    let full = checkout.join(path);
+    let too_big = std::fs::metadata(&full)
+        .map(|m| m.len() > MAX_UNTRACKED_BYTES)
+        .unwrap_or(true);
+    let text = if too_big {
+        None
+    } else {
+        std::fs::read(&full)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+    };
+    let Some(text) = text else {
+        file.binary = true;
+        return file;
+    };
+
+    let lines: Vec<DiffLine> = text
+        .lines()
+        .map(|line| DiffLine {
+            kind: DiffLineKind::Added,
+            text: line.to_string(),
+        })
+        .collect();
+    if !lines.is_empty() {
+        file.hunks.push(Hunk {
+            header: format!("@@ -0,0 +1,{} @@ (untracked)", lines.len()),
+            lines,
+        });
+    }
+    file
+}
+
+/// Parse `git diff` output into files. Anything unrecognised between files
+/// (index lines, mode changes) is skipped rather than treated as an error:
+/// the viewer should show what it can of a diff, never nothing.
+pub fn parse_diff(patch: &str) -> Vec<FileDiff> {
+    let mut files: Vec<FileDiff> = Vec::new();
+
+    for line in patch.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            files.push(FileDiff::new(header_path(rest)));
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+
+        if line.starts_with("@@") {
+            file.hunks.push(Hunk {
+                header: line.to_string(),
+                lines: Vec::new(),
+            });
+            continue;
+        }
+        if let Some(hunk) = file.hunks.last_mut() {
+            let (kind, text) = match line.as_bytes().first() {
+                Some(b'+') => (DiffLineKind::Added, &line[1..]),
+                Some(b'-') => (DiffLineKind::Removed, &line[1..]),
+                Some(b' ') => (DiffLineKind::Context, &line[1..]),
+                Some(b'\\') => (DiffLineKind::Note, line),
+                // An empty line is a context line whose lone space was
+                // stripped by an editor or a transport along the way.
+                None => (DiffLineKind::Context, ""),
+                _ => continue,
+            };
+            hunk.lines.push(DiffLine {
+                kind,
+                text: text.to_string(),
+            });
+            continue;
+        }
+
+        // File header lines, before the first hunk.
+        if line.starts_with("new file mode") {
+            file.status = FileStatus::Added;
+        } else if line.starts_with("deleted file mode") {
+            file.status = FileStatus::Deleted;
+        } else if let Some(from) = line.strip_prefix("rename from ") {
+            file.status = FileStatus::Renamed;
+            file.old_path = Some(from.to_string());
+        } else if let Some(to) = line.strip_prefix("rename to ") {
+            file.path = to.to_string();
+        } else if line.starts_with("Binary files ") {
+            file.binary = true;
+        } else if let Some(to) = line.strip_prefix("+++ b/") {
+            file.path = to.to_string();
+        } else if let Some(from) = line.strip_prefix("--- a/") {
+            if file.status == FileStatus::Deleted {
+                file.path = from.to_string();
+            }
+        }
+    }
+
+    files
+}
+
+/// The path from `a/x b/x`. Both halves are the same unless the file was
+/// renamed, and then the `rename to` line that follows corrects it, so
+/// taking the second half is right either way.
+fn header_path(rest: &str) -> String {
+    match rest.rfind(" b/") {
+        Some(idx) => rest[idx + 3..].to_string(),
+        None => rest.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATCH: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,4 @@ mod a;
+ fn one() {}
+-fn two() {}
++fn two() -> u8 { 2 }
++fn three() {}
+
+@@ -10,2 +11,2 @@
+-old
++new
+\\ No newline at end of file
+diff --git a/gone.txt b/gone.txt
+deleted file mode 100644
+index 3333333..0000000
+--- a/gone.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-bye
+diff --git a/old.rs b/new.rs
+similarity index 100%
+rename from old.rs
+rename to new.rs
+diff --git a/logo.png b/logo.png
+new file mode 100644
+Binary files /dev/null and b/logo.png differ
+";
+
+    /// Every file shape the viewer has to show — edits with several hunks,
+    /// deletions, pure renames and binaries — comes out of one patch with
+    /// the right path, status and line counts.
+    #[test]
+    fn parses_files_hunks_and_statuses_from_one_patch() {
+        let files = parse_diff(PATCH);
+        assert_eq!(files.len(), 4);
+
+        let lib = &files[0];
+        assert_eq!(lib.path, "src/lib.rs");
+        assert_eq!(lib.status, FileStatus::Modified);
+        assert_eq!(lib.hunks.len(), 2);
+        assert_eq!(lib.hunks[0].header, "@@ -1,3 +1,4 @@ mod a;");
+        assert_eq!((lib.additions(), lib.deletions()), (3, 2));
+        // The blank context line survives, and the note is not content.
+        assert_eq!(lib.hunks[0].lines.last().unwrap().kind, DiffLineKind::Context);
+        assert_eq!(lib.hunks[1].lines.last().unwrap().kind, DiffLineKind::Note);
+        assert_eq!(lib.hunk_rows(), vec![0, 6]);
+        assert_eq!(lib.rows(), 10);
+
+        assert_eq!(files[1].path, "gone.txt");
+        assert_eq!(files[1].status, FileStatus::Deleted);
+        assert_eq!(files[1].deletions(), 1);
+
+        assert_eq!(files[2].path, "new.rs");
+        assert_eq!(files[2].old_path.as_deref(), Some("old.rs"));
+        assert_eq!(files[2].status, FileStatus::Renamed);
+        assert!(files[2].hunks.is_empty());
+
+        assert_eq!(files[3].status, FileStatus::Added);
+        assert!(files[3].binary);
+    }
+
+    /// The review has to show what a merge would bring in, and a merge
+    /// commits the uncommitted and untracked work too — so the diff does.
+    #[test]
+    fn branch_diff_includes_uncommitted_and_untracked_work() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = dir.path();
+        for args in [
+            &["init"][..],
+            &["config", "user.email", "test@test.com"],
+            &["config", "user.name", "Test"],
+        ] {
+            git(repo, args).unwrap();
+        }
+        std::fs::write(repo.join("a.txt"), "one\n").unwrap();
+        git(repo, &["add", "."]).unwrap();
+        git(repo, &["commit", "-m", "initial"]).unwrap();
+        let base = git(repo, &["rev-parse", "HEAD"]).unwrap();
+
+        git(repo, &["checkout", "-b", "agent"]).unwrap();
+        std::fs::write(repo.join("a.txt"), "one\ntwo\n").unwrap();
+        git(repo, &["commit", "-am", "two"]).unwrap();
+        std::fs::write(repo.join("a.txt"), "one\ntwo\nthree\n").unwrap();
+        std::fs::write(repo.join("b.txt"), "fresh\n").unwrap();
+
+        let files = branch_diff(repo, base.trim()).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.txt", "b.txt"]);
+        assert_eq!(files[0].additions(), 2);
+        assert_eq!(files[1].status, FileStatus::Added);
+        assert_eq!(files[1].additions(), 1);
+    }
+}
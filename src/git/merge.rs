@@ -0,0 +1,345 @@
+//! The git steps behind the merge strategies other than a plain merge, and
+//! behind cleaning up after one that stopped on conflicts.
+//!
+//! `worktree::merge_branch` covers `git merge`. Squashing and rebasing
+//! take a few more commands each, and a rebase that stops on a conflict
+//! has to be backed out of, so they live here rather than as one-off
+//! `Command`s in the handlers.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+
+/// A conflicted region is cut after this many lines: enough to see both
+/// sides of a typical clash, not a whole file that was rewritten twice.
+const MAX_HUNK_LINES: usize = 40;
+const MAX_HUNKS_PER_FILE: usize = 6;
+
+/// A file git could not merge, with its conflicted regions as they stand
+/// on disk — markers included, since that is what has to be edited.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictFile {
+    pub path: String,
+    pub hunks: Vec<String>,
+}
+
+/// The error a rebase that stopped on conflicts is reported with, so a
+/// caller can tell it from git failing for any other reason.
+#[derive(Debug)]
+pub struct Conflicted {
+    pub files: Vec<String>,
+}
+
+impl fmt::Display for Conflicted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "conflicts in {}", self.files.join(", "))
+    }
+}
+
+impl std::error::Error for Conflicted {}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| format!("Failed to execute git {}", args[0]))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        // Conflicts are reported on stdout; everything else on stderr.
+        let detail = if stderr.trim().is_empty() { stdout } else { stderr };
+        bail!("git {} failed: {}", args[0], detail.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The full messages of the commits `branch` has that `base` does not,
+/// oldest first.
+pub fn branch_messages(repo_path: &Path, base: &str, branch: &str) -> Result<Vec<String>> {
+    let range = format!("{base}..{branch}");
+    let log = git(repo_path, &["log", "--reverse", "--format=%B%x00", &range])?;
+    Ok(log
+        .split('\0')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Bring everything on `branch` into the current branch as one commit.
+///
+/// A conflict is left in the checkout, as `git merge` leaves one: the
+/// work is staged and the markers are in the files, for whoever resolves
+/// it to finish with `git commit`.
+pub fn squash_merge(repo_path: &Path, branch: &str, message: &str) -> Result<()> {
+    git(repo_path, &["merge", "--squash", branch])
+        .with_context(|| format!("Failed to squash branch '{branch}'"))?;
+    // A branch with nothing the target lacks squashes to nothing; that is
+    // a merge that had no work to do, not a failure.
+    if git(repo_path, &["diff", "--cached", "--quiet"]).is_ok() {
+        return Ok(());
+    }
+    git(repo_path, &["commit", "-m", message])?;
+    Ok(())
+}
+
+/// Replay the commits checked out in `worktree_path` on top of `onto`.
+/// A rebase that stops on a conflict is aborted, so the agent's branch is
+/// never left half-moved; the error is then a [`Conflicted`] naming the
+/// files it stopped on.
+pub fn rebase_onto(worktree_path: &Path, onto: &str) -> Result<()> {
+    if let Err(err) = rebase(worktree_path, onto) {
+        let files = conflicted_files(worktree_path).unwrap_or_default();
+        let _ = git(worktree_path, &["rebase", "--abort"]);
+        let note = format!("Rebase onto {onto} stopped; the branch was left as it was");
+        if files.is_empty() {
+            return Err(err.context(note));
+        }
+        return Err(anyhow::Error::new(Conflicted { files }).context(note));
+    }
+    Ok(())
+}
+
+/// Start replaying onto `onto` and leave a rebase that stops where it
+/// stopped, for someone to resolve and `git rebase --continue`.
+pub fn rebase(worktree_path: &Path, onto: &str) -> Result<()> {
+    git(worktree_path, &["rebase", onto])?;
+    Ok(())
+}
+
+/// Paths git has marked unmerged in the index.
+pub fn conflicted_files(repo_path: &Path) -> Result<Vec<String>> {
+    let out = git(repo_path, &["diff", "--name-only", "--diff-filter=U", "-z"])?;
+    Ok(out
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Every unmerged file in the checkout with its conflicted regions.
+pub fn conflicts(repo_path: &Path) -> Result<Vec<ConflictFile>> {
+    Ok(conflicted_files(repo_path)?
+        .into_iter()
+        .map(|path| {
+            let text = std::fs::read_to_string(repo_path.join(&path)).unwrap_or_default();
+            ConflictFile {
+                hunks: conflict_hunks(&text),
+                path,
+            }
+        })
+        .collect())
+}
+
+/// The `<<<<<<<` … `>>>>>>>` regions of a conflicted file. A file with
+/// none (one side deleted it, or it is binary) has no regions to show.
+pub fn conflict_hunks(text: &str) -> Vec<String> {
+    let mut hunks = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    let mut cut = 0;
+    for line in text.lines() {
+        if line.starts_with("<<<<<<<") {
+            current = Some(Vec::new());
+            cut = 0;
+        }
+        let Some(lines) = current.as_mut() else {
+            continue;
+        };
+        if lines.len() < MAX_HUNK_LINES {
+            lines.push(line);
+        } else {
+            cut += 1;
+        }
+        if line.starts_with(">>>>>>>") {
+            let mut hunk = lines.join("\n");
+            if cut > 0 {
+                hunk.push_str(&format!("\n… {cut} more lines"));
+            }
+            hunks.push(hunk);
+            current = None;
+            if hunks.len() == MAX_HUNKS_PER_FILE {
+                break;
+            }
+        }
+    }
+    hunks
+}
+
+/// Whether a rebase is stopped in this checkout.
+pub fn rebase_in_progress(repo_path: &Path) -> bool {
+    ["rebase-merge", "rebase-apply"]
+        .iter()
+        .any(|dir| git_path_exists(repo_path, dir))
+}
+
+/// Whether a merge, a squash or a rebase is half done in this checkout.
+pub fn operation_in_progress(repo_path: &Path) -> bool {
+    rebase_in_progress(repo_path)
+        || git_path_exists(repo_path, "MERGE_HEAD")
+        || conflicted_files(repo_path).is_ok_and(|files| !files.is_empty())
+}
+
+/// Put the checkout back as it was before the merge, squash or rebase that
+/// stopped in it.
+pub fn abort_operation(repo_path: &Path) -> Result<()> {
+    if rebase_in_progress(repo_path) {
+        git(repo_path, &["rebase", "--abort"])?;
+    } else {
+        // Unlike `merge --abort`, this also undoes a squash, which leaves
+        // no MERGE_HEAD behind.
+        git(repo_path, &["reset", "--merge"])?;
+    }
+    Ok(())
+}
+
+/// `.git/<name>` for this checkout — a linked worktree keeps its merge
+/// and rebase state under the main repository's `.git/worktrees/`.
+fn git_path_exists(repo_path: &Path, name: &str) -> bool {
+    git(repo_path, &["rev-parse", "--git-path", name])
+        .map(|path| repo_path.join(path.trim()).exists())
+        .unwrap_or(false)
+}
+
+/// Move the current branch up to `branch`, refusing anything but a
+/// fast-forward.
+pub fn fast_forward(repo_path: &Path, branch: &str) -> Result<()> {
+    git(repo_path, &["merge", "--ff-only", branch])
+        .with_context(|| format!("Failed to fast-forward to '{branch}'"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A repo with one commit on its default branch and an `agent` branch
+    /// two commits ahead of it, and a commit of its own on the default
+    /// branch since, so neither strategy is a trivial fast-forward.
+    fn diverged_repo() -> (TempDir, String) {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path();
+        for args in [
+            &["init"][..],
+            &["config", "user.email", "test@test.com"],
+            &["config", "user.name", "Test"],
+        ] {
+            git(repo, args).unwrap();
+        }
+        std::fs::write(repo.join("a.txt"), "a\n").unwrap();
+        git(repo, &["add", "."]).unwrap();
+        git(repo, &["commit", "-m", "initial"]).unwrap();
+        let main = git(repo, &["rev-parse", "--abbrev-ref", "HEAD"]).unwrap();
+
+        git(repo, &["checkout", "-b", "agent"]).unwrap();
+        for (file, msg) in [("b.txt", "Add b\n\nBecause b."), ("c.txt", "Add c")] {
+            std::fs::write(repo.join(file), "x\n").unwrap();
+            git(repo, &["add", "."]).unwrap();
+            git(repo, &["commit", "-m", msg]).unwrap();
+        }
+        git(repo, &["checkout", main.trim()]).unwrap();
+        std::fs::write(repo.join("d.txt"), "d\n").unwrap();
+        git(repo, &["add", "."]).unwrap();
+        git(repo, &["commit", "-m", "main moved"]).unwrap();
+        (dir, main.trim().to_string())
+    }
+
+    fn subjects(repo: &Path) -> Vec<String> {
+        git(repo, &["log", "--format=%s"])
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Squashing leaves one commit with the given message on top of the
+    /// target, and the branch's messages are read oldest first, bodies and
+    /// all, for writing that message.
+    #[test]
+    fn squash_merge_makes_one_commit_from_the_branch() {
+        let (dir, main) = diverged_repo();
+        let repo = dir.path();
+
+        let messages = branch_messages(repo, &main, "agent").unwrap();
+        assert_eq!(messages, vec!["Add b\n\nBecause b.", "Add c"]);
+
+        squash_merge(repo, "agent", "Add b and c").unwrap();
+        assert_eq!(subjects(repo), vec!["Add b and c", "main moved", "initial"]);
+        assert!(repo.join("c.txt").exists());
+
+        // Squashing again has nothing left to bring in.
+        squash_merge(repo, "agent", "again").unwrap();
+        assert_eq!(subjects(repo).len(), 3);
+    }
+
+    /// Rebase then fast-forward gives a straight history with the agent's
+    /// own commits; a fast-forward that isn't one is refused.
+    #[test]
+    fn rebase_then_fast_forward_gives_linear_history() {
+        let (dir, main) = diverged_repo();
+        let repo = dir.path();
+
+        assert!(fast_forward(repo, "agent").is_err());
+
+        git(repo, &["checkout", "agent"]).unwrap();
+        rebase_onto(repo, &main).unwrap();
+        git(repo, &["checkout", &main]).unwrap();
+        fast_forward(repo, "agent").unwrap();
+        assert_eq!(subjects(repo), vec!["Add c", "Add b", "main moved", "initial"]);
+    }
+
+    /// A rebase that hits a conflict is backed out: the branch keeps its
+    /// commits and no rebase is left in progress.
+    #[test]
+    fn conflicting_rebase_is_aborted() {
+        let (dir, main) = diverged_repo();
+        let repo = dir.path();
+        std::fs::write(repo.join("c.txt"), "main's c\n").unwrap();
+        git(repo, &["add", "."]).unwrap();
+        git(repo, &["commit", "-m", "main's c"]).unwrap();
+
+        git(repo, &["checkout", "agent"]).unwrap();
+        let before = git(repo, &["rev-parse", "HEAD"]).unwrap();
+        let err = rebase_onto(repo, &main).unwrap_err();
+        assert_eq!(err.downcast_ref::<Conflicted>().unwrap().files, vec!["c.txt"]);
+        assert_eq!(git(repo, &["rev-parse", "HEAD"]).unwrap(), before);
+        assert!(git(repo, &["status", "--porcelain"]).unwrap().is_empty());
+        assert!(!operation_in_progress(repo));
+    }
+
+    /// A merge that stops is seen as in progress with its conflicted
+    /// regions readable, and aborting puts the checkout back — for a
+    /// squash too, which leaves no MERGE_HEAD to abort by.
+    #[test]
+    fn stopped_merges_and_squashes_are_found_and_undone() {
+        let (dir, main) = diverged_repo();
+        let repo = dir.path();
+        std::fs::write(repo.join("c.txt"), "main's c\n").unwrap();
+        git(repo, &["add", "."]).unwrap();
+        git(repo, &["commit", "-m", "main's c"]).unwrap();
+        let head = git(repo, &["rev-parse", "HEAD"]).unwrap();
+
+        for squash in [false, true] {
+            if squash {
+                assert!(squash_merge(repo, "agent", "squashed").is_err());
+            } else {
+                assert!(crate::git::merge_branch(repo, "agent").is_err());
+            }
+            assert!(operation_in_progress(repo));
+            let found = conflicts(repo).unwrap();
+            assert_eq!(found.len(), 1);
+            assert_eq!(found[0].path, "c.txt");
+            assert!(found[0].hunks[0].starts_with("<<<<<<<"));
+            assert!(found[0].hunks[0].contains("main's c"));
+
+            abort_operation(repo).unwrap();
+            assert!(!operation_in_progress(repo));
+            assert_eq!(git(repo, &["rev-parse", "HEAD"]).unwrap(), head);
+            assert_eq!(git(repo, &["rev-parse", "--abbrev-ref", "HEAD"]).unwrap().trim(), main);
+        }
+    }
+}
@@ -1,3 +1,7 @@
+pub mod diff;
+pub mod merge;
 pub mod worktree;
 
+pub use diff::*;
+pub use merge::*;
 pub use worktree::*;
@@ -102,6 +102,22 @@ pub fn create_branch(repo_path: &Path, branch_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Create a branch at `start`, a branch or commit, rather than at HEAD.
+pub fn create_branch_at(repo_path: &Path, branch_name: &str, start: &str) -> Result<()> {
+    let output = Command::new("git")
+        .args(["branch", branch_name, start])
+        .current_dir(repo_path)
+        .output()
+        .context("Failed to execute git branch")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Failed to create branch '{}' at '{}': {}", branch_name, start, stderr);
+    }
+
+    Ok(())
+}
+
 /// Create a worktree for a parallel task attempt
 ///
 /// This creates a new git worktree at the specified path, checking out
@@ -181,6 +197,105 @@ pub fn remove_worktree(repo_path: &Path, worktree_path: &Path, delete_branch: bo
     Ok(())
 }
 
+/// One entry of `git worktree list --porcelain`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeEntry {
+    pub path: PathBuf,
+    /// `None` for a detached HEAD.
+    pub branch: Option<String>,
+    /// Registered, but its directory is gone — `git worktree prune` would
+    /// drop it.
+    pub prunable: bool,
+}
+
+/// Every worktree git knows of for this repository, the main checkout
+/// first.
+pub fn list_worktrees(repo_path: &Path) -> Result<Vec<WorktreeEntry>> {
+    let output = Command::new("git")
+        .args(["worktree", "list", "--porcelain"])
+        .current_dir(repo_path)
+        .output()
+        .context("Failed to execute git worktree list")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Failed to list worktrees: {}", stderr);
+    }
+
+    Ok(parse_worktree_list(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_worktree_list(output: &str) -> Vec<WorktreeEntry> {
+    let mut entries = Vec::new();
+    for block in output.split("\n\n") {
+        let mut entry: Option<WorktreeEntry> = None;
+        for line in block.lines() {
+            if let Some(path) = line.strip_prefix("worktree ") {
+                entry = Some(WorktreeEntry {
+                    path: PathBuf::from(path),
+                    branch: None,
+                    prunable: false,
+                });
+            } else if let Some(entry) = entry.as_mut() {
+                if let Some(branch) = line.strip_prefix("branch ") {
+                    let branch = branch.strip_prefix("refs/heads/").unwrap_or(branch);
+                    entry.branch = Some(branch.to_string());
+                } else if line == "prunable" || line.starts_with("prunable ") {
+                    entry.prunable = true;
+                }
+            }
+        }
+        entries.extend(entry);
+    }
+    entries
+}
+
+/// Local branches matching any of `patterns` (`for-each-ref` globs such as
+/// `agent-*`), by short name.
+pub fn list_branches(repo_path: &Path, patterns: &[&str]) -> Result<Vec<String>> {
+    let refs: Vec<String> = patterns.iter().map(|p| format!("refs/heads/{p}")).collect();
+    let output = Command::new("git")
+        .args(["for-each-ref", "--format=%(refname:short)"])
+        .args(&refs)
+        .current_dir(repo_path)
+        .output()
+        .context("Failed to execute git for-each-ref")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Failed to list branches: {}", stderr);
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+/// `<short hash> <subject> (<relative date>)` for `rev`'s tip commit.
+pub fn last_commit(repo_path: &Path, rev: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%h %s (%cr)", rev, "--"])
+        .current_dir(repo_path)
+        .output()
+        .ok()?;
+    let line = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !line.is_empty()).then_some(line)
+}
+
+/// How many commits `branch` has that `base` does not.
+pub fn commits_ahead(repo_path: &Path, base: &str, branch: &str) -> usize {
+    Command::new("git")
+        .args(["rev-list", "--count", &format!("{base}..{branch}")])
+        .current_dir(repo_path)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8_lossy(&o.stdout).trim().parse().ok())
+        .unwrap_or(0)
+}
+
 /// Get the branch name for a worktree
 fn get_worktree_branch(worktree_path: &Path) -> Result<String> {
     let output = Command::new("git")
@@ -197,7 +312,7 @@ fn get_worktree_branch(worktree_path: &Path) -> Result<String> {
 }
 
 /// Force delete a branch
-fn delete_branch_force(repo_path: &Path, branch_name: &str) -> Result<()> {
+pub fn delete_branch_force(repo_path: &Path, branch_name: &str) -> Result<()> {
     let output = Command::new("git")
         .args(["branch", "-D", branch_name])
         .current_dir(repo_path)
@@ -265,6 +380,15 @@ pub fn get_session_worktree_path(workspace_path: &Path, session_id_short: &str)
     get_worktrees_dir(workspace_path).join(format!("session-{}", session_id_short))
 }
 
+/// The branches workbench makes: `agent-…` for a session's worktree,
+/// `parallel-…/…` for a parallel attempt's.
+pub const BRANCH_PATTERNS: [&str; 2] = ["agent-*", "parallel-*/*"];
+
+/// Whether `branch` is named the way workbench names its own.
+pub fn is_workbench_branch(branch: &str) -> bool {
+    branch.starts_with("agent-") || (branch.starts_with("parallel-") && branch.contains('/'))
+}
+
 /// Generate a branch name for a session worktree
 pub fn session_branch_name(agent_name: &str, session_id_short: &str) -> String {
     format!("agent-{}-{}", agent_name.to_lowercase(), session_id_short)
@@ -485,4 +609,30 @@ mod tests {
         let stat = get_diff_shortstat(dir.path(), None);
         assert!(stat.insertions > 0 || stat.deletions > 0);
     }
+
+    #[test]
+    fn test_parse_worktree_list() {
+        let output = [
+            "worktree /repo",
+            "HEAD abc",
+            "branch refs/heads/main",
+            "",
+            "worktree /repo/.worktrees/session-1",
+            "HEAD def",
+            "branch refs/heads/agent-x-1",
+            "",
+            "worktree /repo/.worktrees/gone",
+            "HEAD 123",
+            "detached",
+            "prunable gitdir file points to non-existent location",
+            "",
+        ]
+        .join("\n");
+        let entries = parse_worktree_list(&output);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].branch.as_deref(), Some("agent-x-1"));
+        assert!(!entries[1].prunable);
+        assert_eq!(entries[2].branch, None);
+        assert!(entries[2].prunable);
+    }
 }
@@ -0,0 +1,361 @@
+//! Searching what every agent has said: `workbench grep`.
+//!
+//! Each agent's conversation is already on disk twice over — in its own
+//! journal, and in the transcript workbench exports at every idle — but one
+//! file per agent, in five formats, is no way to answer "which agent
+//! discussed the migration yesterday". This reads them all the way the phone
+//! reads one (see `remote::read_more`) and matches line by line.
+//!
+//! An agent's journal is preferred to its transcript: it is the words without
+//! the terminal around them, and it carries a time for every message. The
+//! transcript is the fallback for an agent with no journal we can read, and
+//! its matches carry the time it was exported — the only time it has.
+//!
+//! Only the journal an agent is writing now is searched. Codex opens a fresh
+//! rollout on every start, and the roster names the current one.
+
+use chrono::{DateTime, Local, NaiveDate, TimeDelta, Utc};
+use regex::Regex;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::agent_tasks::Provider;
+use crate::comms::{Roster, RosterAgent};
+use crate::remote::{Message, Role};
+
+/// What to look for, and how much around it to show.
+pub struct Query {
+    pub pattern: Regex,
+    /// Nothing said before this.
+    pub since: Option<DateTime<Utc>>,
+    /// Lines either side of a match, as `grep -C`.
+    pub context: usize,
+}
+
+/// Which of the agent's records a match came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Origin {
+    Journal,
+    Transcript,
+}
+
+/// One run of lines around one or more matches, from one message.
+#[derive(Debug, Clone, Serialize)]
+pub struct Hit {
+    pub project: String,
+    pub agent: String,
+    pub alias: Option<String>,
+    pub provider: String,
+    pub origin: Origin,
+    /// Who said it; a transcript cannot tell.
+    pub role: Option<Role>,
+    /// When it was said, or for a transcript when it was exported.
+    pub at: Option<DateTime<Utc>>,
+    pub lines: Vec<Line>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Line {
+    pub text: String,
+    #[serde(rename = "match")]
+    pub matched: bool,
+}
+
+/// The pattern as a regex, or why it is not one.
+pub fn pattern(text: &str, ignore_case: bool) -> Result<Regex, String> {
+    regex::RegexBuilder::new(text)
+        .case_insensitive(ignore_case)
+        .build()
+        .map_err(|err| format!("not a pattern: {err}"))
+}
+
+/// `--since` as people type it: `90m`, `6h`, `2d`, `1w`, a date
+/// (`2026-10-16`, from local midnight) or a full RFC 3339 time.
+pub fn parse_since(text: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let text = text.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(text) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return date
+            .and_hms_opt(0, 0, 0)
+            .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+            .map(|at| at.with_timezone(&Utc))
+            .ok_or_else(|| format!("{text} has no local midnight"));
+    }
+    let unit_at = text.char_indices().last().map_or(0, |(at, _)| at);
+    let (amount, unit) = text.split_at(unit_at);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("not a time: {text} — try 90m, 6h, 2d or 2026-10-16"))?;
+    if amount < 0 {
+        return Err(format!("{text} is in the future — give how long ago, as 90m or 2d"));
+    }
+    // Checked all the way: chrono panics on a delta or a date it can't
+    // hold, and a mistyped `99999999999w` should be told so, not crash.
+    let ago = match unit {
+        "m" => TimeDelta::try_minutes(amount),
+        "h" => TimeDelta::try_hours(amount),
+        "d" => TimeDelta::try_days(amount),
+        "w" => TimeDelta::try_weeks(amount),
+        _ => return Err(format!("unknown unit in {text} — use m, h, d or w")),
+    };
+    ago.and_then(|ago| now.checked_sub_signed(ago))
+        .ok_or_else(|| format!("{text} is further back than there are dates for"))
+}
+
+/// The agent answers to `wanted`: its id (or the start of it), its alias or
+/// its provider. A filter, not an address — `codex` means every codex agent.
+pub fn is_named(agent: &RosterAgent, wanted: &str) -> bool {
+    agent.id.starts_with(&wanted.to_ascii_lowercase())
+        || agent.provider.eq_ignore_ascii_case(wanted)
+        || agent
+            .alias
+            .as_deref()
+            .is_some_and(|alias| alias.eq_ignore_ascii_case(wanted))
+}
+
+/// Every match in the agents of `rosters` — those `agent` names, when it
+/// names any — oldest first across all of them, so the answer reads as one
+/// history rather than one agent after another.
+pub fn search(rosters: &[Roster], agent: Option<&str>, query: &Query) -> Result<Vec<Hit>, String> {
+    let agents: Vec<(&Roster, &RosterAgent)> = rosters
+        .iter()
+        .flat_map(|roster| roster.agents.iter().map(move |a| (roster, a)))
+        .filter(|(_, a)| agent.is_none_or(|wanted| is_named(a, wanted)))
+        .collect();
+    if let (Some(wanted), true) = (agent, agents.is_empty()) {
+        return Err(format!("no agent matches '{wanted}'"));
+    }
+    let mut hits: Vec<Hit> = agents
+        .into_iter()
+        .flat_map(|(roster, agent)| search_agent(roster, agent, query))
+        .collect();
+    // Stable, so one agent's matches at the same moment keep their order.
+    hits.sort_by_key(|hit| hit.at);
+    Ok(hits)
+}
+
+/// Every match in one agent's record, oldest first.
+pub fn search_agent(roster: &Roster, agent: &RosterAgent, query: &Query) -> Vec<Hit> {
+    let hit = |origin, role, at, lines| Hit {
+        project: roster.workspace_name.clone(),
+        agent: agent.id.clone(),
+        alias: agent.alias.clone(),
+        provider: agent.provider.clone(),
+        origin,
+        role,
+        at,
+        lines,
+    };
+
+    if let Some(messages) = journal_messages(agent) {
+        let mut hits = Vec::new();
+        // Claude stamps a tool call's line only sometimes; an unstamped
+        // message was said when the one before it was.
+        let mut last_at = None;
+        for message in messages {
+            let at = message
+                .at
+                .as_deref()
+                .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+                .map(|at| at.with_timezone(&Utc))
+                .or(last_at);
+            last_at = at;
+            if query.since.is_some_and(|since| at.is_none_or(|at| at < since)) {
+                continue;
+            }
+            for lines in excerpts(&message.text, query) {
+                hits.push(hit(Origin::Journal, Some(message.role), at, lines));
+            }
+        }
+        return hits;
+    }
+
+    let Some(path) = agent.transcript.as_deref().map(Path::new) else {
+        return Vec::new();
+    };
+    let exported = std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .map(DateTime::<Utc>::from);
+    if query
+        .since
+        .is_some_and(|since| exported.is_none_or(|at| at < since))
+    {
+        return Vec::new();
+    }
+    let Ok(text) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    // Past the export's heading and the blank line under it.
+    let body = text.lines().skip(2).collect::<Vec<_>>().join("\n");
+    excerpts(&body, query)
+        .into_iter()
+        .map(|lines| hit(Origin::Transcript, None, exported, lines))
+        .collect()
+}
+
+fn journal_messages(agent: &RosterAgent) -> Option<Vec<Message>> {
+    let journal = agent.journal.as_ref()?;
+    let provider = Provider::for_command(&journal.provider)?;
+    let path = Path::new(&journal.path);
+    Some(match &journal.session {
+        Some(session) => crate::agent_tasks::conversation(provider, path, session),
+        None => crate::remote::read_all(path, provider),
+    })
+}
+
+/// The matching lines of `text` with their context. Matches close enough for
+/// their context to touch share one excerpt, as `grep -C` prints them.
+fn excerpts(text: &str, query: &Query) -> Vec<Vec<Line>> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if !query.pattern.is_match(line) {
+            continue;
+        }
+        let start = i.saturating_sub(query.context);
+        let end = (i + query.context + 1).min(lines.len());
+        match runs.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => runs.push((start, end)),
+        }
+    }
+    runs.into_iter()
+        .map(|(start, end)| {
+            lines[start..end]
+                .iter()
+                .map(|line| Line {
+                    text: line.to_string(),
+                    matched: query.pattern.is_match(line),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comms::RosterJournal;
+    use std::io::Write;
+
+    fn query(pattern: &str, context: usize) -> Query {
+        Query {
+            pattern: Regex::new(pattern).unwrap(),
+            since: None,
+            context,
+        }
+    }
+
+    fn roster() -> Roster {
+        Roster {
+            workspace_id: "ws".into(),
+            workspace_name: "shop".into(),
+            workspace_path: "/tmp/shop".into(),
+            updated_at: "now".into(),
+            agents: Vec::new(),
+            cross_project: false,
+            notes: Vec::new(),
+            claims: Vec::new(),
+        }
+    }
+
+    fn agent(id: &str, journal: Option<RosterJournal>, transcript: Option<String>) -> RosterAgent {
+        RosterAgent {
+            id: id.into(),
+            provider: "claude".into(),
+            alias: None,
+            branch: "workspace".into(),
+            cwd: "/tmp/shop".into(),
+            status: "idle".into(),
+            transcript,
+            supports_consult: true,
+            journal,
+        }
+    }
+
+    #[test]
+    fn matches_close_together_share_their_context() {
+        let text = "one\ntwo migration\nthree\nfour migration\nfive\nsix\nseven\neight migration";
+
+        let runs = excerpts(text, &query("migration", 1));
+
+        assert_eq!(runs.len(), 2);
+        let first: Vec<&str> = runs[0].iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(first, ["one", "two migration", "three", "four migration", "five"]);
+        assert_eq!(runs[0].iter().filter(|l| l.matched).count(), 2);
+        assert_eq!(runs[1].len(), 2, "the last line has nothing after it");
+    }
+
+    /// The journal is read in full — the phone's tail read would miss what
+    /// was said at the start of a long session, which is exactly what a
+    /// search is for — and each match keeps who said it and when.
+    #[test]
+    fn a_journal_match_carries_its_speaker_and_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("session.jsonl");
+        let mut file = std::fs::File::create(&log).unwrap();
+        for line in [
+            r#"{"type":"user","timestamp":"2026-10-15T09:00:00Z","message":{"role":"user","content":"plan the schema migration"}}"#,
+            r#"{"type":"assistant","timestamp":"2026-10-16T10:00:00Z","message":{"role":"assistant","content":[{"type":"text","text":"The migration adds a column.\nIt is reversible."}]}}"#,
+        ] {
+            writeln!(file, "{line}").unwrap();
+        }
+        let journal = RosterJournal {
+            provider: "claude".into(),
+            path: log.to_string_lossy().to_string(),
+            session: None,
+        };
+        let reader = agent("a1b2c3d4", Some(journal), None);
+
+        let hits = search_agent(&roster(), &reader, &query("migration", 0));
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].role, Some(Role::You));
+        assert_eq!(hits[1].role, Some(Role::Agent));
+        assert_eq!(hits[1].lines[0].text, "The migration adds a column.");
+        assert_eq!((hits[1].agent.as_str(), hits[1].project.as_str()), ("a1b2c3d4", "shop"));
+
+        let mut since = query("migration", 0);
+        since.since = Some(parse_since("2026-10-16T00:00:00Z", Utc::now()).unwrap());
+        let recent = search_agent(&roster(), &reader, &since);
+        assert_eq!(recent.len(), 1, "the older message is before --since");
+    }
+
+    #[test]
+    fn an_agent_without_a_journal_is_searched_by_its_transcript() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("codex-e5f6a7b8.md");
+        std::fs::write(&path, "# migration heading\n\n$ run migration\nok\n").unwrap();
+        let exported = agent("e5f6a7b8", None, Some(path.to_string_lossy().to_string()));
+
+        let hits = search_agent(&roster(), &exported, &query("migration", 0));
+
+        assert_eq!(hits.len(), 1, "the export's own heading is not the agent's");
+        assert_eq!(hits[0].origin, Origin::Transcript);
+        assert!(hits[0].at.is_some());
+    }
+
+    #[test]
+    fn since_reads_the_way_people_type_it() {
+        let now = Utc::now();
+        assert_eq!(parse_since("90m", now).unwrap(), now - TimeDelta::minutes(90));
+        assert_eq!(parse_since("2d", now).unwrap(), now - TimeDelta::days(2));
+        assert!(parse_since("2026-10-16", now).is_ok());
+        assert!(parse_since("yesterday", now).is_err());
+        assert!(parse_since("3y", now).is_err());
+    }
+
+    /// Windows no calendar can hold, or that end in the future, are
+    /// refused rather than panicking or quietly matching nothing.
+    #[test]
+    fn since_refuses_overflowing_and_negative_windows() {
+        let now = Utc::now();
+        assert!(parse_since("99999999999w", now).is_err());
+        assert!(parse_since("9223372036854775807m", now).is_err());
+        assert!(parse_since("-2d", now).is_err());
+        assert_eq!(parse_since("0m", now).unwrap(), now);
+    }
+}
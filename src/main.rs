@@ -3,13 +3,16 @@ mod agent_tasks;
 mod app;
 mod audio;
 mod canvas;
+mod claims;
 mod cli;
 mod comms;
 mod control;
 mod config;
 mod git;
+mod grep;
 mod logger;
 mod models;
+mod notes;
 mod persistence;
 mod scrollback;
 mod ports;
@@ -18,6 +21,7 @@ mod pty;
 mod remote;
 mod theme;
 mod tui;
+mod worktree_gc;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -44,6 +48,66 @@ struct Cli {
     no_alt_screen: bool,
 }
 
+#[derive(Subcommand)]
+enum NoteCommand {
+    /// Write a note, bumping its version. Exits 4 if --if-version does not match
+    Put {
+        key: String,
+        /// The value; `-` reads it from stdin
+        value: String,
+        /// Only write if the note is at this version now (0: only if it does
+        /// not exist yet — how a claim is taken)
+        #[arg(long)]
+        if_version: Option<u64>,
+    },
+    /// Print a note's value
+    Get {
+        key: String,
+        /// Print the whole note (value, version, author, time) as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List every note in the workspace
+    List,
+    /// Block until a note changes, then print it. Exits 3 on timeout
+    Watch {
+        key: String,
+        /// Wait for a version newer than this (default: the current one)
+        #[arg(long)]
+        since: Option<u64>,
+        #[arg(long, default_value_t = 600)]
+        timeout: u64,
+        /// Print the whole note as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum WorktreesCommand {
+    /// List the worktrees and branches no session or parallel attempt owns
+    /// any more, with what each holds, and remove the ones you confirm
+    Prune {
+        /// Only list them
+        #[arg(long)]
+        dry_run: bool,
+        /// Remove every one without asking
+        #[arg(long, short = 'y', conflicts_with = "dry_run")]
+        yes: bool,
+        /// With --yes, also delete branches holding commits HEAD doesn't
+        /// have. Without it they are kept
+        #[arg(long, requires = "yes")]
+        drop_unmerged: bool,
+        /// With --yes, also remove worktrees with uncommitted changes.
+        /// Without it they are kept
+        #[arg(long, requires = "yes")]
+        drop_dirty: bool,
+        /// Only this project (by name). Default: every project
+        #[arg(long)]
+        project: Option<String>,
+    },
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Add a workspace directory
@@ -57,10 +121,15 @@ enum Commands {
     /// List all workspaces
     List,
     /// List agent sessions in this workspace (agent-to-agent comms)
-    Agents,
+    Agents {
+        /// Also list the agents of other projects open to cross-project consults
+        #[arg(long)]
+        all: bool,
+    },
     /// Print a peer agent's recent conversation transcript
     Transcript {
-        /// Target agent: short id, alias, or provider name (if unique)
+        /// Target agent: short id, alias, or provider name (if unique), or
+        /// `project:agent` for an agent in another project
         target: String,
         /// How many trailing lines to print
         #[arg(long, default_value_t = 200)]
@@ -71,10 +140,21 @@ enum Commands {
     },
     /// Queue a question for a live peer agent; prints a ticket
     Ask {
-        /// Target agent: short id, alias, or provider name (if unique)
-        target: String,
-        /// The question to deliver
-        message: String,
+        /// `<target> <question>`, or just `<question>` with --to or
+        /// --all-peers. A target is a short id, alias, or provider name (if
+        /// unique), or `project:agent` for an agent in another project
+        #[arg(required = true, num_args = 1..=2, value_names = ["TARGET", "QUESTION"])]
+        args: Vec<String>,
+        /// Ask several peers at once (comma-separated); their replies are
+        /// collected under one group ticket
+        #[arg(long, value_delimiter = ',', conflicts_with = "all_peers")]
+        to: Vec<String>,
+        /// Ask every live peer in this project that can take a consult
+        #[arg(long)]
+        all_peers: bool,
+        /// For a group: return once this many have answered (default: all)
+        #[arg(long)]
+        quorum: Option<usize>,
         /// Block until the reply arrives (or timeout)
         #[arg(long)]
         wait: bool,
@@ -82,10 +162,23 @@ enum Commands {
         #[arg(long, default_value_t = 600)]
         timeout: u64,
     },
-    /// Ask a peer for a structured handoff summary of its work
+    /// Ask a peer for a structured handoff summary of its work — or, with
+    /// --to, start a new agent that takes the work over
     Handoff {
-        /// Target agent: short id, alias, or provider name (if unique)
+        /// Target agent: short id, alias, or provider name (if unique), or
+        /// `project:agent` for an agent in another project
         target: String,
+        /// Start a successor on this provider (claude or codex), seeded with
+        /// the summary and given the target's queued TODO items
+        #[arg(long)]
+        to: Option<String>,
+        /// Give the successor its own worktree, branched from the target's
+        /// branch, instead of sharing the target's checkout
+        #[arg(long, requires = "to")]
+        worktree: bool,
+        /// Stop the target once the successor has started
+        #[arg(long, requires = "to")]
+        stop: bool,
         /// Block until the handoff arrives (or timeout)
         #[arg(long)]
         wait: bool,
@@ -93,18 +186,65 @@ enum Commands {
         #[arg(long, default_value_t = 600)]
         timeout: u64,
     },
-    /// Collect the reply for a consult ticket
+    /// Collect the reply for a consult ticket, or every reply for a group
     Replies {
         ticket: String,
         /// Block until the reply arrives (or timeout)
         #[arg(long)]
         wait: bool,
+        /// For a group: return once this many have answered (default: all)
+        #[arg(long)]
+        quorum: Option<usize>,
         /// Timeout in seconds for --wait
         #[arg(long, default_value_t = 600)]
         timeout: u64,
     },
     /// Set this session's alias for agent-to-agent addressing
     Alias { name: String },
+    /// Claim files or directories before editing them, so peers in the same
+    /// checkout keep clear. With no paths, list the claims. Exits 4 if
+    /// another agent holds any of them
+    Claim { paths: Vec<PathBuf> },
+    /// Let go of claimed paths (all of yours, when none are named)
+    Release { paths: Vec<PathBuf> },
+    /// Read and write the workspace's shared notes (claims, decisions, status)
+    Note {
+        #[command(subcommand)]
+        action: NoteCommand,
+    },
+    /// Clean up after agents' worktrees: checkouts and branches left behind
+    Worktrees {
+        #[command(subcommand)]
+        action: WorktreesCommand,
+    },
+    /// Search what the agents have said — their journals, or exported
+    /// transcripts for agents without one — oldest match first
+    Grep {
+        /// A regular expression, matched line by line
+        pattern: String,
+        /// Search another project (by name or id), or `all` for every one
+        /// taking cross-project consults. Default: this project
+        #[arg(long)]
+        project: Option<String>,
+        /// Only agents answering to this: an id prefix, alias or provider
+        #[arg(long)]
+        agent: Option<String>,
+        /// Only what was said since: 90m, 6h, 2d, 1w, or a date (2026-10-16)
+        #[arg(long)]
+        since: Option<String>,
+        /// Lines of context around each match
+        #[arg(long, short = 'C', default_value_t = 2)]
+        context: usize,
+        /// Match regardless of case
+        #[arg(long, short = 'i')]
+        ignore_case: bool,
+        /// Show at most this many matches, the newest
+        #[arg(long, default_value_t = 50)]
+        max: usize,
+        /// Print the matches as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Block until an agent stops working (for scripts and other agents)
     Wait {
         /// Target agent: short id, an unambiguous prefix, or a provider name
@@ -126,6 +266,18 @@ enum Commands {
         #[arg(long)]
         json: bool,
     },
+    /// Call any control-socket method and print the answer (see `api.schema`)
+    Ctl {
+        /// The method, e.g. `agents.list`, `todo.move`, `events.subscribe`
+        method: String,
+        /// A param as `key=value` (always a string) or `key:=json` (numbers,
+        /// booleans, lists). Repeat a key to send a list.
+        #[arg(long = "param", short = 'p', value_name = "KEY=VALUE")]
+        params: Vec<String>,
+        /// Keep printing events, one JSON object per line, after the answer
+        #[arg(long, short = 'f')]
+        follow: bool,
+    },
     /// Analyze messages submitted to agents through Workbench
     Prompts {
         /// How many recent messages to include
@@ -176,28 +328,85 @@ fn main() -> Result<()> {
         Some(Commands::List) => {
             println!("Workspaces: (in-memory only, no persistence)");
         }
-        Some(Commands::Agents) => cli::cmd_agents()?,
+        Some(Commands::Agents { all }) => cli::cmd_agents(all)?,
         Some(Commands::Transcript { target, lines, all }) => {
             cli::cmd_transcript(target, lines, all)?
         }
         Some(Commands::Ask {
-            target,
-            message,
+            args,
+            to,
+            all_peers,
+            quorum,
             wait,
             timeout,
-        }) => cli::cmd_ask(target, message, wait, timeout)?,
+        }) => {
+            if to.is_empty() && !all_peers {
+                let [target, message] = <[String; 2]>::try_from(args).map_err(|_| {
+                    anyhow::anyhow!("give a target and a question, or the question alone with --to or --all-peers")
+                })?;
+                cli::cmd_ask(target, message, wait, timeout)?
+            } else {
+                let [message] = <[String; 1]>::try_from(args).map_err(|_| {
+                    anyhow::anyhow!("with --to or --all-peers, give only the question")
+                })?;
+                cli::cmd_ask_group(to, all_peers, message, wait, quorum, timeout)?
+            }
+        }
         Some(Commands::Handoff {
             target,
+            to,
+            worktree,
+            stop,
             wait,
             timeout,
-        }) => cli::cmd_handoff(target, wait, timeout)?,
+        }) => cli::cmd_handoff(target, to, worktree, stop, wait, timeout)?,
         Some(Commands::Replies {
             ticket,
             wait,
+            quorum,
             timeout,
-        }) => cli::cmd_replies(ticket, wait, timeout)?,
+        }) => cli::cmd_replies(ticket, wait, quorum, timeout)?,
         Some(Commands::Hook { event }) => cli::cmd_hook(event.as_deref()),
         Some(Commands::Alias { name }) => cli::cmd_alias(name)?,
+        Some(Commands::Claim { paths }) => cli::cmd_claim(paths)?,
+        Some(Commands::Release { paths }) => cli::cmd_release(paths)?,
+        Some(Commands::Note { action }) => match action {
+            NoteCommand::Put {
+                key,
+                value,
+                if_version,
+            } => cli::cmd_note_put(key, value, if_version)?,
+            NoteCommand::Get { key, json } => cli::cmd_note_get(key, json)?,
+            NoteCommand::List => cli::cmd_note_list()?,
+            NoteCommand::Watch {
+                key,
+                since,
+                timeout,
+                json,
+            } => cli::cmd_note_watch(key, since, timeout, json)?,
+        },
+        Some(Commands::Worktrees { action }) => match action {
+            WorktreesCommand::Prune {
+                dry_run,
+                yes,
+                drop_unmerged,
+                drop_dirty,
+                project,
+            } => cli::cmd_worktrees_prune(dry_run, yes, drop_unmerged, drop_dirty, project)?,
+        },
+        Some(Commands::Grep {
+            pattern,
+            project,
+            agent,
+            since,
+            context,
+            ignore_case,
+            max,
+            json,
+        }) => {
+            let query = cli::grep_query(&pattern, ignore_case, since.as_deref(), context)?;
+            cli::cmd_grep(query, project, agent, max, json)?
+        }
         Some(Commands::Wait {
             target,
             state,
@@ -205,6 +414,11 @@ fn main() -> Result<()> {
             timeout,
             json,
         }) => cli::cmd_wait(target, state, project, timeout, json)?,
+        Some(Commands::Ctl {
+            method,
+            params,
+            follow,
+        }) => cli::cmd_ctl(method, params, follow)?,
         Some(Commands::Prompts { limit, json }) => {
             if json {
                 println!("{}", serde_json::to_string_pretty(&prompt_log::recent(limit)?)?);
@@ -0,0 +1,288 @@
+//! A spending cap on a TODO queue or a whole workspace.
+//!
+//! Queues are meant to run unattended, which is exactly when an agent stuck in
+//! a loop costs the most: nobody is watching the meter. A budget does not stop
+//! the agent mid-turn — workbench never interrupts one — it stops the queue
+//! from handing over the *next* item once the cap is reached (see
+//! `app::todo_dispatch`).
+//!
+//! Spend is read off the agent's own journal as a running total, and that
+//! total is not to be trusted to only go up: it starts again from nothing
+//! when a session restarts or a Codex resume opens a new rollout, and a
+//! workspace's shrinks when one of its sessions is deleted. So a budget
+//! does not measure from a snapshot of the meter; it adds up what each new
+//! reading puts on top of the last one, and takes a reading that went down
+//! for a meter that started over.
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::SessionCost;
+
+/// What the cap is measured over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetWindow {
+    /// Since local midnight; the cap comes back each morning.
+    #[default]
+    Day,
+    /// Since the cap was set. Set it again to start over.
+    Queue,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Budget {
+    /// Dollar cap, against the estimate from the price table.
+    #[serde(default)]
+    pub usd: Option<f64>,
+    /// Token cap, for spend nobody has priced — or for anyone who would
+    /// rather reason in tokens.
+    #[serde(default)]
+    pub tokens: Option<u64>,
+    #[serde(default)]
+    pub window: BudgetWindow,
+    /// Spend added up inside the current window, reading by reading.
+    #[serde(default)]
+    pub counted: SessionCost,
+    /// What the meter read last time it was looked at. Saved before this
+    /// was counted reading by reading as the meter at the window's opening,
+    /// which is the same thing seen from one reading further back.
+    #[serde(default, alias = "baseline")]
+    pub last_meter: SessionCost,
+    #[serde(default)]
+    pub opened_at: Option<DateTime<Utc>>,
+}
+
+impl Budget {
+    /// Read a cap as typed into the TODO pane: `$5`, `$20/day`, `2m tokens`,
+    /// `500k/queue`, or both kinds at once (`$5 1m/day`). A bare number is
+    /// dollars; the window defaults to the day.
+    pub fn parse(text: &str) -> Result<Budget, String> {
+        let text = text.trim().to_ascii_lowercase();
+        let (amounts, window) = match text.rsplit_once('/') {
+            Some((amounts, "day")) | Some((amounts, "daily")) => (amounts, BudgetWindow::Day),
+            Some((amounts, "queue")) => (amounts, BudgetWindow::Queue),
+            Some((_, other)) => return Err(format!("unknown window '/{other}' — use /day or /queue")),
+            None => (text.as_str(), BudgetWindow::Day),
+        };
+
+        let mut budget = Budget {
+            usd: None,
+            tokens: None,
+            window,
+            counted: SessionCost::default(),
+            last_meter: SessionCost::default(),
+            opened_at: None,
+        };
+        for word in amounts.split_whitespace() {
+            match word {
+                "tokens" | "token" | "tok" => {}
+                _ if word.starts_with('$') => budget.usd = Some(dollars(&word[1..], word)?),
+                _ if word.ends_with(['k', 'm']) || amounts.contains("tok") => {
+                    budget.tokens = Some(parse_tokens(word)?);
+                }
+                _ => budget.usd = Some(dollars(word, word)?),
+            }
+        }
+        if budget.usd.is_none() && budget.tokens.is_none() {
+            return Err("give a cap, e.g. $5/day or 2m tokens".to_string());
+        }
+        Ok(budget)
+    }
+
+    /// How the cap reads back — `$5.00 2.0M tok/day` — in a form `parse`
+    /// accepts, so editing a cap starts from the one already set.
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(usd) = self.usd {
+            parts.push(format!("${usd:.2}"));
+        }
+        if let Some(tokens) = self.tokens {
+            parts.push(format!("{} tok", token_count(tokens)));
+        }
+        let window = match self.window {
+            BudgetWindow::Day => "/day",
+            BudgetWindow::Queue => "/queue",
+        };
+        format!("{}{window}", parts.join(" "))
+    }
+
+    /// Open the window if it has not been, or the next one if the day turned
+    /// over, and count what the meter added since it was last read. `meter`
+    /// is what the queue (or workspace) has spent in total. True when
+    /// anything changed, which is worth saving.
+    pub fn roll(&mut self, meter: SessionCost, now: DateTime<Utc>) -> bool {
+        let stale = match (self.opened_at, self.window) {
+            (None, _) => true,
+            (Some(opened), BudgetWindow::Day) => {
+                opened.with_timezone(&Local).date_naive() != now.with_timezone(&Local).date_naive()
+            }
+            (Some(_), BudgetWindow::Queue) => false,
+        };
+        if stale {
+            self.counted = SessionCost::default();
+            self.last_meter = meter;
+            self.opened_at = Some(now);
+            return true;
+        }
+        let added = self.uncounted(meter);
+        self.counted.tokens += added.tokens;
+        self.counted.estimated_cost_usd += added.estimated_cost_usd;
+        let changed = self.last_meter != meter;
+        self.last_meter = meter;
+        changed
+    }
+
+    /// Spent inside the current window: what has been counted, and what the
+    /// meter has added since.
+    pub fn spent(&self, meter: SessionCost) -> SessionCost {
+        let added = self.uncounted(meter);
+        let mut spent = self.counted;
+        spent.tokens += added.tokens;
+        spent.estimated_cost_usd += added.estimated_cost_usd;
+        spent
+    }
+
+    /// What `meter` adds on top of the last reading. A meter that reads
+    /// lower than last time has started over, not refunded anything: it
+    /// adds nothing now, and counts up from where it is next time.
+    fn uncounted(&self, meter: SessionCost) -> SessionCost {
+        let last = &self.last_meter;
+        if meter.tokens.total() < last.tokens.total()
+            || meter.estimated_cost_usd < last.estimated_cost_usd
+        {
+            return SessionCost::default();
+        }
+        SessionCost {
+            tokens: meter.tokens.since(&last.tokens),
+            estimated_cost_usd: meter.estimated_cost_usd - last.estimated_cost_usd,
+        }
+    }
+
+    /// The cap has been reached — either of them, when both are set. A
+    /// window not yet opened has spent nothing.
+    pub fn exceeded(&self, meter: SessionCost) -> bool {
+        if self.opened_at.is_none() {
+            return false;
+        }
+        let spent = self.spent(meter);
+        self.usd.is_some_and(|cap| spent.estimated_cost_usd >= cap)
+            || self.tokens.is_some_and(|cap| spent.tokens.total() >= cap)
+    }
+}
+
+/// A cap of nothing, less than nothing, or not a number at all would hold
+/// every queue at once or none ever; either way it is a typo.
+fn dollars(digits: &str, word: &str) -> Result<f64, String> {
+    digits
+        .parse::<f64>()
+        .ok()
+        .filter(|usd| usd.is_finite() && *usd > 0.0)
+        .ok_or_else(|| format!("not a dollar amount: {word}"))
+}
+
+fn parse_tokens(word: &str) -> Result<u64, String> {
+    let (digits, scale) = match word.chars().last() {
+        Some('k') => (&word[..word.len() - 1], 1_000.0),
+        Some('m') => (&word[..word.len() - 1], 1_000_000.0),
+        _ => (word, 1.0),
+    };
+    digits
+        .parse::<f64>()
+        .ok()
+        .map(|n| n * scale)
+        .filter(|n| n.is_finite() && *n >= 1.0)
+        .map(|n| n as u64)
+        .ok_or_else(|| format!("not a token count: {word}"))
+}
+
+fn token_count(tokens: u64) -> String {
+    match tokens {
+        n if n >= 1_000_000 => format!("{:.1}M", n as f64 / 1_000_000.0),
+        n if n >= 1_000 => format!("{}k", n / 1_000),
+        n => n.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TokenUsage;
+
+    fn meter(usd: f64, n: u64) -> SessionCost {
+        SessionCost {
+            tokens: TokenUsage {
+                input: n,
+                ..TokenUsage::default()
+            },
+            estimated_cost_usd: usd,
+        }
+    }
+
+    #[test]
+    fn caps_read_the_way_people_type_them() {
+        let budget = Budget::parse("$20/day").unwrap();
+        assert_eq!((budget.usd, budget.tokens), (Some(20.0), None));
+        assert_eq!(budget.window, BudgetWindow::Day);
+
+        let budget = Budget::parse("500k/queue").unwrap();
+        assert_eq!((budget.usd, budget.tokens), (None, Some(500_000)));
+        assert_eq!(budget.window, BudgetWindow::Queue);
+
+        let budget = Budget::parse("$5 2m tokens").unwrap();
+        assert_eq!((budget.usd, budget.tokens), (Some(5.0), Some(2_000_000)));
+        assert_eq!(budget.label(), "$5.00 2.0M tok/day");
+        assert_eq!(Budget::parse(&budget.label()).unwrap(), budget);
+
+        assert!(Budget::parse("/day").is_err());
+        assert!(Budget::parse("$5/week").is_err());
+        for typo in ["$0", "-5", "$-5/day", "$nan", "inf", "$1e999", "0k tokens", "-2m"] {
+            assert!(Budget::parse(typo).is_err(), "{typo}");
+        }
+    }
+
+    /// A meter that starts over — a restart, a new rollout, a deleted
+    /// session — neither wipes out what was spent nor goes negative; what
+    /// it adds afterwards still counts.
+    #[test]
+    fn spend_survives_a_meter_that_starts_over() {
+        let mut budget = Budget::parse("$5/queue").unwrap();
+        let now = Utc::now();
+        budget.roll(meter(10.0, 1000), now);
+        budget.roll(meter(13.0, 3000), now);
+        assert_eq!(budget.spent(meter(13.0, 3000)).estimated_cost_usd, 3.0);
+
+        // Started over at a lower reading.
+        assert!(budget.roll(meter(0.5, 100), now));
+        assert_eq!(budget.spent(meter(0.5, 100)).estimated_cost_usd, 3.0);
+        assert_eq!(budget.spent(meter(0.2, 50)).estimated_cost_usd, 3.0);
+        assert!(!budget.exceeded(meter(2.0, 500)));
+
+        budget.roll(meter(2.5, 600), now);
+        assert!(budget.exceeded(meter(2.5, 600)));
+        assert_eq!(budget.counted.tokens.total(), 2000 + 500);
+    }
+
+    /// Spend from before the cap was set is not held against it: the point
+    /// is to stop what happens next, not to punish what already happened.
+    #[test]
+    fn only_spend_inside_the_window_counts() {
+        let mut budget = Budget::parse("$5/queue").unwrap();
+        budget.roll(meter(40.0, 0), Utc::now());
+
+        assert!(!budget.exceeded(meter(44.0, 0)));
+        assert!(budget.exceeded(meter(45.0, 0)));
+    }
+
+    #[test]
+    fn a_daily_cap_comes_back_the_next_day() {
+        let mut budget = Budget::parse("1m tokens/day").unwrap();
+        let yesterday = Utc::now() - chrono::TimeDelta::days(1);
+        budget.roll(meter(0.0, 0), yesterday);
+        assert!(budget.exceeded(meter(0.0, 1_200_000)));
+
+        budget.roll(meter(0.0, 1_200_000), Utc::now());
+
+        assert!(!budget.exceeded(meter(0.0, 1_500_000)));
+    }
+}
@@ -0,0 +1,173 @@
+//! What an agent has spent: tokens as its journal recorded them, and dollars
+//! as the user's price table says those tokens cost.
+//!
+//! Tokens are facts — the agent wrote them down (see `agent_tasks::files`).
+//! Dollars are an estimate layered on top, which is why the two are kept
+//! apart: a price that changes, or a model nobody has priced yet, should move
+//! the estimate without losing the count underneath it.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+
+use crate::config::user_config::ModelPrice;
+
+/// Tokens one model consumed, in the four buckets providers bill separately.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input: u64,
+    pub output: u64,
+    /// Prompt tokens served from the provider's cache — cheap, but not free.
+    pub cache_read: u64,
+    /// Prompt tokens written into the cache. Codex does not report these.
+    pub cache_write: u64,
+}
+
+impl TokenUsage {
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_read + self.cache_write
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    /// What was added between two readings of a running total. Saturating:
+    /// a bucket that shrank added nothing, it did not refund anything.
+    pub fn since(&self, earlier: &TokenUsage) -> TokenUsage {
+        TokenUsage {
+            input: self.input.saturating_sub(earlier.input),
+            output: self.output.saturating_sub(earlier.output),
+            cache_read: self.cache_read.saturating_sub(earlier.cache_read),
+            cache_write: self.cache_write.saturating_sub(earlier.cache_write),
+        }
+    }
+}
+
+impl AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: TokenUsage) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_read += other.cache_read;
+        self.cache_write += other.cache_write;
+    }
+}
+
+/// Spend for a session, a workspace or everything — the same shape at every
+/// level, so totals are just sums.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionCost {
+    pub tokens: TokenUsage,
+    /// Estimated from the price table. Tokens from a model with no price
+    /// count here as zero rather than guessing at someone else's rate.
+    pub estimated_cost_usd: f64,
+}
+
+impl SessionCost {
+    /// Price a session's tokens, model by model: a session switched from
+    /// Sonnet to Opus halfway through pays each rate for its own half.
+    pub fn priced(
+        by_model: &BTreeMap<String, TokenUsage>,
+        pricing: &BTreeMap<String, ModelPrice>,
+    ) -> SessionCost {
+        let mut cost = SessionCost::default();
+        for (model, tokens) in by_model {
+            cost.tokens += *tokens;
+            if let Some(price) = price_for(pricing, model) {
+                cost.estimated_cost_usd += price.cost(tokens);
+            }
+        }
+        cost
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// `$1.23`, or `<$0.01` for spend too small to round to a cent — which is
+    /// still spend, and reads differently from nothing at all.
+    pub fn label(&self) -> String {
+        if self.estimated_cost_usd > 0.0 && self.estimated_cost_usd < 0.005 {
+            "<$0.01".to_string()
+        } else {
+            format!("${:.2}", self.estimated_cost_usd)
+        }
+    }
+}
+
+impl AddAssign for SessionCost {
+    fn add_assign(&mut self, other: SessionCost) {
+        self.tokens += other.tokens;
+        self.estimated_cost_usd += other.estimated_cost_usd;
+    }
+}
+
+/// The price for `model`: the longest key in the table that its id starts
+/// with, so `claude-opus` prices every Opus and `claude-opus-4-5` can still
+/// override it for the one release priced differently.
+///
+/// Matched on the bare id — the context-window suffix (`[1m]`) and a
+/// gateway's vendor prefix (`anthropic/…`) are stripped first, the same
+/// noise `model_label` ignores.
+pub fn price_for<'a>(
+    pricing: &'a BTreeMap<String, ModelPrice>,
+    model: &str,
+) -> Option<&'a ModelPrice> {
+    let bare = model.split('[').next().unwrap_or(model).trim();
+    let bare = bare.rsplit('/').next().unwrap_or(bare).to_ascii_lowercase();
+    pricing
+        .iter()
+        .filter(|(prefix, _)| bare.starts_with(&prefix.to_ascii_lowercase()))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, price)| price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> BTreeMap<String, ModelPrice> {
+        BTreeMap::from([
+            ("claude-opus".to_string(), ModelPrice::per_million(15.0, 75.0, 1.5, 18.75)),
+            ("claude-opus-4-5".to_string(), ModelPrice::per_million(5.0, 25.0, 0.5, 6.25)),
+        ])
+    }
+
+    #[test]
+    fn the_most_specific_price_wins() {
+        let pricing = table();
+
+        assert_eq!(price_for(&pricing, "claude-opus-4-5-20251101").unwrap().input, 5.0);
+        assert_eq!(price_for(&pricing, "claude-opus-5[1m]").unwrap().input, 15.0);
+        assert_eq!(price_for(&pricing, "anthropic/claude-opus-5").unwrap().input, 15.0);
+        assert!(price_for(&pricing, "gemini-3-pro").is_none());
+    }
+
+    /// A model nobody priced still shows its tokens; it just adds nothing to
+    /// the dollar estimate instead of borrowing another model's rate.
+    #[test]
+    fn unpriced_tokens_are_counted_but_not_charged() {
+        let by_model = BTreeMap::from([
+            (
+                "claude-opus-5".to_string(),
+                TokenUsage {
+                    input: 1_000_000,
+                    ..TokenUsage::default()
+                },
+            ),
+            (
+                "mystery-1".to_string(),
+                TokenUsage {
+                    output: 500,
+                    ..TokenUsage::default()
+                },
+            ),
+        ]);
+
+        let cost = SessionCost::priced(&by_model, &table());
+
+        assert_eq!(cost.tokens.total(), 1_000_500);
+        assert!((cost.estimated_cost_usd - 15.0).abs() < 1e-9);
+        assert_eq!(cost.label(), "$15.00");
+    }
+}
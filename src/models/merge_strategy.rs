@@ -0,0 +1,98 @@
+//! How an agent's branch lands on the branch it was started from.
+//!
+//! A plain `git merge` keeps every "wip", "fix typo" and auto-commit an
+//! agent left behind, which is fine for a scratch repo and noise for one
+//! with a reviewed history. The confirm modals let each merge pick another
+//! way in, and the workspace remembers the last pick so a project that
+//! always squashes doesn't have to say so every time.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// `git merge --no-edit`: the branch's commits plus a merge commit.
+    #[default]
+    Merge,
+    /// One commit on the target, its message taken from what the agent
+    /// wrote — its own commit messages and, for a parallel attempt, its
+    /// report.
+    Squash,
+    /// Replay the branch on top of the target, then fast-forward: the
+    /// agent's commits, in a straight line.
+    Rebase,
+    /// Commit what is left in the worktree and stop there. The worktree
+    /// goes, the branch stays for a pull request or a later look; nothing
+    /// is merged and nothing is pushed.
+    Branch,
+}
+
+impl MergeStrategy {
+    pub const ALL: [MergeStrategy; 4] = [Self::Merge, Self::Squash, Self::Rebase, Self::Branch];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Squash => "squash",
+            Self::Rebase => "rebase",
+            Self::Branch => "branch",
+        }
+    }
+
+    /// One line for the modal, under the picker.
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::Merge => "Merge the branch as is, with a merge commit",
+            Self::Squash => "One commit, message written from the agent's commits",
+            Self::Rebase => "Replay the commits on top, then fast-forward",
+            Self::Branch => "Commit and keep the branch; merge and push nothing",
+        }
+    }
+
+    /// The step to take from the modal's confirm key, as the help line and
+    /// the status bar word it.
+    pub fn verb(self) -> &'static str {
+        match self {
+            Self::Merge => "Commit & Merge",
+            Self::Squash => "Squash & Merge",
+            Self::Rebase => "Rebase & Merge",
+            Self::Branch => "Commit & Keep Branch",
+        }
+    }
+
+    /// Whether the work ends up on the target branch — and so whether the
+    /// target's checkout has to be clean and the branch can be deleted.
+    pub fn lands(self) -> bool {
+        self != Self::Branch
+    }
+
+    /// The next choice along the picker, wrapping at either end.
+    pub fn cycle(self, delta: isize) -> Self {
+        let len = Self::ALL.len() as isize;
+        let idx = Self::ALL.iter().position(|s| *s == self).unwrap_or(0) as isize;
+        Self::ALL[(idx + delta).rem_euclid(len) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Workspace;
+
+    /// The picker wraps both ways, and a workspace saved before strategies
+    /// existed loads with the plain merge it always had.
+    #[test]
+    fn cycle_wraps_and_old_workspaces_default_to_merge() {
+        assert_eq!(MergeStrategy::Merge.cycle(1), MergeStrategy::Squash);
+        assert_eq!(MergeStrategy::Merge.cycle(-1), MergeStrategy::Branch);
+        assert_eq!(MergeStrategy::Branch.cycle(1), MergeStrategy::Merge);
+
+        let mut json = serde_json::to_value(Workspace::new("ws".into(), "/tmp/ws".into())).unwrap();
+        json.as_object_mut().unwrap().remove("merge_strategy");
+        let workspace: Workspace = serde_json::from_value(json).unwrap();
+        assert_eq!(workspace.merge_strategy, MergeStrategy::Merge);
+
+        let json = serde_json::to_string(&MergeStrategy::Squash).unwrap();
+        assert_eq!(json, "\"squash\"");
+    }
+}
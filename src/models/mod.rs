@@ -1,11 +1,20 @@
 mod agent;
+mod budget;
+mod cost;
+mod merge_strategy;
 mod parallel_task;
 mod session;
 mod todo_queue;
 mod workspace;
 
 pub use agent::{model_label, AgentType};
-pub use parallel_task::{AttemptStatus, ParallelTask, ParallelTaskAttempt, ParallelTaskStatus};
+pub use budget::{Budget, BudgetWindow};
+pub use cost::{SessionCost, TokenUsage};
+pub use merge_strategy::MergeStrategy;
+pub use parallel_task::{
+    AttemptStatus, ParallelTask, ParallelTaskAttempt, ParallelTaskStatus, Verification,
+    VerifyOutcome,
+};
 pub use session::{Session, SessionStatus};
-pub use todo_queue::{QueuedTodo, TodoQueue, TodoState};
+pub use todo_queue::{QueuedTodo, TodoKind, TodoQueue, TodoState};
 pub use workspace::{Workspace, MAX_PINNED_TERMINALS};
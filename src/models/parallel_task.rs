@@ -26,6 +26,12 @@ pub struct ParallelTask {
     /// Whether to request a PARALLEL_REPORT.md from agents
     #[serde(default)]
     pub request_report: bool,
+    /// Shell command run in each attempt's worktree once the agent is done
+    /// (e.g. `cargo test`). Copied from the workspace when the task starts,
+    /// so editing the workspace's command later doesn't change what this
+    /// task's attempts were measured against.
+    #[serde(default)]
+    pub verify_command: Option<String>,
 }
 
 impl ParallelTask {
@@ -49,6 +55,7 @@ impl ParallelTask {
             winner_attempt_id: None,
             attempts: Vec::new(),
             request_report,
+            verify_command: None,
         }
     }
 
@@ -89,6 +96,34 @@ impl ParallelTask {
         self.completed_at = Some(Utc::now());
     }
 
+    /// The attempts best-first, the order the Reports tab lists them in.
+    ///
+    /// Passing verification beats everything, and among passes the faster
+    /// run wins — with the same tests green, a suite that takes half as
+    /// long usually means the change did less. Attempts with no verdict
+    /// yet (still working, waiting their turn, or no command at all) keep
+    /// their original order in the middle, ahead of failed verifications
+    /// and then attempts whose agent failed outright.
+    pub fn ranked_attempts(&self) -> Vec<&ParallelTaskAttempt> {
+        let mut ranked: Vec<&ParallelTaskAttempt> = self.attempts.iter().collect();
+        ranked.sort_by_key(|attempt| {
+            let outcome = attempt.verification.as_ref().and_then(|v| v.outcome.as_ref());
+            match (attempt.status, outcome) {
+                (AttemptStatus::Failed, _) => (3, 0),
+                (_, Some(outcome)) if outcome.passed() => (0, outcome.duration_ms),
+                (_, Some(_)) => (2, 0),
+                (_, None) => (1, 0),
+            }
+        });
+        ranked
+    }
+
+    /// The attempt at `idx` in ranked order — what the Reports tab cursor
+    /// points at.
+    pub fn ranked_attempt(&self, idx: usize) -> Option<&ParallelTaskAttempt> {
+        self.ranked_attempts().get(idx).copied()
+    }
+
     /// Get the full prompt to send to agents, including report instructions if requested
     pub fn full_prompt(&self) -> String {
         if self.request_report {
@@ -128,6 +163,10 @@ pub struct ParallelTaskAttempt {
     pub report_content: Option<String>,
     #[serde(default)]
     pub prompt_sent: bool,
+    /// The task's verify command, run in this worktree after the agent
+    /// finished. `None` until its turn comes (or if there is no command).
+    #[serde(default)]
+    pub verification: Option<Verification>,
 }
 
 impl ParallelTaskAttempt {
@@ -149,6 +188,7 @@ impl ParallelTaskAttempt {
             status: AttemptStatus::Running,
             report_content: None,
             prompt_sent: false,
+            verification: None,
         }
     }
 
@@ -170,6 +210,49 @@ impl ParallelTaskAttempt {
     }
 }
 
+/// A run of the task's verify command in one attempt's worktree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Verification {
+    pub command: String,
+    pub started_at: DateTime<Utc>,
+    /// `None` while the command runs.
+    #[serde(default)]
+    pub outcome: Option<VerifyOutcome>,
+}
+
+/// How a finished verification went.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyOutcome {
+    /// The command's exit status; `None` if it could not be started, was
+    /// killed by a signal, or ran past the time limit.
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    /// The last lines the command printed, stdout and stderr together —
+    /// the failing test is almost always at the end.
+    pub output_tail: String,
+}
+
+impl VerifyOutcome {
+    pub fn passed(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    /// "passed in 42s" / "exit 101 after 1m 05s" / "failed after 3s".
+    pub fn summary(&self) -> String {
+        let secs = self.duration_ms / 1000;
+        let took = if secs >= 60 {
+            format!("{}m {:02}s", secs / 60, secs % 60)
+        } else {
+            format!("{}.{}s", secs, (self.duration_ms % 1000) / 100)
+        };
+        match self.exit_code {
+            Some(0) => format!("passed in {took}"),
+            Some(code) => format!("exit {code} after {took}"),
+            None => format!("failed after {took}"),
+        }
+    }
+}
+
 /// Status of an individual attempt
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AttemptStatus {
@@ -312,6 +395,48 @@ mod tests {
         assert!(task.winner_attempt_id.is_none());
     }
 
+    fn verified(attempt: &mut ParallelTaskAttempt, exit_code: Option<i32>, duration_ms: u64) {
+        attempt.verification = Some(Verification {
+            command: "cargo test".to_string(),
+            started_at: Utc::now(),
+            outcome: Some(VerifyOutcome {
+                exit_code,
+                duration_ms,
+                output_tail: String::new(),
+            }),
+        });
+    }
+
+    /// Passing attempts come first, quickest first; undecided ones keep
+    /// their order in the middle; failed verifications and failed agents
+    /// sink to the bottom.
+    #[test]
+    fn test_ranked_attempts_put_the_fastest_pass_first() {
+        let mut task = create_test_task();
+        for agent in [
+            AgentType::Claude,
+            AgentType::Codex,
+            AgentType::Gemini,
+            AgentType::Grok,
+        ] {
+            let mut attempt = create_test_attempt(task.id, agent);
+            attempt.status = AttemptStatus::Completed;
+            task.add_attempt(attempt);
+        }
+        task.add_attempt(create_test_attempt(task.id, AgentType::Claude));
+        task.attempts[0].status = AttemptStatus::Failed;
+        verified(&mut task.attempts[1], Some(1), 500);
+        verified(&mut task.attempts[2], Some(0), 9_000);
+        verified(&mut task.attempts[3], Some(0), 4_000);
+
+        let order: Vec<Uuid> = task.ranked_attempts().iter().map(|a| a.id).collect();
+        let expected: Vec<Uuid> = [3, 2, 4, 1, 0].iter().map(|&i| task.attempts[i].id).collect();
+        assert_eq!(order, expected);
+
+        let outcome = task.attempts[3].verification.as_ref().unwrap().outcome.as_ref().unwrap();
+        assert_eq!(outcome.summary(), "passed in 4.0s");
+    }
+
     // ==================== ParallelTaskAttempt Tests ====================
 
     #[test]
@@ -23,13 +23,30 @@ pub enum TodoState {
     Done,
 }
 
+/// Whose an item is.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TodoKind {
+    /// Work you queued.
+    #[default]
+    Task,
+    /// A question a peer asked with `workbench ask` (see `app::comms_tick`).
+    /// It waits its turn like your work does, so it cannot land on top of
+    /// you or of an item in flight; removing it refuses the consult.
+    Consult { ticket: String, from: String },
+}
+
 /// One thing for the agent to do.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueuedTodo {
     pub id: Uuid,
+    /// For a consult, the question as the peer asked it; what reaches the
+    /// agent is framed at dispatch.
     pub text: String,
     #[serde(default)]
     pub state: TodoState,
+    #[serde(default)]
+    pub kind: TodoKind,
     pub created_at: DateTime<Utc>,
     /// When it was sent to the agent, if it has been.
     #[serde(default)]
@@ -42,10 +59,19 @@ impl QueuedTodo {
             id: Uuid::new_v4(),
             text: text.into(),
             state: TodoState::Pending,
+            kind: TodoKind::Task,
             created_at: Utc::now(),
             sent_at: None,
         }
     }
+
+    /// The consult this item carries, if it is one.
+    pub fn consult_ticket(&self) -> Option<&str> {
+        match &self.kind {
+            TodoKind::Consult { ticket, .. } => Some(ticket),
+            TodoKind::Task => None,
+        }
+    }
 }
 
 /// One agent's queue: an ordered list, at most one item in flight.
@@ -56,6 +82,9 @@ pub struct TodoQueue {
     /// Paused queues hold their items instead of dispatching them.
     #[serde(default)]
     pub paused: bool,
+    /// A spending cap: once reached, items wait instead of going out.
+    #[serde(default)]
+    pub budget: Option<super::Budget>,
 }
 
 impl TodoQueue {
@@ -85,22 +114,57 @@ impl TodoQueue {
         id
     }
 
-    pub fn remove(&mut self, id: Uuid) {
-        self.items.retain(|item| item.id != id);
+    /// Queue a peer's consult: ahead of your waiting work, behind other
+    /// consults already ahead of it — or, `behind`, at the back.
+    pub fn add_consult(
+        &mut self,
+        ticket: impl Into<String>,
+        from: impl Into<String>,
+        question: impl Into<String>,
+        behind: bool,
+    ) -> Uuid {
+        let mut todo = QueuedTodo::new(question);
+        todo.kind = TodoKind::Consult {
+            ticket: ticket.into(),
+            from: from.into(),
+        };
+        let id = todo.id;
+        let at = self
+            .items
+            .iter()
+            .position(|item| item.state == TodoState::Pending && item.kind == TodoKind::Task)
+            .filter(|_| !behind)
+            .unwrap_or(self.items.len());
+        self.items.insert(at, todo);
+        id
+    }
+
+    /// Take an item out of the queue, handing it back so the caller can
+    /// settle whatever it stood for.
+    pub fn remove(&mut self, id: Uuid) -> Option<QueuedTodo> {
+        let at = self.items.iter().position(|item| item.id == id)?;
+        Some(self.items.remove(at))
+    }
+
+    pub fn find_consult(&self, ticket: &str) -> Option<&QueuedTodo> {
+        self.items
+            .iter()
+            .find(|item| item.consult_ticket() == Some(ticket))
     }
 
     pub fn get_mut(&mut self, id: Uuid) -> Option<&mut QueuedTodo> {
         self.items.iter_mut().find(|item| item.id == id)
     }
 
-    /// Move an item one place earlier or later, so you can reorder work
-    /// without retyping it.
+    /// Move an item earlier or later, so you can reorder work without
+    /// retyping it. A move past either end stops there, so a script can send
+    /// an item to the top without first counting how far away it is.
     pub fn shift(&mut self, id: Uuid, delta: isize) {
         let Some(from) = self.items.iter().position(|item| item.id == id) else {
             return;
         };
-        let to = from as isize + delta;
-        if to < 0 || to >= self.items.len() as isize {
+        let to = (from as isize + delta).clamp(0, self.items.len() as isize - 1);
+        if to == from as isize {
             return;
         }
         let item = self.items.remove(from);
@@ -121,6 +185,18 @@ impl TodoQueue {
         self.items.iter().find(|i| i.state == TodoState::Pending)
     }
 
+    /// The first consult waiting its turn. Pausing the queue holds your
+    /// work, not a peer blocked on its question, so this is what still goes
+    /// out while a pause holds the rest.
+    pub fn next_consult(&self) -> Option<&QueuedTodo> {
+        if self.running().is_some() {
+            return None;
+        }
+        self.items
+            .iter()
+            .find(|i| i.state == TodoState::Pending && i.consult_ticket().is_some())
+    }
+
     pub fn pending_count(&self) -> usize {
         self.items
             .iter()
@@ -216,6 +292,10 @@ mod tests {
         queue.shift(a, -1);
         queue.shift(b, 1);
         assert_eq!(order(&queue), vec![a, c, b]);
+
+        // Further than the queue is long: as far as it goes.
+        queue.shift(b, -99);
+        assert_eq!(order(&queue), vec![b, a, c]);
     }
 
     #[test]
@@ -247,6 +327,42 @@ mod tests {
         assert_eq!(queue.items[0].text, "still to do");
     }
 
+    /// A consult jumps your waiting work, but not the consults that came
+    /// before it — peers are answered in the order they asked.
+    #[test]
+    fn consults_go_ahead_of_queued_work_in_the_order_they_came() {
+        let mut queue = queue_of(&["running work", "queued work"]);
+        let first = queue.items[0].id;
+        queue.mark_running(first);
+
+        queue.add_consult("c1", "aaaa1111", "which lock?", false);
+        queue.add_consult("c2", "bbbb2222", "which table?", false);
+        queue.add_consult("c3", "cccc3333", "when you get to it", true);
+
+        let order: Vec<&str> = queue.items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(
+            order,
+            vec!["running work", "which lock?", "which table?", "queued work", "when you get to it"]
+        );
+        assert_eq!(queue.find_consult("c2").unwrap().text, "which table?");
+
+        // Paused, the consults still have somewhere to go; your work does not.
+        queue.paused = true;
+        queue.finish_running();
+        assert_eq!(queue.next_consult().unwrap().consult_ticket(), Some("c1"));
+    }
+
+    /// Queues saved before there were consults load as your own work.
+    #[test]
+    fn an_item_saved_without_a_kind_is_yours() {
+        let item: QueuedTodo = serde_json::from_str(&format!(
+            r#"{{"id":"{}","text":"ship it","created_at":"2026-01-01T00:00:00Z"}}"#,
+            Uuid::new_v4()
+        ))
+        .unwrap();
+        assert_eq!(item.kind, TodoKind::Task);
+    }
+
     fn order(queue: &TodoQueue) -> Vec<Uuid> {
         queue.items.iter().map(|i| i.id).collect()
     }
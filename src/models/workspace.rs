@@ -27,6 +27,23 @@ pub struct Workspace {
     /// Last active session ID for this workspace (restored when switching back)
     #[serde(default)]
     pub last_active_session_id: Option<Uuid>,
+    /// A spending cap shared by every agent's queue in this workspace, on
+    /// top of any cap a queue carries itself.
+    #[serde(default)]
+    pub budget: Option<super::Budget>,
+    /// The verify command last used for a parallel task here, offered again
+    /// when the next one starts (see `ParallelTask::verify_command`).
+    #[serde(default)]
+    pub verify_command: Option<String>,
+    /// How the last merge from a worktree here was done; the confirm
+    /// modals open on it.
+    #[serde(default)]
+    pub merge_strategy: super::MergeStrategy,
+    /// Branches a worktree's work was kept on (`MergeStrategy::Branch`)
+    /// instead of being landed. Once the worktree is gone nothing else
+    /// points at them, and `worktree_gc` would take them for orphans.
+    #[serde(default)]
+    pub kept_branches: Vec<String>,
 }
 
 impl Workspace {
@@ -42,6 +59,10 @@ impl Workspace {
             parallel_tasks: Vec::new(),
             active_worktree_session_id: None,
             last_active_session_id: None,
+            budget: None,
+            verify_command: None,
+            merge_strategy: super::MergeStrategy::default(),
+            kept_branches: Vec::new(),
         }
     }
 
@@ -85,6 +106,13 @@ impl Workspace {
         }
     }
 
+    /// Remember a branch the user chose to keep.
+    pub fn keep_branch(&mut self, branch: String) {
+        if !self.kept_branches.contains(&branch) {
+            self.kept_branches.push(branch);
+        }
+    }
+
     /// Add a terminal to the pinned list (up to MAX_PINNED_TERMINALS)
     pub fn pin_terminal(&mut self, session_id: Uuid) -> bool {
         if self.pinned_terminal_ids.len() >= MAX_PINNED_TERMINALS {
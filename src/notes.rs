@@ -0,0 +1,171 @@
+//! A workspace's shared notes: a small key-value store agents write to
+//! instead of interrupting each other.
+//!
+//! A consult costs the peer a full turn, and reading its transcript only says
+//! what it has done, not what it has taken on. Agents splitting a job need
+//! somewhere to leave "I have `src/parser/`", "we settled on the v2 schema"
+//! or "tests green on my branch" where the others will look — without anyone
+//! stopping to be told. That is all this is.
+//!
+//! ```text
+//! <config>/workbench/comms/<workspace-id>/notes/<key>.json
+//! ```
+//!
+//! Every write bumps the key's version, and a writer can insist on the
+//! version it read (`--if-version`), so two agents claiming the same thing
+//! cannot both believe they won. Writers are separate processes, so the
+//! read-compare-write happens under a lock file beside the note.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::comms;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Note {
+    pub key: String,
+    pub value: String,
+    /// 1 for the first write, and one more for each after it.
+    pub version: u64,
+    /// The short id of the session that wrote it, or none from a plain shell.
+    #[serde(default)]
+    pub author: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What a write came to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Put {
+    Written(Note),
+    /// The key was not at the version the writer expected; `current` is the
+    /// one it is at, 0 for a key that does not exist.
+    Conflict { current: u64 },
+}
+
+/// 1-64 characters of `[a-zA-Z0-9_.-]`, not starting with a dot: something
+/// that is both typed on a command line without quoting and safe as a file
+/// name. `claims.parser` and `decision-schema` are the intended shape.
+pub fn key_is_valid(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 64
+        && !key.starts_with('.')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// `key_is_valid`, as an error that says what a key may be. Every command
+/// that takes a key asks first: the key becomes a file name, and a reader
+/// given `../x` would otherwise go looking outside the notes directory.
+pub fn check_key(key: &str) -> Result<()> {
+    if !key_is_valid(key) {
+        bail!("note keys are 1-64 chars of [a-zA-Z0-9_.-], not starting with a dot");
+    }
+    Ok(())
+}
+
+pub fn notes_dir(workspace_id: &str) -> Result<PathBuf> {
+    Ok(comms::workspace_dir(workspace_id)?.join("notes"))
+}
+
+fn note_path(dir: &Path, key: &str) -> PathBuf {
+    dir.join(format!("{key}.json"))
+}
+
+pub fn get(dir: &Path, key: &str) -> Option<Note> {
+    serde_json::from_slice(&fs::read(note_path(dir, key)).ok()?).ok()
+}
+
+/// Every note, by key.
+pub fn list(dir: &Path) -> Vec<Note> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut notes: Vec<Note> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().and_then(|e| e.to_str()) == Some("json"))
+        .filter_map(|entry| serde_json::from_slice(&fs::read(entry.path()).ok()?).ok())
+        .collect();
+    notes.sort_by(|a, b| a.key.cmp(&b.key));
+    notes
+}
+
+/// Write `value` under `key`. With `expect`, only if the key is at that
+/// version now — 0 meaning it must not exist yet, which is how a claim is
+/// taken.
+pub fn put(
+    dir: &Path,
+    key: &str,
+    value: &str,
+    author: Option<&str>,
+    expect: Option<u64>,
+) -> Result<Put> {
+    check_key(key)?;
+    fs::create_dir_all(dir)?;
+    let _lock = comms::FileLock::take(&dir.join(format!(".{key}.lock")))?;
+
+    let current = get(dir, key).map_or(0, |note| note.version);
+    if expect.is_some_and(|expected| expected != current) {
+        return Ok(Put::Conflict { current });
+    }
+    let note = Note {
+        key: key.to_string(),
+        value: value.to_string(),
+        version: current + 1,
+        author: author.map(str::to_string),
+        updated_at: Utc::now(),
+    };
+    comms::write_atomic(&note_path(dir, key), serde_json::to_string_pretty(&note)?.as_bytes())?;
+    Ok(Put::Written(note))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A claim is a write that expects nothing to be there. The second
+    /// claimant must lose, and be told the version it lost to.
+    #[test]
+    fn only_one_writer_wins_a_compare_and_set() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let first = put(dir.path(), "claims.parser", "aaaa1111", Some("aaaa1111"), Some(0)).unwrap();
+        let second = put(dir.path(), "claims.parser", "bbbb2222", Some("bbbb2222"), Some(0)).unwrap();
+
+        assert!(matches!(first, Put::Written(ref note) if note.version == 1));
+        assert_eq!(second, Put::Conflict { current: 1 });
+        assert_eq!(get(dir.path(), "claims.parser").unwrap().value, "aaaa1111");
+
+        // Handing it over names the version read, and bumps it.
+        let handed = put(dir.path(), "claims.parser", "bbbb2222", None, Some(1)).unwrap();
+        assert!(matches!(handed, Put::Written(ref note) if note.version == 2));
+    }
+
+    #[test]
+    fn a_plain_write_always_lands_and_notes_list_by_key() {
+        let dir = tempfile::tempdir().unwrap();
+
+        put(dir.path(), "status", "red", None, None).unwrap();
+        put(dir.path(), "status", "green", None, None).unwrap();
+        put(dir.path(), "decision", "v2 schema", None, None).unwrap();
+
+        let notes = list(dir.path());
+        let keys: Vec<&str> = notes.iter().map(|n| n.key.as_str()).collect();
+        assert_eq!(keys, ["decision", "status"]);
+        assert_eq!((notes[1].value.as_str(), notes[1].version), ("green", 2));
+        // The lock does not outlive the write, or the next one would wait.
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn keys_must_be_safe_file_names() {
+        assert!(key_is_valid("claims.src-parser_2"));
+        assert!(!key_is_valid("../escape"));
+        assert!(!key_is_valid(".hidden"));
+        assert!(!key_is_valid("a b"));
+        assert!(!key_is_valid(""));
+    }
+}
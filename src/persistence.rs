@@ -1,3 +1,4 @@
+use crate::app::merge::MergeConflict;
 use crate::models::{Session, SessionStatus, Workspace};
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
@@ -24,6 +25,12 @@ pub struct PersistedState {
     pub sessions: HashMap<Uuid, Vec<Session>>,
     #[serde(default)]
     pub notepad_content: HashMap<Uuid, String>, // workspace_id -> notepad text
+    /// Landings that stopped on conflicts and were still being dealt with:
+    /// handed to an agent, or on screen waiting for a decision. Without
+    /// them a restart would leave a half-done merge in a checkout with
+    /// nothing pointing at it (see `handlers::merge_conflict::restore`).
+    #[serde(default)]
+    pub merge_conflicts: Vec<MergeConflict>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -109,6 +116,7 @@ impl PersistedState {
             workspaces: Vec::new(),
             sessions: HashMap::new(),
             notepad_content: HashMap::new(),
+            merge_conflicts: Vec::new(),
         }
     }
 }
@@ -238,6 +246,7 @@ struct PersistedStateRef<'a> {
     workspaces: &'a [Workspace],
     sessions: &'a HashMap<Uuid, Vec<Session>>,
     notepad_content: &'a HashMap<Uuid, String>,
+    merge_conflicts: &'a [MergeConflict],
 }
 
 /// Serialize the full persisted state to a JSON string. Cheap and in-memory,
@@ -247,12 +256,14 @@ pub fn serialize_state(
     workspaces: &[Workspace],
     sessions: &HashMap<Uuid, Vec<Session>>,
     notepad_content: &HashMap<Uuid, String>,
+    merge_conflicts: &[MergeConflict],
 ) -> Result<String> {
     let state = PersistedStateRef {
         version: STATE_SCHEMA_VERSION,
         workspaces,
         sessions,
         notepad_content,
+        merge_conflicts,
     };
     Ok(serde_json::to_string_pretty(&state)?)
 }
@@ -411,6 +422,7 @@ mod restart_tests {
             workspaces: vec![workspace.clone()],
             sessions: [(workspace.id, vec![session])].into_iter().collect(),
             notepad_content: Default::default(),
+            merge_conflicts: Vec::new(),
         };
 
         // The same pass that decides a PTY did not survive the restart.
@@ -26,17 +26,18 @@ mod thread;
 pub use prompt::Prompt;
 pub use push::Push;
 pub use server::{new_token, Remote, RemoteCommand};
-pub use thread::{Cursor, Message};
+pub use thread::{read_all, read_more, Cursor, Message, Role};
 
 use serde::Serialize;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use uuid::Uuid;
 
 use crate::agent_status::Activity;
 use crate::agent_tasks::Source;
 use crate::app::{tasks_view, todo_dispatch, AppState};
-use crate::models::{SessionStatus, TodoState};
+use crate::models::{SessionCost, SessionStatus, TodoState};
 
 /// How far back the open conversation goes. Enough to scroll through the
 /// morning; not so much that the snapshot stops being phone-sized.
@@ -58,6 +59,12 @@ pub struct Snapshot {
     /// publishing my conversation", and an open phone kept polling forever
     /// while its thread silently stopped growing.
     pub open: Option<String>,
+    /// Consults between agents, in flight or just settled. Settled ones stay
+    /// only briefly (see `comms_tick::SettledConsult`) — long enough for the
+    /// control socket to notice, not a history.
+    pub consults: Vec<ConsultView>,
+    /// Estimated spend across every agent on screen (see `models::cost`).
+    pub cost: SessionCost,
     /// Seconds since the epoch, so the page can show staleness if the desktop
     /// goes away mid-session.
     pub at: i64,
@@ -69,6 +76,8 @@ pub struct ProjectView {
     pub name: String,
     /// Dev servers running in this project, reachable from the phone.
     pub servers: Vec<ServerView>,
+    /// What this project's agents have spent between them.
+    pub cost: SessionCost,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -108,6 +117,9 @@ pub struct AgentView {
     /// That item's steps, as the agent reports them.
     pub steps: Vec<StepView>,
     pub queued: Vec<String>,
+    /// The whole queue, finished items included, with the ids a caller needs
+    /// to edit it (see `control`'s `todo.*` methods).
+    pub todos: Vec<TodoView>,
     pub paused: bool,
     /// Why the queue is holding, when it is.
     pub holding: Option<String>,
@@ -137,6 +149,41 @@ pub struct AgentView {
     /// was recent. The service worker reads it to tell "finished" apart from
     /// "needs you" — the push itself carries no payload to say which.
     pub finished_ago: Option<i64>,
+    /// Tokens and estimated dollars, once the journal has recorded any.
+    pub cost: Option<SessionCost>,
+    /// Where the conversation is journalled. Never sent: it is here for the
+    /// control socket's `agent.messages`, which reads the file on its own
+    /// thread rather than asking the event loop to.
+    #[serde(skip)]
+    pub journal: Option<(crate::agent_tasks::Provider, PathBuf)>,
+    /// You have this agent's pane focused, or did a moment ago. Not sent:
+    /// the control socket reads it to refuse keys that would land in the
+    /// middle of yours.
+    #[serde(skip)]
+    pub user_at: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TodoView {
+    pub id: String,
+    pub text: String,
+    /// "pending" | "running" | "done"
+    pub state: TodoState,
+    /// `{"type": "task"}`, or `{"type": "consult", "ticket", "from"}` for a
+    /// peer's question waiting its turn.
+    pub kind: crate::models::TodoKind,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsultView {
+    pub ticket: String,
+    pub project_id: String,
+    /// Short ids of the asker and the agent asked.
+    pub from: String,
+    pub to: String,
+    /// "queued" | "delivered", then the reply's own status once it has one:
+    /// "answered" | "timeout" | …
+    pub status: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -235,7 +282,7 @@ fn dev_servers(state: &AppState) -> std::collections::HashMap<Uuid, Vec<ServerVi
 /// finished agent stays readable — the journal outlives the process, and being
 /// unable to read what an agent did once it stopped is a poor reason to have
 /// walked to the desk.
-fn journal(state: &AppState, session_id: Uuid) -> Option<(crate::agent_tasks::Provider, PathBuf)> {
+pub fn journal(state: &AppState, session_id: Uuid) -> Option<(crate::agent_tasks::Provider, PathBuf)> {
     if let Some(tracker) = state.system.agent_tasks.get(&session_id) {
         if let Some(Source::File(path)) = tracker.source() {
             return Some((tracker.provider(), path.clone()));
@@ -297,6 +344,7 @@ fn publish_with(state: &AppState, shared: &Shared, open: Option<(Vec<Message>, u
             id: workspace.id.to_string(),
             name: workspace.name.clone(),
             servers: servers.get(&workspace.id).cloned().unwrap_or_default(),
+            cost: state.workspace_cost(workspace.id),
         })
         .collect();
 
@@ -366,6 +414,16 @@ fn publish_with(state: &AppState, shared: &Shared, open: Option<(Vec<Message>, u
                     .filter(|item| item.state == TodoState::Pending)
                     .map(|item| item.text.clone())
                     .collect(),
+                todos: queue
+                    .items
+                    .iter()
+                    .map(|item| TodoView {
+                        id: item.id.to_string(),
+                        text: item.text.clone(),
+                        state: item.state,
+                        kind: item.kind.clone(),
+                    })
+                    .collect(),
                 paused: queue.paused,
                 holding,
                 prompt: question,
@@ -394,6 +452,9 @@ fn publish_with(state: &AppState, shared: &Shared, open: Option<(Vec<Message>, u
                     .remote_finished
                     .get(&session.short_id())
                     .map(|at| (chrono::Utc::now() - *at).num_seconds()),
+                cost: state.session_cost(session.id),
+                journal: journal(state, session.id),
+                user_at: state.user_is_at(session.id),
             });
         }
     }
@@ -406,9 +467,34 @@ fn publish_with(state: &AppState, shared: &Shared, open: Option<(Vec<Message>, u
         _ => 3,
     });
 
+    let comms = &state.system.comms;
+    let consults = comms
+        .pending
+        .iter()
+        .map(|consult| ConsultView {
+            ticket: consult.ticket.clone(),
+            project_id: consult.workspace_id.to_string(),
+            from: consult.from_short.clone(),
+            to: consult.to_short.clone(),
+            status: match consult.delivered {
+                true => "delivered",
+                false => "queued",
+            }
+            .to_string(),
+        })
+        .chain(comms.settled.iter().map(|consult| ConsultView {
+            ticket: consult.ticket.clone(),
+            project_id: consult.workspace_id.to_string(),
+            from: consult.from_short.clone(),
+            to: consult.to_short.clone(),
+            status: consult.status.clone(),
+        }))
+        .collect();
+
     if let Ok(mut snapshot) = shared.lock() {
         snapshot.projects = projects;
         snapshot.agents = agents;
+        snapshot.consults = consults;
         // Through get_session, not straight off the uuid: a focus left
         // pointing at a deleted session publishes as "none", so the page
         // knows to claim it afresh rather than trusting a ghost.
@@ -417,10 +503,100 @@ fn publish_with(state: &AppState, shared: &Shared, open: Option<(Vec<Message>, u
             .remote_focus
             .and_then(|id| state.get_session(id))
             .map(|session| session.short_id());
+        snapshot.cost = state.total_cost();
         snapshot.at = chrono::Utc::now().timestamp();
     }
 }
 
+/// Check a request for a new agent against what is running now, and name
+/// the project it is for by id. The phone and the control socket both ask
+/// before queueing anything, so a typo is refused on the spot instead of
+/// timing out `AGENT_START_TIMEOUT` later with nothing to say why.
+pub fn vet_new_agent(
+    snapshot: &Snapshot,
+    wanted: &str,
+    provider: &str,
+    options: &crate::app::SpawnOptions,
+) -> Result<String, (&'static str, String)> {
+    if !matches!(provider, "claude" | "codex") {
+        return Err(("bad_params", format!("no provider {provider} — claude or codex")));
+    }
+    if let Some(alias) = &options.alias {
+        if !crate::comms::alias_is_valid(alias) {
+            return Err((
+                "bad_params",
+                format!("alias {alias:?} must be 1-24 letters, digits, - or _"),
+            ));
+        }
+    }
+    let project = snapshot
+        .projects
+        .iter()
+        .find(|project| project.id == wanted)
+        .or_else(|| {
+            let named: Vec<_> = snapshot
+                .projects
+                .iter()
+                .filter(|project| project.name == wanted)
+                .collect();
+            (named.len() == 1).then(|| named[0])
+        })
+        .ok_or_else(|| ("no_such_project", format!("no project {wanted}")))?;
+    if let Some(alias) = &options.alias {
+        let taken = snapshot.agents.iter().any(|agent| {
+            agent.project_id == project.id
+                && agent
+                    .alias
+                    .as_deref()
+                    .is_some_and(|taken| taken.eq_ignore_ascii_case(alias))
+        });
+        if taken {
+            return Err(("alias_taken", format!("{alias} is taken in {}", project.name)));
+        }
+    }
+    // A successor takes over a checkout in its own project; anything else
+    // would quietly start a fresh agent nobody asked for.
+    if let Some(predecessor) = &options.successor_of {
+        let found = snapshot.agents.iter().any(|agent| {
+            agent.project_id == project.id && agent.id.eq_ignore_ascii_case(predecessor)
+        });
+        if !found {
+            return Err((
+                "no_such_agent",
+                format!("no agent {predecessor} in {}", project.name),
+            ));
+        }
+    }
+    Ok(project.id.clone())
+}
+
+/// How long a request for a new agent waits to see it. Long enough for
+/// `git worktree add` on a large repository; a plain spawn shows up on the
+/// next tick.
+pub const AGENT_START_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Block until the published snapshot lists `short_id`. For a caller that
+/// asked for an agent and wants to address it straight away: the request was
+/// queued, not performed, so the only way to know it worked is to watch for
+/// the result. False if it never appeared — the spawn failed, or is slower
+/// than `timeout`.
+pub fn wait_for_agent(shared: &Shared, short_id: &str, timeout: Duration) -> bool {
+    let started = std::time::Instant::now();
+    loop {
+        let listed = shared
+            .lock()
+            .map(|snapshot| snapshot.agents.iter().any(|agent| agent.id == short_id))
+            .unwrap_or(false);
+        if listed {
+            return true;
+        }
+        if started.elapsed() >= timeout {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
 /// Resolve a short id from the phone back to a session.
 pub fn session_for(state: &AppState, short_id: &str) -> Option<Uuid> {
     state
@@ -447,6 +623,28 @@ fn screen_prompt(state: &AppState, session_id: Uuid) -> Option<Prompt> {
     prompt::parse(&parser.screen().contents())
 }
 
+/// Most scrollback `screen_text` will replay. Rebuilding it re-parses the raw
+/// output buffer, which is cheap at a few thousand rows and not at any size.
+pub const MAX_SCROLLBACK: u16 = 5000;
+
+/// What an agent's pane shows, as text: the live screen, or with `scrollback`
+/// rows of history above it replayed from the raw output (see `tui::replay`).
+/// Trailing blank rows are dropped — a half-empty screen is not output.
+pub fn screen_text(state: &AppState, session_id: Uuid, scrollback: u16) -> Option<String> {
+    let parser = state.system.output_buffers.get(&session_id)?;
+    let text = match state.system.raw_output_buffers.get(&session_id) {
+        Some(raw) if scrollback > 0 && !raw.bytes.is_empty() => {
+            let (rows, cols) = parser.screen().size();
+            let rows = rows.saturating_add(scrollback.min(MAX_SCROLLBACK));
+            crate::tui::replay::create_replay_parser(raw, cols, rows)
+                .screen()
+                .contents()
+        }
+        _ => parser.screen().contents(),
+    };
+    Some(text.trim_end().to_string())
+}
+
 /// The last non-empty lines of an agent's output, for deciding whether to
 /// approve without walking to the desk.
 fn output_tail(state: &AppState, session_id: Uuid, lines: usize) -> Vec<String> {
@@ -590,6 +788,25 @@ mod tests {
         assert!(closed.tail.is_empty() && closed.messages.is_empty());
     }
 
+    /// The live screen holds what fits in the pane; asking for scrollback
+    /// replays the raw output to reach what scrolled off the top.
+    #[test]
+    fn a_screen_read_can_reach_back_past_the_pane() {
+        let (mut state, busy, _blocked) = state_with_agents();
+        state
+            .system
+            .create_session_buffers(busy, 5, 40, &AgentType::Claude);
+        let output: String = (1..=20).map(|i| format!("line {i}\r\n")).collect();
+        state.system.output_buffers.get_mut(&busy).unwrap().process(output.as_bytes());
+        state.system.raw_output_buffers.get_mut(&busy).unwrap().append(output.as_bytes());
+
+        let live = screen_text(&state, busy, 0).unwrap();
+        assert!(live.ends_with("line 20") && !live.contains("line 10"), "{live}");
+
+        let deep = screen_text(&state, busy, 100).unwrap();
+        assert!(deep.starts_with("line 1\n") && deep.ends_with("line 20"), "{deep}");
+    }
+
     /// The failure this guards is the phone showing Approve/Deny for a
     /// question nobody asked — the hook fires for plain idleness too.
     #[test]
@@ -691,6 +908,7 @@ mod tests {
             running: None,
             steps: Vec::new(),
             queued: Vec::new(),
+            todos: Vec::new(),
             paused: false,
             holding: None,
             prompt: None,
@@ -699,6 +917,9 @@ mod tests {
             msg_reset: false,
             tail: Vec::new(),
             finished_ago: None,
+            cost: None,
+            journal: None,
+            user_at: false,
         });
 
         // Up to date: nothing owed.
@@ -742,6 +963,7 @@ mod tests {
             running: None,
             steps: Vec::new(),
             queued: Vec::new(),
+            todos: Vec::new(),
             paused: false,
             holding: None,
             prompt: None,
@@ -754,6 +976,9 @@ mod tests {
             msg_reset: false,
             tail: Vec::new(),
             finished_ago: None,
+            cost: None,
+            journal: None,
+            user_at: false,
         });
 
         let ahead = since(&snapshot, 347);
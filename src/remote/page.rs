@@ -2201,6 +2201,7 @@ function render() {
     named(a),
     a.running || (a.queued.length ? a.queued.length + " queued" : null),
     a.holding,
+    a.cost && "$" + a.cost.estimated_cost_usd.toFixed(2),
   ].filter(Boolean).join(" · ");
   document.getElementById("hdot").className = "dot " + a.status;
   document.getElementById("cycle").hidden =
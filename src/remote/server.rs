@@ -10,9 +10,10 @@ use std::net::{IpAddr, SocketAddr};
 use std::process::Command;
 use tiny_http::{Header, Response, Server};
 use tokio::sync::mpsc;
+use uuid::Uuid;
 
 use super::{page, Shared};
-use crate::app::Action;
+use crate::app::{Action, SpawnOptions};
 
 /// Where the server listens, and the token it demands.
 #[derive(Debug, Clone)]
@@ -49,11 +50,48 @@ pub enum RemoteCommand {
     /// The conversation the phone currently has open. Only this agent's full
     /// history is published, so the snapshot stays small.
     Focus { agent: String },
-    /// Start a new agent in a project. `agent` carries the project id and
-    /// `text` the provider, since every write endpoint speaks that shape.
-    NewAgent { project: String, provider: String },
+    /// Start a new agent in a project. On the phone's endpoint `agent`
+    /// carries the project id and `text` the provider, since every write
+    /// endpoint speaks that shape. `id` is the session id to give it, chosen
+    /// by the sender so it can wait for the agent to appear.
+    NewAgent {
+        project: String,
+        provider: String,
+        id: Uuid,
+        options: SpawnOptions,
+    },
     /// A device asking to be told when an agent needs you.
     Subscribe { endpoint: String },
+    /// Take an item out of an agent's queue. `todo` is the item's full id,
+    /// already resolved against the snapshot by whoever sent this.
+    TodoRemove { agent: String, todo: Uuid },
+    /// Move an item `by` places, earlier (negative) or later.
+    TodoMove { agent: String, todo: Uuid, by: isize },
+    /// Hold the queue, or let it run again.
+    TodoPause { agent: String, paused: bool },
+    /// Drop the items the agent has finished.
+    TodoClearDone { agent: String },
+    /// Interrupt the agent's turn, the way Ctrl-C at the desk does. The
+    /// process lives on.
+    Stop { agent: String },
+    /// Start a stopped agent again, back in its own conversation.
+    Restart { agent: String },
+    /// End the agent's process. The session stays, stopped.
+    Kill { agent: String },
+    /// Remove the agent, its process and its worktree.
+    Delete { agent: String },
+    /// Press keys in the agent's pane, one write per key. Refused for a
+    /// session you are typing into (see `AppState::user_is_at`).
+    Keys { agent: String, keys: Vec<Vec<u8>> },
+    /// Read the agent's screen, with `scrollback` rows of history above it.
+    /// A read, but of state only the event loop holds, so it is asked for
+    /// here and answered on `reply`; a sender dropped unanswered means the
+    /// agent was not there.
+    Screen {
+        agent: String,
+        scrollback: u16,
+        reply: std::sync::mpsc::Sender<String>,
+    },
 }
 
 /// This machine's Tailscale address, if it is on a tailnet.
@@ -233,9 +271,7 @@ fn handle(
         ("POST", "/api/focus") => {
             command_from(request, commands, |agent, _| Some(RemoteCommand::Focus { agent }))
         }
-        ("POST", "/api/new-agent") => command_from(request, commands, |project, provider| {
-            Some(RemoteCommand::NewAgent { project, provider })
-        }),
+        ("POST", "/api/new-agent") => new_agent(request, shared, commands),
         _ => status(404, "not found"),
     }
 }
@@ -453,6 +489,51 @@ fn command_from(
     }
 }
 
+/// Start an agent and answer at once with the short id it will have: 202,
+/// because it is queued rather than started. This server answers one request
+/// at a time, so waiting here for the agent to appear would hold up every
+/// other phone request for as long as a `git worktree add` takes; the page
+/// sees the agent arrive in its next poll of the state. The body is the
+/// usual pair — project id and provider — plus any of `SpawnOptions`'
+/// fields, checked before anything is queued.
+fn new_agent(
+    request: &mut tiny_http::Request,
+    shared: &Shared,
+    commands: &mpsc::UnboundedSender<RemoteCommand>,
+) -> Response<std::io::Cursor<Vec<u8>>> {
+    let mut body = String::new();
+    if std::io::Read::read_to_string(request.as_reader(), &mut body).is_err() {
+        return status(400, "unreadable body");
+    }
+    let Some((project, provider)) = parse_command_body(&body) else {
+        return status(400, "expected {\"agent\": \"…\"}");
+    };
+    let Ok(options) = serde_json::from_str::<SpawnOptions>(&body) else {
+        return status(400, "unreadable options");
+    };
+    let vetted = match shared.lock() {
+        Ok(snapshot) => super::vet_new_agent(&snapshot, &project, &provider, &options),
+        Err(_) => return status(503, "state is momentarily unreadable"),
+    };
+    let project = match vetted {
+        Ok(project) => project,
+        Err((_, message)) => return status(400, &message),
+    };
+    let id = Uuid::new_v4();
+    let command = RemoteCommand::NewAgent {
+        project,
+        provider,
+        id,
+        options,
+    };
+    if commands.send(command).is_err() {
+        return status(503, "workbench is shutting down");
+    }
+    let short = crate::models::Session::short_id_of(id);
+    json(serde_json::json!({"ok": true, "pending": true, "agent": short}).to_string())
+        .with_status_code(202)
+}
+
 /// `{"agent": "ab12cd34", "text": "…"}` → the pair, or nothing if malformed.
 fn parse_command_body(body: &str) -> Option<(String, String)> {
     let value: serde_json::Value = serde_json::from_str(body).ok()?;
@@ -84,6 +84,23 @@ pub fn read_more(path: &Path, provider: Provider, from: Cursor, out: &mut Vec<Me
         consumed += reader.read_line(&mut partial).unwrap_or(0) as u64;
     }
 
+    Cursor(consumed + parse(&mut reader, provider, out))
+}
+
+/// The whole journal, start to end — for a search, which is no use if it
+/// only sees the recent end (see `crate::grep`). Costs what the file costs;
+/// nothing on the tick calls it.
+pub fn read_all(path: &Path, provider: Provider) -> Vec<Message> {
+    let mut out = Vec::new();
+    if let Ok(file) = std::fs::File::open(path) {
+        parse(&mut std::io::BufReader::new(file), provider, &mut out);
+    }
+    out
+}
+
+/// Parse whole lines to the end of `reader`, returning the bytes consumed.
+fn parse(reader: &mut impl std::io::BufRead, provider: Provider, out: &mut Vec<Message>) -> u64 {
+    let mut consumed = 0;
     let mut line = String::new();
     loop {
         line.clear();
@@ -107,7 +124,7 @@ pub fn read_more(path: &Path, provider: Provider, from: Cursor, out: &mut Vec<Me
             Err(_) => break,
         }
     }
-    Cursor(consumed)
+    consumed
 }
 
 /// One-shot read of a session's recent conversation. Production always reads
@@ -227,6 +227,18 @@ fn render_quickref_tab(frame: &mut Frame, area: Rect, state: &AppState) {
         Span::styled("  m                  ", key_style),
         Span::raw("Merge worktree into main"),
     ]));
+    lines.push(Line::from(vec![
+        Span::styled("  Tab (in merge)     ", key_style),
+        Span::raw("Merge / squash / rebase / keep branch"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  o/f/a (conflict)   ", key_style),
+        Span::raw("Hand to agent / fresh agent / abort"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  f                  ", key_style),
+        Span::raw("Review the branch's diff"),
+    ]));
 
     // -- Tasks --
     lines.push(Line::from(""));
@@ -277,6 +289,14 @@ fn render_quickref_tab(frame: &mut Frame, area: Rect, state: &AppState) {
         Span::styled("  m                  ", key_style),
         Span::raw("Merge selected attempt"),
     ]));
+    lines.push(Line::from(vec![
+        Span::styled("  f                  ", key_style),
+        Span::raw("Review the attempt's diff"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  r                  ", key_style),
+        Span::raw("Run the verify command again"),
+    ]));
     lines.push(Line::from(vec![
         Span::styled("  d                  ", key_style),
         Span::raw("Discard attempt"),
@@ -0,0 +1,434 @@
+//! Full-screen review of an agent's branch: the changed files down the
+//! left, the selected file's hunks on the right.
+//!
+//! Colouring is deliberately shallow. Each line is tokenised on its own —
+//! keywords, strings, numbers and line comments for the languages agents
+//! mostly write — because a diff hunk starts mid-file, so a real parser
+//! would be guessing at the state it opens in anyway. Removed lines are not
+//! highlighted at all: they are going away, and one flat colour reads
+//! faster than syntax when scanning for what changed.
+
+use crate::app::AppState;
+use crate::git::{DiffLineKind, FileDiff, Hunk};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, state: &mut AppState) {
+    let t = crate::theme::current();
+    let area = centered_rect(94, 92, frame.area());
+    frame.render_widget(Clear, area);
+
+    let Some(view) = state.ui.diff_view.as_mut() else {
+        return;
+    };
+
+    let files = view.files();
+    let (additions, deletions) = files
+        .iter()
+        .fold((0, 0), |(a, d), f| (a + f.additions(), d + f.deletions()));
+    let summary = match &view.files {
+        Some(Ok(_)) => format!(" {} files  +{} -{} ", files.len(), additions, deletions),
+        _ => String::new(),
+    };
+    let block = Block::default()
+        .title(format!(" Diff · {} ", view.title))
+        .title_bottom(Line::from(Span::styled(summary, Style::default().fg(t.fg_dim))))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(t.info))
+        .style(Style::default().bg(t.bg));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let message = match &view.files {
+        None => Some(("Running git diff…", t.fg_dim)),
+        Some(Err(err)) => Some((err.as_str(), t.error)),
+        Some(Ok(files)) if files.is_empty() => Some(("No changes on this branch yet.", t.fg_dim)),
+        Some(Ok(_)) => None,
+    };
+    if let Some((text, color)) = message {
+        let paragraph = Paragraph::new(Line::from(Span::styled(
+            format!(" {text}"),
+            Style::default().fg(color),
+        )));
+        frame.render_widget(paragraph, inner);
+        return;
+    }
+
+    let list_width = (inner.width / 3).clamp(20, 48);
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(list_width), Constraint::Min(1)])
+        .split(inner);
+
+    render_file_list(frame, chunks[0], view.files(), view.selected_file);
+
+    let diff_block = Block::default()
+        .borders(Borders::LEFT)
+        .border_style(Style::default().fg(t.border));
+    let diff_area = diff_block.inner(chunks[1]);
+    frame.render_widget(diff_block, chunks[1]);
+
+    // Header row for the file, then the hunks below it.
+    let header_area = Rect { height: 1, ..diff_area };
+    let body_area = Rect {
+        y: diff_area.y + 1,
+        height: diff_area.height.saturating_sub(1),
+        ..diff_area
+    };
+    view.page = body_area.height as usize;
+
+    let Some(file) = view.current_file() else {
+        return;
+    };
+    frame.render_widget(Paragraph::new(file_header(file)), header_area);
+    frame.render_widget(Paragraph::new(file_body(file, view.scroll, view.page)), body_area);
+}
+
+fn render_file_list(frame: &mut Frame, area: Rect, files: &[FileDiff], selected: usize) {
+    let t = crate::theme::current();
+    let height = area.height as usize;
+    let offset = selected.saturating_sub(height.saturating_sub(1));
+    let width = area.width as usize;
+
+    let lines: Vec<Line> = files
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(height)
+        .map(|(idx, file)| {
+            let counts = format!(" +{} -{}", file.additions(), file.deletions());
+            let room = width.saturating_sub(3 + counts.chars().count());
+            let mut line = Line::from(vec![
+                Span::styled(
+                    format!("{} ", file.status.marker()),
+                    Style::default().fg(status_color(file)),
+                ),
+                Span::styled(
+                    format!("{:<room$}", truncate_left(&file.path, room)),
+                    Style::default().fg(t.fg),
+                ),
+                Span::styled(counts, Style::default().fg(t.fg_dim)),
+            ]);
+            if idx == selected {
+                line = line.style(Style::default().bg(t.selection_bg).add_modifier(Modifier::BOLD));
+            }
+            line
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn status_color(file: &FileDiff) -> ratatui::style::Color {
+    let t = crate::theme::current();
+    match file.status {
+        crate::git::FileStatus::Added => t.success,
+        crate::git::FileStatus::Deleted => t.error,
+        crate::git::FileStatus::Modified => t.warning,
+        crate::git::FileStatus::Renamed => t.info,
+    }
+}
+
+/// Keep the end of a path — the file name is the part worth reading.
+fn truncate_left(path: &str, room: usize) -> String {
+    let len = path.chars().count();
+    if len <= room {
+        return path.to_string();
+    }
+    let keep = room.saturating_sub(1);
+    let tail: String = path.chars().skip(len - keep).collect();
+    format!("…{tail}")
+}
+
+fn file_header(file: &FileDiff) -> Line<'static> {
+    let t = crate::theme::current();
+    let mut spans = vec![Span::styled(
+        format!(" {}", file.path),
+        Style::default().fg(t.fg).add_modifier(Modifier::BOLD),
+    )];
+    if let Some(old) = &file.old_path {
+        spans.push(Span::styled(format!("  ← {old}"), Style::default().fg(t.fg_dim)));
+    }
+    spans.push(Span::styled(
+        format!("  +{} -{}", file.additions(), file.deletions()),
+        Style::default().fg(t.fg_dim),
+    ));
+    Line::from(spans)
+}
+
+/// The rows of `file` from `scroll`, laid out as `FileDiff::rows` counts
+/// them: each hunk's header, then its lines.
+fn file_body(file: &FileDiff, scroll: usize, height: usize) -> Vec<Line<'static>> {
+    let t = crate::theme::current();
+    if file.hunks.is_empty() {
+        let note = if file.binary {
+            "Binary file — not shown".to_string()
+        } else if let Some(old) = &file.old_path {
+            format!("Renamed from {old}, contents unchanged")
+        } else {
+            "No content changes".to_string()
+        };
+        return vec![Line::from(Span::styled(format!(" {note}"), Style::default().fg(t.fg_dim)))];
+    }
+
+    let syntax = syntax_for(&file.path);
+    file.hunks
+        .iter()
+        .flat_map(|hunk| {
+            std::iter::once(hunk_header(hunk))
+                .chain(hunk.lines.iter().map(move |line| diff_line(line, syntax)))
+        })
+        .skip(scroll)
+        .take(height)
+        .collect()
+}
+
+fn hunk_header(hunk: &Hunk) -> Line<'static> {
+    let t = crate::theme::current();
+    // "@@ -a,b +c,d @@ fn enclosing()" — the ranges, then git's guess at
+    // the enclosing definition, which is worth keeping but quieter.
+    let split = hunk.header[2..]
+        .find("@@")
+        .map(|idx| idx + 4)
+        .unwrap_or(hunk.header.len());
+    let (ranges, context) = hunk.header.split_at(split);
+    Line::from(vec![
+        Span::styled(ranges.to_string(), Style::default().fg(t.info)),
+        Span::styled(context.to_string(), Style::default().fg(t.fg_faint)),
+    ])
+}
+
+fn diff_line(line: &crate::git::DiffLine, syntax: Option<&'static Syntax>) -> Line<'static> {
+    let t = crate::theme::current();
+    let text = line.text.replace('\t', "    ");
+    let (marker, marker_color) = match line.kind {
+        DiffLineKind::Added => ("+", t.success),
+        DiffLineKind::Removed => ("-", t.error),
+        DiffLineKind::Context => (" ", t.fg_faint),
+        DiffLineKind::Note => {
+            return Line::from(Span::styled(
+                text,
+                Style::default().fg(t.fg_faint).add_modifier(Modifier::ITALIC),
+            ));
+        }
+    };
+
+    let mut spans = vec![Span::styled(
+        marker,
+        Style::default().fg(marker_color).add_modifier(Modifier::BOLD),
+    )];
+    match line.kind {
+        DiffLineKind::Removed => spans.push(Span::styled(text, Style::default().fg(t.error))),
+        DiffLineKind::Added => spans.extend(highlight(&text, syntax, Style::default().fg(t.fg))),
+        _ => spans.extend(highlight(&text, syntax, Style::default().fg(t.fg_dim))),
+    }
+    Line::from(spans)
+}
+
+/// Just enough of a language to colour one line of it.
+struct Syntax {
+    keywords: &'static [&'static str],
+    line_comment: &'static str,
+    quotes: &'static [char],
+}
+
+const RUST: Syntax = Syntax {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+        "true", "type", "unsafe", "use", "where", "while",
+    ],
+    line_comment: "//",
+    // Not `'`: lifetimes would open a "string" that never closes.
+    quotes: &['"'],
+};
+
+const SCRIPT: Syntax = Syntax {
+    keywords: &[
+        "async", "await", "break", "case", "catch", "class", "const", "continue", "default",
+        "delete", "do", "else", "enum", "export", "extends", "false", "finally", "for", "from",
+        "function", "if", "implements", "import", "in", "instanceof", "interface", "let", "new",
+        "null", "private", "public", "readonly", "return", "super", "switch", "this", "throw",
+        "true", "try", "type", "typeof", "undefined", "var", "void", "while", "yield",
+    ],
+    line_comment: "//",
+    quotes: &['"', '\'', '`'],
+};
+
+const PYTHON: Syntax = Syntax {
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "False", "finally", "for", "from", "global", "if", "import",
+        "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return", "self",
+        "True", "try", "while", "with", "yield",
+    ],
+    line_comment: "#",
+    quotes: &['"', '\''],
+};
+
+const GO: Syntax = Syntax {
+    keywords: &[
+        "break", "case", "chan", "const", "continue", "default", "defer", "else", "false", "for",
+        "func", "go", "goto", "if", "import", "interface", "map", "nil", "package", "range",
+        "return", "select", "struct", "switch", "true", "type", "var",
+    ],
+    line_comment: "//",
+    quotes: &['"', '`'],
+};
+
+const C_FAMILY: Syntax = Syntax {
+    keywords: &[
+        "break", "case", "catch", "char", "class", "const", "continue", "default", "delete", "do",
+        "double", "else", "enum", "extends", "false", "final", "float", "for", "if", "import",
+        "int", "long", "namespace", "new", "null", "nullptr", "private", "protected", "public",
+        "return", "static", "struct", "switch", "template", "this", "throw", "true", "try",
+        "typedef", "unsigned", "virtual", "void", "while",
+    ],
+    line_comment: "//",
+    quotes: &['"'],
+};
+
+const SHELL: Syntax = Syntax {
+    keywords: &[
+        "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+        "in", "local", "return", "then", "while",
+    ],
+    line_comment: "#",
+    quotes: &['"', '\''],
+};
+
+const CONFIG: Syntax = Syntax {
+    keywords: &["true", "false", "null"],
+    line_comment: "#",
+    quotes: &['"', '\''],
+};
+
+fn syntax_for(path: &str) -> Option<&'static Syntax> {
+    let ext = path.rsplit_once('.').map(|(_, ext)| ext)?;
+    let syntax = match ext {
+        "rs" => &RUST,
+        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => &SCRIPT,
+        "py" => &PYTHON,
+        "go" => &GO,
+        "c" | "h" | "cc" | "cpp" | "hpp" | "java" | "kt" | "cs" | "swift" => &C_FAMILY,
+        "sh" | "bash" | "zsh" => &SHELL,
+        "toml" | "yaml" | "yml" => &CONFIG,
+        _ => return None,
+    };
+    Some(syntax)
+}
+
+/// Colour one line: keywords in the accent, strings and numbers in their
+/// own colours, a trailing line comment faint. Everything else keeps
+/// `base`, which is what tells an added line from a context one.
+fn highlight(text: &str, syntax: Option<&Syntax>, base: Style) -> Vec<Span<'static>> {
+    let Some(syntax) = syntax else {
+        return vec![Span::styled(text.to_string(), base)];
+    };
+    let t = crate::theme::current();
+    let chars: Vec<char> = text.chars().collect();
+    let comment: Vec<char> = syntax.line_comment.chars().collect();
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let push = |spans: &mut Vec<Span<'static>>, plain: &mut String, token: String, style: Style| {
+        if !plain.is_empty() {
+            spans.push(Span::styled(std::mem::take(plain), base));
+        }
+        spans.push(Span::styled(token, style));
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if chars[i..].starts_with(&comment) {
+            let rest: String = chars[i..].iter().collect();
+            let style = Style::default().fg(t.fg_faint).add_modifier(Modifier::ITALIC);
+            push(&mut spans, &mut plain, rest, style);
+            i = chars.len();
+        } else if syntax.quotes.contains(&c) {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            i = (i + 1).min(chars.len());
+            let token: String = chars[start..i].iter().collect();
+            push(&mut spans, &mut plain, token, Style::default().fg(t.command));
+        } else if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if c.is_ascii_digit() {
+                push(&mut spans, &mut plain, word, Style::default().fg(t.special));
+            } else if syntax.keywords.contains(&word.as_str()) {
+                push(&mut spans, &mut plain, word, Style::default().fg(t.accent));
+            } else {
+                plain.push_str(&word);
+            }
+        } else {
+            plain.push(c);
+            i += 1;
+        }
+    }
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Rust line splits into keyword, plain, string and comment spans,
+    /// and a lifetime's quote doesn't swallow the rest of the line.
+    #[test]
+    fn highlight_colours_keywords_strings_and_comments() {
+        let t = crate::theme::current();
+        let base = Style::default().fg(t.fg);
+        let spans = highlight(
+            "let s: &'a str = \"x\"; // note",
+            syntax_for("src/main.rs"),
+            base,
+        );
+        let pieces: Vec<(&str, Style)> =
+            spans.iter().map(|s| (s.content.as_ref(), s.style)).collect();
+
+        assert_eq!(pieces[0], ("let", Style::default().fg(t.accent)));
+        assert_eq!(pieces[1], (" s: &'a str = ", base));
+        assert_eq!(pieces[2], ("\"x\"", Style::default().fg(t.command)));
+        assert_eq!(pieces[3], ("; ", base));
+        assert_eq!(pieces[4].0, "// note");
+
+        // Files we have no syntax for come through as one plain span.
+        assert_eq!(highlight("let x", syntax_for("NOTES"), base).len(), 1);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::app::AppState;
 use crate::git;
+use crate::models::MergeStrategy;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -10,7 +11,7 @@ use ratatui::{
 
 pub fn render(frame: &mut Frame, state: &AppState) {
     let t = crate::theme::current();
-    let area = centered_rect(50, 25, frame.area());
+    let area = centered_rect(56, 35, frame.area());
 
     // Clear the background
     frame.render_widget(Clear, area);
@@ -57,35 +58,52 @@ pub fn render(frame: &mut Frame, state: &AppState) {
         ("unknown".to_string(), "main".to_string())
     };
 
-    // Split into: message, branch info, help
+    // Split into: message, branch info, strategy, help
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Warning message
             Constraint::Length(3), // Branch info
+            Constraint::Length(3), // Strategy picker
             Constraint::Length(2), // Help
         ])
         .split(inner);
 
     let message_area = chunks[0];
     let branch_area = chunks[1];
-    let help_area = chunks[2];
+    let strategy_area = chunks[2];
+    let help_area = chunks[3];
+
+    let strategy = state.ui.merge_strategy;
+    let blocked = strategy.lands() && state.ui.merge_workspace_dirty;
 
     // Warning message
-    let message = Paragraph::new(vec![
-        Line::from(vec![
+    let mut lines = Vec::new();
+    if state.ui.merge_worktree_dirty {
+        lines.push(Line::from(vec![
             Span::styled("⚠ ", Style::default().fg(t.warning)),
+            Span::styled("Worktree has uncommitted changes", Style::default().fg(t.fg)),
+        ]));
+        lines.push(Line::from(vec![Span::styled(
+            "  They are committed first",
+            Style::default().fg(t.fg_dim),
+        )]));
+    } else {
+        lines.push(Line::from(vec![Span::styled(
+            "  Land the agent's branch?",
+            Style::default().fg(t.fg),
+        )]));
+    }
+    if blocked {
+        lines.push(Line::from(vec![
+            Span::styled("✗ ", Style::default().fg(t.error)),
             Span::styled(
-                "Worktree has uncommitted changes",
-                Style::default().fg(t.fg),
+                "Workspace has uncommitted changes — only branch can run",
+                Style::default().fg(t.error),
             ),
-        ]),
-        Line::from(vec![Span::styled(
-            "  Commit all changes and merge?",
-            Style::default().fg(t.fg_dim),
-        )]),
-    ]);
-    frame.render_widget(message, message_area);
+        ]));
+    }
+    frame.render_widget(Paragraph::new(lines), message_area);
 
     // Branch info
     let branch_info = Paragraph::new(vec![
@@ -106,19 +124,51 @@ pub fn render(frame: &mut Frame, state: &AppState) {
     ]);
     frame.render_widget(branch_info, branch_area);
 
+    frame.render_widget(Paragraph::new(strategy_lines(strategy)), strategy_area);
+
     // Help
+    let confirm_style = if blocked {
+        Style::default().fg(t.fg_faint)
+    } else {
+        Style::default().fg(t.success).add_modifier(Modifier::BOLD)
+    };
     let help = Paragraph::new(Line::from(vec![
-        Span::styled(
-            "[Y/Enter]",
-            Style::default().fg(t.success).add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(" Commit & Merge  "),
+        Span::styled("[Y/Enter]", confirm_style),
+        Span::raw(format!(" {}  ", strategy.verb())),
+        Span::styled("[Tab]", Style::default().fg(t.accent)),
+        Span::raw(" Strategy  "),
         Span::styled("[N/Esc]", Style::default().fg(t.error)),
         Span::raw(" Cancel"),
     ]));
     frame.render_widget(help, help_area);
 }
 
+/// The strategy picker both merge modals show: every choice on one line
+/// with the current one highlighted, and what it does underneath.
+pub fn strategy_lines(strategy: MergeStrategy) -> Vec<Line<'static>> {
+    let t = crate::theme::current();
+    let mut picker = vec![Span::styled("  Strategy: ", Style::default().fg(t.fg_dim))];
+    for choice in MergeStrategy::ALL {
+        let style = if choice == strategy {
+            Style::default()
+                .fg(t.on_accent)
+                .bg(t.accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(t.fg_dim)
+        };
+        picker.push(Span::styled(format!(" {} ", choice.label()), style));
+        picker.push(Span::raw(" "));
+    }
+    vec![
+        Line::from(picker),
+        Line::from(Span::styled(
+            format!("  {}", strategy.describe()),
+            Style::default().fg(t.fg_dim),
+        )),
+    ]
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -0,0 +1,135 @@
+use crate::app::handlers::merge_conflict::originating_agent;
+use crate::app::AppState;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// More than this and the list says how many it left out; the agent gets
+/// every file either way.
+const MAX_LISTED_FILES: usize = 8;
+
+pub fn render(frame: &mut Frame, state: &AppState) {
+    let Some(conflict) = state.ui.merge_conflict.as_deref() else {
+        return;
+    };
+    let t = crate::theme::current();
+    let area = centered_rect(60, 45, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Merge Conflict ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(t.error))
+        .style(Style::default().bg(t.bg));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4), // What stopped, and where
+            Constraint::Min(3),    // Conflicted files
+            Constraint::Length(2), // Help
+        ])
+        .split(inner);
+
+    let landing = &conflict.landing;
+    let files = &conflict.conflict.files;
+    let waiting = match &conflict.conflict.in_progress {
+        Some(dir) => format!("  Waiting in {}", dir.display()),
+        None => "  Backed out — nothing was changed".to_string(),
+    };
+    let header = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("✗ ", Style::default().fg(t.error)),
+            Span::styled(
+                format!("The {} stopped on conflicts", landing.strategy.label()),
+                Style::default().fg(t.fg),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  From: ", Style::default().fg(t.fg_dim)),
+            Span::styled(
+                landing.branch.clone(),
+                Style::default().fg(t.accent).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  Into: ", Style::default().fg(t.fg_dim)),
+            Span::styled(
+                conflict.conflict.target.clone(),
+                Style::default().fg(t.success).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(Span::styled(waiting, Style::default().fg(t.fg_dim))),
+    ]);
+    frame.render_widget(header, chunks[0]);
+
+    let mut lines: Vec<Line> = files
+        .iter()
+        .take(MAX_LISTED_FILES)
+        .map(|file| {
+            let hunks = match file.hunks.len() {
+                0 => "deleted or renamed".to_string(),
+                1 => "1 hunk".to_string(),
+                n => format!("{n} hunks"),
+            };
+            Line::from(vec![
+                Span::styled("  U ", Style::default().fg(t.error)),
+                Span::styled(file.path.clone(), Style::default().fg(t.fg)),
+                Span::styled(format!("  {hunks}"), Style::default().fg(t.fg_faint)),
+            ])
+        })
+        .collect();
+    if files.len() > MAX_LISTED_FILES {
+        lines.push(Line::from(Span::styled(
+            format!("  … and {} more", files.len() - MAX_LISTED_FILES),
+            Style::default().fg(t.fg_dim),
+        )));
+    }
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    let agent = originating_agent(state, conflict.source)
+        .and_then(|id| state.get_session(id))
+        .map(|s| s.display_name());
+    let hand_style = if agent.is_some() {
+        Style::default().fg(t.success).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(t.fg_faint)
+    };
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("[o/Enter]", hand_style),
+        Span::raw(format!(" Hand to {}  ", agent.as_deref().unwrap_or("agent"))),
+        Span::styled("[f]", Style::default().fg(t.accent)),
+        Span::raw(" Fresh agent  "),
+        Span::styled("[a]", Style::default().fg(t.warning)),
+        Span::raw(" Abort  "),
+        Span::styled("[Esc]", Style::default().fg(t.fg_dim)),
+        Span::raw(" Leave as is"),
+    ]));
+    frame.render_widget(help, chunks[2]);
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
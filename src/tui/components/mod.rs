@@ -4,7 +4,9 @@ pub mod config_window;
 pub mod create_session_dialog;
 pub mod create_workspace_dialog;
 pub mod debug_overlay;
+pub mod diff_viewer;
 pub mod merge_confirm_modal;
+pub mod merge_conflict_modal;
 pub mod output_pane;
 pub mod parallel_merge_confirm_modal;
 pub mod parallel_task_modal;
@@ -1,3 +1,4 @@
+use super::merge_confirm_modal;
 use crate::app::AppState;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -9,7 +10,7 @@ use ratatui::{
 
 pub fn render(frame: &mut Frame, state: &AppState) {
     let t = crate::theme::current();
-    let area = centered_rect(50, 25, frame.area());
+    let area = centered_rect(56, 35, frame.area());
 
     // Clear the background
     frame.render_widget(Clear, area);
@@ -39,19 +40,23 @@ pub fn render(frame: &mut Frame, state: &AppState) {
             ("unknown".to_string(), "main".to_string())
         };
 
-    // Split into: message, branch info, help
+    // Split into: message, branch info, strategy, help
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Warning message
             Constraint::Length(3), // Branch info
+            Constraint::Length(3), // Strategy picker
             Constraint::Length(2), // Help
         ])
         .split(inner);
 
     let message_area = chunks[0];
     let branch_area = chunks[1];
-    let help_area = chunks[2];
+    let strategy_area = chunks[2];
+    let help_area = chunks[3];
+
+    let strategy = state.ui.merge_strategy;
 
     // Warning message
     let message = Paragraph::new(vec![
@@ -63,7 +68,11 @@ pub fn render(frame: &mut Frame, state: &AppState) {
             ),
         ]),
         Line::from(vec![Span::styled(
-            "  Commit all changes and merge into source?",
+            if strategy.lands() {
+                "  Commit all changes and land them on source?"
+            } else {
+                "  Commit all changes and keep the branch?"
+            },
             Style::default().fg(t.fg_dim),
         )]),
     ]);
@@ -88,13 +97,20 @@ pub fn render(frame: &mut Frame, state: &AppState) {
     ]);
     frame.render_widget(branch_info, branch_area);
 
+    frame.render_widget(
+        Paragraph::new(merge_confirm_modal::strategy_lines(strategy)),
+        strategy_area,
+    );
+
     // Help
     let help = Paragraph::new(Line::from(vec![
         Span::styled(
             "[Y/Enter]",
             Style::default().fg(t.success).add_modifier(Modifier::BOLD),
         ),
-        Span::raw(" Commit & Merge  "),
+        Span::raw(format!(" {}  ", strategy.verb())),
+        Span::styled("[Tab]", Style::default().fg(t.accent)),
+        Span::raw(" Strategy  "),
         Span::styled("[N/Esc]", Style::default().fg(t.error)),
         Span::raw(" Cancel"),
     ]));
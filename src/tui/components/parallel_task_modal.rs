@@ -10,7 +10,7 @@ use ratatui::{
 
 pub fn render(frame: &mut Frame, state: &AppState) {
     let t = crate::theme::current();
-    let area = centered_rect(60, 50, frame.area());
+    let area = centered_rect(60, 60, frame.area());
 
     // Clear the background
     frame.render_widget(Clear, area);
@@ -201,6 +201,32 @@ pub fn render(frame: &mut Frame, state: &AppState) {
     };
     content.push(report_line);
 
+    // Verify command - index = agent_count + 2
+    let verify_focused = state.ui.parallel_task.editing_verify();
+    let verify = &state.ui.parallel_task.verify_command;
+    let verify_text = match (verify.is_empty(), verify_focused) {
+        (true, true) => Span::styled("_", Style::default().fg(t.fg)),
+        (true, false) => Span::styled("none (e.g. cargo test)", Style::default().fg(t.fg_faint)),
+        (false, true) => Span::styled(format!("{verify}_"), Style::default().fg(t.fg)),
+        (false, false) => Span::styled(verify.clone(), Style::default().fg(t.command)),
+    };
+    content.push(Line::from(vec![
+        if verify_focused {
+            Span::styled("  > ", Style::default().fg(t.active))
+        } else {
+            Span::raw("    ")
+        },
+        Span::styled(
+            "Verify with: ",
+            if verify_focused {
+                Style::default().fg(t.fg).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(t.fg_dim)
+            },
+        ),
+        verify_text,
+    ]));
+
     content.push(Line::from(""));
     content.push(Line::from("  ─────────────────────────────────────────"));
     content.push(Line::from(vec![
@@ -383,6 +383,35 @@ fn create_session_item<'a>(
         None => Span::raw(""),
     };
 
+    // It edited a file another agent claimed. Louder than the rest of the
+    // row: two agents are now working the same file, and only you can sort
+    // out which one should stop.
+    let claim_indicator = match state.system.comms.claim_conflicts.get(&session.id) {
+        Some(conflict) => Span::styled(
+            format!(
+                " ⚠ {} is {}'s",
+                conflict
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_default(),
+                conflict.holder
+            ),
+            Style::default().fg(t.danger).add_modifier(Modifier::BOLD),
+        ),
+        None => Span::raw(""),
+    };
+
+    // Only once the journal has recorded any — an agent whose store keeps
+    // no usage shows nothing rather than a misleading $0.00.
+    let cost_indicator = match state.session_cost(session.id) {
+        Some(cost) => Span::styled(
+            format!(" {}", cost.label()),
+            crate::tui::utils::spend_style(&cost),
+        ),
+        None => Span::raw(""),
+    };
+
     let main_spans = vec![
         Span::styled(prefix.to_string(), name_style),
         Span::styled(status_icon, Style::default().fg(status_color)),
@@ -393,8 +422,10 @@ fn create_session_item<'a>(
         Span::styled(state.session_label(session.id), name_style),
         alias_indicator,
         attention_indicator,
+        claim_indicator,
         dangerous_indicator,
         branch_indicator,
+        cost_indicator,
     ];
 
     let main_line = Line::from(main_spans);
@@ -479,6 +510,68 @@ mod tests {
         assert!(!out.contains("◆ Claude"), "{out}");
     }
 
+    /// Spend sits on the row once the journal has any, and a session with
+    /// none shows nothing rather than a `$0.00` that reads like a fact.
+    #[test]
+    fn a_session_that_has_spent_shows_it_on_its_row() {
+        use crate::agent_tasks::{Provider, Source, TaskSource, TaskTracker};
+        use std::io::Write;
+
+        let mut state = state_with_blocked_agent(Attention::Input, "waiting");
+        state.system.agent_status.clear();
+        let session_id = state.data.sessions.values().flatten().next().unwrap().id;
+        assert!(!screen(&state, 60, 10).contains('$'));
+
+        let mut log = tempfile::NamedTempFile::new().unwrap();
+        let line = serde_json::json!({
+            "type": "assistant",
+            "message": {
+                "id": "msg_1",
+                "model": "claude-opus-5",
+                "usage": {"input_tokens": 100_000, "output_tokens": 10_000}
+            }
+        });
+        writeln!(log, "{line}").unwrap();
+        let mut tracker =
+            TaskTracker::with_source(Provider::Claude, Source::File(log.path().to_path_buf()));
+        tracker.refresh(
+            &TaskSource {
+                provider: Provider::Claude,
+                session_uuid: session_id.to_string(),
+                cwd: std::path::PathBuf::from("/tmp/w"),
+                started_at: chrono::Utc::now(),
+                conversation: None,
+                spawned_at: None,
+                reported: None,
+            },
+            &std::collections::HashSet::new(),
+        );
+        state.system.agent_tasks.insert(session_id, tracker);
+
+        // 100k input at $5/M plus 10k output at $25/M.
+        assert!(screen(&state, 60, 10).contains("$0.75"));
+    }
+
+    #[test]
+    fn an_edit_to_a_claimed_file_is_flagged_on_the_editors_row() {
+        let mut state = state_with_blocked_agent(Attention::Input, "waiting");
+        state.system.agent_status.clear();
+        let session_id = state.data.sessions.values().flatten().next().unwrap().id;
+        state.system.comms.claim_conflicts.insert(
+            session_id,
+            crate::claims::Conflict {
+                session: "aaaa1111".into(),
+                path: "/tmp/w/src/parser.rs".into(),
+                holder: "bbbb2222".into(),
+                at: chrono::Utc::now(),
+            },
+        );
+
+        let out = screen(&state, 60, 10);
+
+        assert!(out.contains("⚠ parser.rs is bbbb2222's"), "{out}");
+    }
+
     #[test]
     fn an_unblocked_agent_keeps_the_ordinary_row() {
         let mut state = state_with_blocked_agent(Attention::Input, "waiting");
@@ -167,6 +167,14 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
                         " EDIT TODO ",
                         "Change the text, Enter to save, Esc to cancel",
                     ),
+                    Some(TaskEdit::Budget) => (
+                        " QUEUE BUDGET ",
+                        "$5/day, 2m tokens/queue, or both — empty clears it, Esc to cancel",
+                    ),
+                    Some(TaskEdit::WorkspaceBudget) => (
+                        " WORKSPACE BUDGET ",
+                        "Shared by every queue here: $20/day, 5m tokens — empty clears it",
+                    ),
                     _ => (
                         " ADD TODO ",
                         "Describe the work, Enter to queue it for this agent, Esc to cancel",
@@ -245,7 +253,10 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
                         .add_modifier(Modifier::BOLD),
                 )],
                 vec![Span::styled(
-                    "Y/Enter: commit & merge  N/Esc: cancel",
+                    format!(
+                        "Y/Enter: {}  Tab: strategy  N/Esc: cancel",
+                        state.ui.merge_strategy.verb().to_lowercase()
+                    ),
                     Style::default().fg(t.fg_dim),
                 )],
             ),
@@ -258,7 +269,23 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
                         .add_modifier(Modifier::BOLD),
                 )],
                 vec![Span::styled(
-                    "Y/Enter: commit & merge  N/Esc: cancel",
+                    format!(
+                        "Y/Enter: {}  Tab: strategy  N/Esc: cancel",
+                        state.ui.merge_strategy.verb().to_lowercase()
+                    ),
+                    Style::default().fg(t.fg_dim),
+                )],
+            ),
+            InputMode::MergeConflict => (
+                vec![Span::styled(
+                    " MERGE CONFLICT ",
+                    Style::default()
+                        .fg(t.on_accent)
+                        .bg(t.error)
+                        .add_modifier(Modifier::BOLD),
+                )],
+                vec![Span::styled(
+                    "O/Enter: hand to agent  F: fresh agent  A: abort  Esc: leave as is",
                     Style::default().fg(t.fg_dim),
                 )],
             ),
@@ -288,6 +315,19 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
                     Style::default().fg(t.fg_dim),
                 )],
             ),
+            InputMode::DiffViewer => (
+                vec![Span::styled(
+                    " DIFF ",
+                    Style::default()
+                        .fg(t.on_accent)
+                        .bg(t.info)
+                        .add_modifier(Modifier::BOLD),
+                )],
+                vec![Span::styled(
+                    "j/k: scroll  n/N: hunk  Tab/[]: file  m: merge  q/Esc: close",
+                    Style::default().fg(t.fg_dim),
+                )],
+            ),
             InputMode::Normal => {
                 // An agent stopped for you outranks key hints: it is the one thing
                 // on screen that is costing time while it goes unread. The agent's
@@ -428,6 +468,16 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
                     status.push(Span::styled(" [`]", Style::default().fg(t.active)));
                 }
 
+                // Estimated spend across every agent, once any has spent.
+                let spent = state.total_cost();
+                if !spent.is_empty() {
+                    status.push(Span::raw(" | "));
+                    status.push(Span::styled(
+                        spent.label(),
+                        crate::tui::utils::spend_style(&spent),
+                    ));
+                }
+
                 // Performance metrics - always visible
                 let fps = state.system.perf.fps();
                 let frame_ms = state.system.perf.frame_time_ms();
@@ -8,7 +8,7 @@
 
 use crate::agent_tasks::TaskState;
 use crate::app::{tasks_view, AppState, FocusPanel, TaskRow, TasksTab};
-use crate::models::TodoState;
+use crate::models::{TodoKind, TodoState};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -40,6 +40,13 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
             },
         ));
     }
+    // Spend against the queue's cap, so a hold never comes as a surprise.
+    if let Some(budget) = budget_line(state) {
+        title_spans.push(Span::styled(
+            format!("{budget} "),
+            Style::default().fg(t.fg_dim),
+        ));
+    }
     // Why nothing is moving, when nothing is moving — a queue that silently
     // sits there is indistinguishable from a broken one.
     if let Some(reason) = holding_reason(state) {
@@ -117,6 +124,23 @@ fn queue_counts(state: &AppState) -> (usize, usize) {
     (left, queue.items.len())
 }
 
+/// `$1.20 of $5.00/day` for the queue's own cap — the one the pane can act
+/// on. A workspace cap still holds the queue, and says so as the reason.
+fn budget_line(state: &AppState) -> Option<String> {
+    let agent = tasks_view::selected_agent(state)?;
+    let budget = state
+        .get_session(agent.session_id)?
+        .todo_queue
+        .budget
+        .as_ref()?;
+    let spent = budget.spent(state.session_cost(agent.session_id).unwrap_or_default());
+    let so_far = match budget.usd {
+        Some(_) => spent.label(),
+        None => format!("{} tok", spent.tokens.total()),
+    };
+    Some(format!("{so_far} of {}", budget.label()))
+}
+
 /// The queue's state, when it is worth saying out loud.
 fn holding_reason(state: &AppState) -> Option<&'static str> {
     use crate::app::todo_dispatch::Holding;
@@ -187,13 +211,14 @@ fn render_action_bar(frame: &mut Frame, area: Rect, state: &AppState, is_focused
     };
 
     let hints: &[(&str, &str)] = if state.ui.selected_tasks_tab == TasksTab::Reports {
-        &[("v", ":view "), ("m", ":merge "), ("h", ":help")]
+        &[("v", ":view "), ("f", ":diff "), ("m", ":merge "), ("r", ":reverify "), ("h", ":help")]
     } else {
         &[
             ("n", ":add "),
             ("e", ":edit "),
             ("d", ":del "),
             ("p", ":pause "),
+            ("b", ":budget "),
             ("J/K", ":move "),
             ("h", ":help"),
         ]
@@ -237,18 +262,34 @@ fn render_row<'a>(
                 TodoState::Pending => Style::default().fg(t.fg),
             };
 
+            // A peer's consult says whose it is, ahead of the question; the
+            // rest of its row reads like your own items.
+            let asker = match &item.kind {
+                TodoKind::Consult { from, .. } => format!("⇄ {from} asks: "),
+                TodoKind::Task => String::new(),
+            };
+            let asker_style = Style::default().fg(t.info);
+
             const INDENT: usize = 4;
-            let lines: Vec<Line> = wrap(&single_line(&item.text), width.saturating_sub(INDENT), 3)
+            let text = format!("{asker}{}", single_line(&item.text));
+            let lines: Vec<Line> = wrap(&text, width.saturating_sub(INDENT), 3)
                 .into_iter()
                 .enumerate()
                 .map(|(i, chunk)| {
                     if i == 0 {
-                        Line::from(vec![
+                        let mut spans = vec![
                             Span::styled(prefix, text_style),
                             Span::styled(icon, Style::default().fg(color)),
                             Span::raw(" "),
-                            Span::styled(chunk, text_style),
-                        ])
+                        ];
+                        match chunk.strip_prefix(asker.as_str()) {
+                            Some(rest) if !asker.is_empty() => {
+                                spans.push(Span::styled(asker.clone(), asker_style));
+                                spans.push(Span::styled(rest.to_string(), text_style));
+                            }
+                            _ => spans.push(Span::styled(chunk, text_style)),
+                        }
+                        Line::from(spans)
                     } else {
                         Line::from(vec![
                             Span::raw(" ".repeat(INDENT)),
@@ -351,6 +392,47 @@ fn wrap(text: &str, width: usize, max_lines: usize) -> Vec<String> {
     lines
 }
 
+/// The verify line under an attempt: running, passed, or failed with the
+/// last few lines of output — usually the assertion that broke.
+fn verification_lines(
+    verification: &crate::models::Verification,
+    state: &AppState,
+) -> Vec<Line<'static>> {
+    let t = crate::theme::current();
+    let Some(outcome) = &verification.outcome else {
+        return vec![Line::from(vec![
+            Span::raw("      "),
+            Span::styled("verify: ", Style::default().fg(t.fg_faint)),
+            Span::styled(
+                format!("{} running {}", state.spinner_char(), verification.command),
+                Style::default().fg(t.active),
+            ),
+        ])];
+    };
+
+    let (icon, color) = if outcome.passed() {
+        ("✓", t.success)
+    } else {
+        ("✗", t.error)
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::raw("      "),
+        Span::styled("verify: ", Style::default().fg(t.fg_faint)),
+        Span::styled(format!("{icon} {}", outcome.summary()), Style::default().fg(color)),
+    ])];
+    if !outcome.passed() {
+        let tail: Vec<&str> = outcome.output_tail.lines().collect();
+        for line in &tail[tail.len().saturating_sub(3)..] {
+            let line: String = line.chars().take(70).collect();
+            lines.push(Line::from(vec![
+                Span::raw("        "),
+                Span::styled(line, Style::default().fg(t.fg_dim)),
+            ]));
+        }
+    }
+    lines
+}
+
 /// Render the Reports tab content showing parallel task attempts
 fn render_reports_tab(frame: &mut Frame, area: Rect, state: &AppState, is_focused: bool) {
     use crate::models::AttemptStatus;
@@ -429,6 +511,13 @@ fn render_reports_tab(frame: &mut Frame, area: Rect, state: &AppState, is_focuse
         Line::from(vec![
             Span::styled("  Source: ", Style::default().fg(t.fg_faint)),
             Span::styled(task.source_branch.clone(), Style::default().fg(t.accent)),
+            Span::styled(
+                task.verify_command
+                    .as_ref()
+                    .map(|cmd| format!("  verify: {cmd}"))
+                    .unwrap_or_default(),
+                Style::default().fg(t.command),
+            ),
         ]),
     ]);
     frame.render_widget(header, header_area);
@@ -441,8 +530,8 @@ fn render_reports_tab(frame: &mut Frame, area: Rect, state: &AppState, is_focuse
     }
 
     let items: Vec<ListItem> = task
-        .attempts
-        .iter()
+        .ranked_attempts()
+        .into_iter()
         .enumerate()
         .map(|(i, attempt)| {
             let is_selected = i == state.ui.parallel_task.selected_report_idx && is_focused;
@@ -488,6 +577,14 @@ fn render_reports_tab(frame: &mut Frame, area: Rect, state: &AppState, is_focuse
 
             // Third line: report preview (if available)
             let mut lines = vec![line1, line2];
+            if let Some(verification) = &attempt.verification {
+                lines.extend(verification_lines(verification, state));
+            } else if task.verify_command.is_some() && attempt.status == AttemptStatus::Completed {
+                lines.push(Line::from(vec![
+                    Span::raw("      "),
+                    Span::styled("verify: waiting its turn", Style::default().fg(t.fg_faint)),
+                ]));
+            }
             if let Some(preview) = attempt.report_preview() {
                 // Truncate preview to fit in available width
                 let max_chars = 60;
@@ -151,11 +151,22 @@ fn create_workspace_item<'a>(
         Span::raw("  ")
     };
 
+    let spent = state.workspace_cost(ws.id);
+    let cost_info = if spent.is_empty() {
+        Span::raw("")
+    } else {
+        Span::styled(
+            format!(" {}", spent.label()),
+            crate::tui::utils::spend_style(&spent),
+        )
+    };
+
     ListItem::new(Line::from(vec![
         Span::styled(prefix.to_string(), style),
         working_indicator,
         Span::styled(name, style),
         Span::styled(time_info, time_style),
+        cost_info,
     ]))
 }
 
@@ -1,4 +1,4 @@
-use crate::app::{Action, AppState, FocusPanel, PendingDelete, TaskEdit, TasksTab};
+use crate::app::{Action, AppState, DiffSubject, FocusPanel, PendingDelete, TaskEdit, TasksTab};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use super::key_modes::handle_input_mode_key;
@@ -225,6 +225,12 @@ impl EventHandler {
                     Action::Tick
                 }
             }
+            KeyCode::Char('f') => match state.selected_session() {
+                Some(session) if session.has_worktree() => {
+                    Action::OpenDiff(DiffSubject::Session(session.id))
+                }
+                _ => Action::Tick,
+            },
             KeyCode::Char('w') => {
                 if let Some(session) = state.selected_session() {
                     if session.has_worktree() {
@@ -280,6 +286,18 @@ impl EventHandler {
             // -- Reports tab (parallel task attempts) --
             KeyCode::Char('v') | KeyCode::Enter if reports => Action::ViewReport,
             KeyCode::Char('m') if reports => Action::MergeSelectedReport,
+            KeyCode::Char('r') if reports => Action::RerunVerification,
+            KeyCode::Char('f') if reports => {
+                if let Some(attempt) = state
+                    .selected_workspace()
+                    .and_then(|ws| ws.active_parallel_task())
+                    .and_then(|t| t.ranked_attempt(state.ui.parallel_task.selected_report_idx))
+                {
+                    Action::OpenDiff(DiffSubject::Attempt(attempt.id))
+                } else {
+                    Action::Tick
+                }
+            }
             KeyCode::Char('d') if reports => {
                 if let Some(task_id) = state
                     .selected_workspace()
@@ -296,6 +314,8 @@ impl EventHandler {
             KeyCode::Enter => Action::FocusSelectedTaskAgent,
             KeyCode::Char('n') => Action::EnterTaskEditMode(TaskEdit::Add),
             KeyCode::Char('e') => Action::EnterTaskEditMode(TaskEdit::Rewrite),
+            KeyCode::Char('b') => Action::EnterTaskEditMode(TaskEdit::Budget),
+            KeyCode::Char('B') => Action::EnterTaskEditMode(TaskEdit::WorkspaceBudget),
             KeyCode::Char('d') => Action::DeleteSelectedTodo,
             KeyCode::Char('p') => Action::ToggleTodoQueuePaused,
             KeyCode::Char('c') => Action::ClearCompletedTodos,
@@ -1,4 +1,4 @@
-use crate::app::{Action, AppState, ConfigTab, InputMode};
+use crate::app::{Action, AppState, ConfigTab, DiffNav, InputMode};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use super::shortcuts::agent_shortcut;
@@ -104,7 +104,9 @@ pub(super) fn handle_input_mode_key(key: &KeyEvent, state: &AppState) -> Option<
             KeyCode::Esc => Action::ExitMode,
             KeyCode::Tab => Action::NextParallelAgent,
             KeyCode::BackTab => Action::PrevParallelAgent,
-            KeyCode::Char('x') => Action::ToggleParallelAgent(state.ui.parallel_task.agent_idx),
+            KeyCode::Char('x') if !state.ui.parallel_task.editing_verify() => {
+                Action::ToggleParallelAgent(state.ui.parallel_task.agent_idx)
+            }
             KeyCode::Enter => Action::StartParallelTask,
             KeyCode::Backspace => Action::InputBackspace,
             KeyCode::Char(c) => Action::InputChar(c),
@@ -115,6 +117,8 @@ pub(super) fn handle_input_mode_key(key: &KeyEvent, state: &AppState) -> Option<
             KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
                 Action::ConfirmMergeWithCommit
             }
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => Action::CycleMergeStrategy(1),
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => Action::CycleMergeStrategy(-1),
             _ => Action::Tick,
         },
         InputMode::ConfirmParallelMerge => match key.code {
@@ -122,6 +126,15 @@ pub(super) fn handle_input_mode_key(key: &KeyEvent, state: &AppState) -> Option<
             KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
                 Action::ConfirmParallelMerge
             }
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => Action::CycleMergeStrategy(1),
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => Action::CycleMergeStrategy(-1),
+            _ => Action::Tick,
+        },
+        InputMode::MergeConflict => match key.code {
+            KeyCode::Enter | KeyCode::Char('o') => Action::ResolveMergeConflict { fresh: false },
+            KeyCode::Char('f') => Action::ResolveMergeConflict { fresh: true },
+            KeyCode::Char('a') => Action::AbortMergeConflict,
+            KeyCode::Esc | KeyCode::Char('q') => Action::DismissMergeConflict,
             _ => Action::Tick,
         },
         InputMode::CommandPalette => match key.code {
@@ -174,6 +187,25 @@ pub(super) fn handle_input_mode_key(key: &KeyEvent, state: &AppState) -> Option<
                 }
             }
         }
+        InputMode::DiffViewer => match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Action::CloseDiff,
+            KeyCode::Char('j') | KeyCode::Down => Action::DiffNavigate(DiffNav::Scroll(1)),
+            KeyCode::Char('k') | KeyCode::Up => Action::DiffNavigate(DiffNav::Scroll(-1)),
+            KeyCode::PageDown | KeyCode::Char(' ') => Action::DiffNavigate(DiffNav::Page(1)),
+            KeyCode::PageUp => Action::DiffNavigate(DiffNav::Page(-1)),
+            KeyCode::Char('n') => Action::DiffNavigate(DiffNav::Hunk(1)),
+            KeyCode::Char('N') | KeyCode::Char('p') => Action::DiffNavigate(DiffNav::Hunk(-1)),
+            KeyCode::Tab | KeyCode::Char(']') | KeyCode::Char('l') | KeyCode::Right => {
+                Action::DiffNavigate(DiffNav::File(1))
+            }
+            KeyCode::BackTab | KeyCode::Char('[') | KeyCode::Char('h') | KeyCode::Left => {
+                Action::DiffNavigate(DiffNav::File(-1))
+            }
+            KeyCode::Char('g') | KeyCode::Home => Action::DiffNavigate(DiffNav::Top),
+            KeyCode::Char('G') | KeyCode::End => Action::DiffNavigate(DiffNav::Bottom),
+            KeyCode::Char('m') => Action::MergeFromDiff,
+            _ => Action::Tick,
+        },
         InputMode::Normal => return None,
     };
 
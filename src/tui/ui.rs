@@ -1,9 +1,10 @@
 use crate::app::{AppState, InputMode};
 use crate::tui::components::{
     banner, command_palette, config_window, create_session_dialog, create_workspace_dialog,
-    debug_overlay, merge_confirm_modal, output_pane, parallel_merge_confirm_modal,
-    parallel_task_modal, pinned_terminal_pane, session_list, status_bar, tasks_pane,
-    utilities_pane, workspace_action_dialog, workspace_list, workspace_name_dialog,
+    debug_overlay, diff_viewer, merge_confirm_modal, merge_conflict_modal, output_pane,
+    parallel_merge_confirm_modal, parallel_task_modal, pinned_terminal_pane, session_list,
+    status_bar, tasks_pane, utilities_pane, workspace_action_dialog, workspace_list,
+    workspace_name_dialog,
 };
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -204,12 +205,18 @@ pub fn draw(frame: &mut Frame, state: &mut AppState) {
         InputMode::ConfirmParallelMerge => {
             parallel_merge_confirm_modal::render(frame, state);
         }
+        InputMode::MergeConflict => {
+            merge_conflict_modal::render(frame, state);
+        }
         InputMode::CommandPalette => {
             command_palette::render(frame, state);
         }
         InputMode::ConfigWindow => {
             config_window::render(frame, state);
         }
+        InputMode::DiffViewer => {
+            diff_viewer::render(frame, state);
+        }
         InputMode::Normal => {}
     }
 
@@ -291,6 +291,21 @@ pub fn convert_vt100_cell_style(cell: &vt100::Cell) -> Style {
     style
 }
 
+/// Spend colours, shared by every place a dollar figure appears: green under
+/// a dollar, amber up to ten, red past that — the point where an agent left
+/// running is worth a look.
+pub fn spend_style(cost: &crate::models::SessionCost) -> Style {
+    let t = crate::theme::current();
+    let usd = cost.estimated_cost_usd;
+    Style::default().fg(if usd < 1.0 {
+        t.success
+    } else if usd <= 10.0 {
+        t.warning
+    } else {
+        t.error
+    })
+}
+
 pub fn convert_vt100_color(color: vt100::Color) -> Color {
     match color {
         vt100::Color::Default => Color::Reset,
@@ -0,0 +1,355 @@
+//! Finding the worktrees and branches nobody owns any more.
+//!
+//! Every agent started in a worktree gets a checkout under `.worktrees/`
+//! and a branch of its own, and both are meant to go when its work is
+//! merged or thrown away. They don't when workbench dies between the two,
+//! when a session is deleted while its worktree is still being made, or
+//! when a parallel task is abandoned instead of cancelled — and nothing
+//! notices: the checkout keeps its disk and the branch turns up in every
+//! `git branch` from then on.
+//!
+//! An orphan is one of ours — under the workspace's `.worktrees/`, or on a
+//! branch named the way workbench names them — that no session and no
+//! parallel attempt in the saved state points at, and that is not a branch
+//! someone chose to keep its work on. Each is reported with
+//! what would go with it: commits the workspace's HEAD doesn't have, their
+//! size, uncommitted changes, the last commit. "Orphaned" is a guess about
+//! who owns it, not about what it is worth; an abandoned attempt may have
+//! been the one that was right. Nothing is removed without being asked,
+//! and commits that exist nowhere else are asked about on their own.
+
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+use crate::git::{self, DiffStat};
+use crate::models::{Session, Workspace};
+
+/// A worktree younger than this is left alone whoever owns it: it may be
+/// one a running workbench is still setting up, not yet in the state file.
+pub const GRACE: Duration = Duration::from_secs(5 * 60);
+
+/// Every checkout and branch the saved state still points at, or was told
+/// to keep.
+#[derive(Debug, Default)]
+pub struct Known {
+    paths: HashSet<PathBuf>,
+    branches: HashSet<String>,
+}
+
+impl Known {
+    pub fn new(workspaces: &[Workspace], sessions: &HashMap<Uuid, Vec<Session>>) -> Self {
+        let mut known = Self::default();
+        for session in sessions.values().flatten() {
+            known.paths.extend(session.worktree_path.as_deref().map(normalize));
+            known.branches.extend(session.worktree_branch.clone());
+        }
+        let attempts = workspaces
+            .iter()
+            .flat_map(|ws| ws.parallel_tasks.iter())
+            .flat_map(|task| task.attempts.iter());
+        for attempt in attempts {
+            known.paths.insert(normalize(&attempt.worktree_path));
+            known.branches.insert(attempt.branch_name.clone());
+        }
+        let kept = workspaces.iter().flat_map(|ws| ws.kept_branches.iter().cloned());
+        known.branches.extend(kept);
+        known
+    }
+
+    fn owns(&self, path: &Path, branch: Option<&str>) -> bool {
+        self.paths.contains(&normalize(path)) || branch.is_some_and(|b| self.branches.contains(b))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrphanKind {
+    /// A checkout git still has, that nothing of ours uses.
+    Worktree,
+    /// Registered with git, but its directory is already gone.
+    Missing,
+    /// One of our branches with no checkout and no owner.
+    Branch,
+}
+
+#[derive(Debug, Clone)]
+pub struct Orphan {
+    pub kind: OrphanKind,
+    pub path: Option<PathBuf>,
+    pub branch: Option<String>,
+    /// Commits on the branch that the workspace's HEAD doesn't have.
+    pub ahead: usize,
+    /// The size of those commits, from where the two diverged.
+    pub stat: DiffStat,
+    /// Uncommitted changes in the checkout.
+    pub dirty: bool,
+    pub last_commit: Option<String>,
+}
+
+impl Orphan {
+    /// What it is, as the workspace would name it.
+    pub fn title(&self, repo_path: &Path) -> String {
+        let path = self
+            .path
+            .as_deref()
+            .map(|p| p.strip_prefix(repo_path).unwrap_or(p).display().to_string());
+        match (self.kind, path, self.branch.as_deref()) {
+            (OrphanKind::Branch, _, Some(branch)) => format!("branch {branch}"),
+            (kind, Some(path), branch) => {
+                let gone = if kind == OrphanKind::Missing { ", directory gone" } else { "" };
+                format!("worktree {path} ({}{gone})", branch.unwrap_or("detached"))
+            }
+            (_, None, branch) => format!("branch {}", branch.unwrap_or("?")),
+        }
+    }
+
+    /// What would be lost with it, and when it was last worked on.
+    pub fn details(&self) -> Vec<String> {
+        let mut lost = Vec::new();
+        if self.ahead > 0 {
+            lost.push(format!(
+                "{} commit{} not on HEAD (+{} −{})",
+                self.ahead,
+                if self.ahead == 1 { "" } else { "s" },
+                self.stat.insertions,
+                self.stat.deletions
+            ));
+        }
+        if self.dirty {
+            lost.push("uncommitted changes".to_string());
+        }
+        let mut lines = vec![if lost.is_empty() {
+            "nothing HEAD doesn't already have".to_string()
+        } else {
+            lost.join(", ")
+        }];
+        lines.extend(self.last_commit.as_ref().map(|c| format!("last commit {c}")));
+        lines
+    }
+}
+
+/// The orphans in one repository. Blocks on git.
+pub fn find_orphans(repo_path: &Path, known: &Known, grace: Duration) -> Result<Vec<Orphan>> {
+    let worktrees_dir = normalize(&git::get_worktrees_dir(repo_path));
+    let entries = git::list_worktrees(repo_path)?;
+    let checked_out: HashSet<&str> = entries.iter().filter_map(|e| e.branch.as_deref()).collect();
+
+    let mut orphans = Vec::new();
+    // The first entry is the workspace's own checkout.
+    for entry in entries.iter().skip(1) {
+        let branch = entry.branch.as_deref();
+        let ours = normalize(&entry.path).starts_with(&worktrees_dir)
+            || branch.is_some_and(git::is_workbench_branch);
+        if !ours || known.owns(&entry.path, branch) {
+            continue;
+        }
+        if !entry.prunable && touched_within(&entry.path, grace) {
+            continue;
+        }
+        let kind = if entry.prunable { OrphanKind::Missing } else { OrphanKind::Worktree };
+        orphans.push(inspect(repo_path, kind, Some(entry.path.clone()), entry.branch.clone()));
+    }
+
+    for branch in git::list_branches(repo_path, &git::BRANCH_PATTERNS)? {
+        if checked_out.contains(branch.as_str()) || known.branches.contains(&branch) {
+            continue;
+        }
+        orphans.push(inspect(repo_path, OrphanKind::Branch, None, Some(branch)));
+    }
+    Ok(orphans)
+}
+
+/// Remove an orphan, and the branch with it when the branch is one of
+/// ours — but a branch with commits HEAD doesn't have only with
+/// `drop_commits`. Those commits are nowhere else, and yes to "remove this
+/// worktree?" is not yes to "lose this work". Blocks on git.
+pub fn remove(repo_path: &Path, orphan: &Orphan, drop_commits: bool) -> Result<()> {
+    match (orphan.kind, orphan.path.as_deref()) {
+        (OrphanKind::Worktree, Some(path)) => {
+            git::remove_worktree(repo_path, path, false)?;
+            // A parallel task's attempts share a `parallel-…` directory;
+            // the last one out takes it too. Not empty: not ours to take.
+            let worktrees_dir = normalize(&git::get_worktrees_dir(repo_path));
+            if let Some(parent) = path.parent().filter(|p| normalize(p) != worktrees_dir) {
+                let _ = std::fs::remove_dir(parent);
+            }
+        }
+        // This entry only: `git worktree prune` would take every other
+        // missing checkout's registration with it, asked about or not.
+        (OrphanKind::Missing, Some(path)) => git::remove_worktree(repo_path, path, false)?,
+        _ => {}
+    }
+    let branch = orphan
+        .branch
+        .as_deref()
+        .filter(|b| git::is_workbench_branch(b))
+        .filter(|_| orphan.ahead == 0 || drop_commits);
+    if let Some(branch) = branch {
+        git::delete_branch_force(repo_path, branch)?;
+    }
+    Ok(())
+}
+
+fn inspect(
+    repo_path: &Path,
+    kind: OrphanKind,
+    path: Option<PathBuf>,
+    branch: Option<String>,
+) -> Orphan {
+    let dirty =
+        kind == OrphanKind::Worktree && path.as_deref().is_some_and(git::worktree_has_changes);
+    let (ahead, stat, last_commit) = match branch.as_deref() {
+        Some(branch) => (
+            git::commits_ahead(repo_path, "HEAD", branch),
+            git::get_diff_shortstat(repo_path, Some(&format!("HEAD...{branch}"))),
+            git::last_commit(repo_path, branch),
+        ),
+        // Detached: nothing to compare by name, but the checkout still
+        // says where it stopped.
+        None => (
+            0,
+            DiffStat::default(),
+            path.as_deref()
+                .filter(|_| kind == OrphanKind::Worktree)
+                .and_then(|p| git::last_commit(p, "HEAD")),
+        ),
+    };
+    Orphan {
+        kind,
+        path,
+        branch,
+        ahead,
+        stat,
+        dirty,
+        last_commit,
+    }
+}
+
+fn touched_within(path: &Path, grace: Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < grace)
+}
+
+/// Git reports worktrees by their real path; the state file has whatever
+/// path the workspace was added with. Compare them resolved.
+fn normalize(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AgentType;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let out = Command::new("git").args(args).current_dir(dir).output().unwrap();
+        assert!(out.status.success(), "git {args:?}: {out:?}");
+    }
+
+    /// A session's worktree is kept; a worktree and a branch nobody points
+    /// at are reported with their unmerged commit and removed; a branch of
+    /// the user's own is never looked at.
+    #[test]
+    fn only_our_unowned_worktrees_and_branches_are_orphans() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = dir.path();
+        git(repo, &["init"]);
+        git(repo, &["config", "user.email", "test@test.com"]);
+        git(repo, &["config", "user.name", "Test"]);
+        std::fs::write(repo.join("a.txt"), "a\n").unwrap();
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-m", "initial"]);
+        git(repo, &["branch", "feature"]);
+        git(repo, &["branch", "agent-codex-dead"]);
+
+        let owned = git::get_session_worktree_path(repo, "live");
+        let stray = git::get_attempt_worktree_path(repo, "gone", "claude");
+        git::create_worktree(repo, "agent-claude-live", &owned).unwrap();
+        git::create_worktree(repo, "parallel-gone/claude", &stray).unwrap();
+        std::fs::write(stray.join("b.txt"), "b\n").unwrap();
+        git(&stray, &["add", "."]);
+        git(&stray, &["commit", "-m", "Add b"]);
+
+        let mut session = Session::new(Uuid::new_v4(), AgentType::Claude, false);
+        session.worktree_path = Some(owned.clone());
+        session.worktree_branch = Some("agent-claude-live".into());
+        let sessions = HashMap::from([(session.workspace_id, vec![session])]);
+        let known = Known::new(&[], &sessions);
+
+        assert!(find_orphans(repo, &known, GRACE).unwrap().len() == 1, "fresh ones wait");
+        let orphans = find_orphans(repo, &known, Duration::ZERO).unwrap();
+        let titles: Vec<String> = orphans.iter().map(|o| o.title(repo)).collect();
+        assert_eq!(
+            titles,
+            [
+                "worktree .worktrees/parallel-gone/claude (parallel-gone/claude)",
+                "branch agent-codex-dead",
+            ]
+        );
+        assert_eq!(orphans[0].ahead, 1);
+        assert!(orphans[0].details()[1].contains("Add b"));
+        assert_eq!(orphans[1].details()[0], "nothing HEAD doesn't already have");
+
+        for orphan in &orphans {
+            remove(repo, orphan, true).unwrap();
+        }
+        assert!(find_orphans(repo, &known, Duration::ZERO).unwrap().is_empty());
+        assert!(!repo.join(".worktrees/parallel-gone").exists());
+        assert!(owned.exists());
+        assert_eq!(git::list_branches(repo, &["feature"]).unwrap(), ["feature"]);
+    }
+
+    /// Removing a checkout is not permission to lose its commits, and
+    /// clearing one missing checkout's registration leaves the others'.
+    #[test]
+    fn unmerged_commits_and_other_missing_checkouts_survive_a_removal() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = dir.path();
+        git(repo, &["init"]);
+        git(repo, &["config", "user.email", "test@test.com"]);
+        git(repo, &["config", "user.name", "Test"]);
+        std::fs::write(repo.join("a.txt"), "a\n").unwrap();
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-m", "initial"]);
+
+        let worked = git::get_session_worktree_path(repo, "worked");
+        let gone = git::get_session_worktree_path(repo, "gone");
+        let also_gone = git::get_session_worktree_path(repo, "alsogone");
+        git::create_worktree(repo, "agent-claude-worked", &worked).unwrap();
+        git::create_worktree(repo, "agent-claude-gone", &gone).unwrap();
+        git::create_worktree(repo, "agent-claude-alsogone", &also_gone).unwrap();
+        std::fs::write(worked.join("b.txt"), "b\n").unwrap();
+        git(&worked, &["add", "."]);
+        git(&worked, &["commit", "-m", "Add b"]);
+        std::fs::remove_dir_all(&gone).unwrap();
+        std::fs::remove_dir_all(&also_gone).unwrap();
+
+        let known = Known::default();
+        let orphans = find_orphans(repo, &known, Duration::ZERO).unwrap();
+        let find = |branch: &str| {
+            orphans.iter().find(|o| o.branch.as_deref() == Some(branch)).unwrap()
+        };
+        remove(repo, find("agent-claude-worked"), false).unwrap();
+        remove(repo, find("agent-claude-gone"), false).unwrap();
+
+        assert!(!worked.exists());
+        let branches = git::list_branches(repo, &git::BRANCH_PATTERNS).unwrap();
+        assert!(branches.contains(&"agent-claude-worked".to_string()), "{branches:?}");
+        assert!(!branches.contains(&"agent-claude-gone".to_string()), "nothing to lose");
+        let left = find_orphans(repo, &known, Duration::ZERO).unwrap();
+        let kinds: Vec<_> = left.iter().map(|o| (o.kind, o.branch.clone().unwrap())).collect();
+        assert_eq!(
+            kinds,
+            [
+                (OrphanKind::Missing, "agent-claude-alsogone".to_string()),
+                (OrphanKind::Branch, "agent-claude-worked".to_string()),
+            ]
+        );
+    }
+}